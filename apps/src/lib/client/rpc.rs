@@ -50,6 +50,7 @@ use namada::types::transaction::{
     WrapperTx,
 };
 use namada::types::{storage, token};
+use rust_decimal::Decimal;
 use tokio::time::{Duration, Instant};
 
 use crate::cli::{self, args, Context};
@@ -1382,6 +1383,10 @@ pub async fn query_protocol_parameters(
         "{:4}Block vote reward: {}",
         "", pos_params.block_vote_reward
     );
+    println!(
+        "{:4}Proposer timing bonus: {}",
+        "", pos_params.proposer_timing_bonus
+    );
     println!(
         "{:4}Duplicate vote minimum slash rate: {}",
         "", pos_params.duplicate_vote_min_slash_rate
@@ -1390,6 +1395,18 @@ pub async fn query_protocol_parameters(
         "{:4}Light client attack minimum slash rate: {}",
         "", pos_params.light_client_attack_min_slash_rate
     );
+    println!(
+        "{:4}Multisig bond timeout epochs: {}",
+        "", pos_params.multisig_timeout_epochs
+    );
+    println!(
+        "{:4}Commission rate change notice epochs: {}",
+        "", pos_params.commission_change_notice_epochs
+    );
+    println!(
+        "{:4}Beacon epoch interval: {}",
+        "", pos_params.beacon_epoch_interval
+    );
     println!(
         "{:4}Max. validator slots: {}",
         "", pos_params.max_validator_slots
@@ -1499,7 +1516,7 @@ pub async fn query_bonds(
     for (bond_id, details) in bonds_and_unbonds {
         let mut total: token::Amount = 0.into();
         let mut total_slashed: token::Amount = 0.into();
-        let bond_type = if bond_id.source == bond_id.validator {
+        let bond_type = if bond_id.is_self_bond() {
             format!("Self-bonds from {}", bond_id.validator)
         } else {
             format!(
@@ -1533,7 +1550,7 @@ pub async fn query_bonds(
         if !details.unbonds.is_empty() {
             let mut total: token::Amount = 0.into();
             let mut total_slashed: token::Amount = 0.into();
-            let bond_type = if bond_id.source == bond_id.validator {
+            let bond_type = if bond_id.is_self_bond() {
                 format!("Unbonded self-bonds from {}", bond_id.validator)
             } else {
                 format!("Unbonded delegations from {}", bond_id.source)
@@ -2392,6 +2409,7 @@ pub async fn get_proposal_offline_votes(
                     bonds,
                     unbonds: _,
                     slashes: _,
+                    validator_state: _,
                 },
             ) in bonds_and_unbonds
             {
@@ -2589,6 +2607,12 @@ pub async fn get_governance_parameters(client: &HttpClient) -> GovParams {
         .await
         .expect("Parameter should be definied.");
 
+    let key = gov_storage::get_max_pos_params_change_rate_key();
+    let max_pos_params_change_rate =
+        query_storage_value::<Decimal>(client, &key)
+            .await
+            .expect("Parameter should be definied.");
+
     GovParams {
         min_proposal_fund: u64::from(min_proposal_fund),
         max_proposal_code_size,
@@ -2596,6 +2620,7 @@ pub async fn get_governance_parameters(client: &HttpClient) -> GovParams {
         max_proposal_period,
         max_proposal_content_size,
         min_proposal_grace_epochs,
+        max_pos_params_change_rate,
     }
 }
 
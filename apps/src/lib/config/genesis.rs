@@ -159,6 +159,8 @@ pub mod genesis_config {
         // Minimum number of epoch between end and grace epoch
         // XXX: u64 doesn't work with toml-rs!
         pub min_proposal_grace_epochs: u64,
+        // Maximum relative change of any PoS parameter in a single proposal
+        pub max_pos_params_change_rate: Decimal,
     }
 
     /// Validator pre-genesis configuration can be created with client utils
@@ -292,6 +294,9 @@ pub mod genesis_config {
         // Reward for voting on a block.
         // XXX: u64 doesn't work with toml-rs!
         pub block_vote_reward: Decimal,
+        // Reduction of the proposer reward per consensus round.
+        // XXX: u64 doesn't work with toml-rs!
+        pub proposer_timing_bonus: Decimal,
         // Maximum staking APY
         // XXX: u64 doesn't work with toml-rs!
         pub max_inflation_rate: Decimal,
@@ -305,6 +310,16 @@ pub mod genesis_config {
         // light client attack.
         // XXX: u64 doesn't work with toml-rs!
         pub light_client_attack_min_slash_rate: Decimal,
+        // Number of epochs within which a multisig bond must be approved.
+        // XXX: u64 doesn't work with toml-rs!
+        pub multisig_timeout_epochs: u64,
+        // Minimum number of epochs between the announcement of a commission
+        // rate change and the change itself.
+        // XXX: u64 doesn't work with toml-rs!
+        pub commission_change_notice_epochs: u64,
+        // Number of epochs between two beacon epochs.
+        // XXX: u64 doesn't work with toml-rs!
+        pub beacon_epoch_interval: u64,
     }
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -626,6 +641,7 @@ pub mod genesis_config {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             max_proposal_period,
+            max_pos_params_change_rate,
         } = gov_params;
         let gov_params = GovParams {
             min_proposal_fund,
@@ -634,6 +650,7 @@ pub mod genesis_config {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             max_proposal_period,
+            max_pos_params_change_rate,
         };
 
         let PosParamsConfig {
@@ -643,10 +660,14 @@ pub mod genesis_config {
             tm_votes_per_token,
             block_proposer_reward,
             block_vote_reward,
+            proposer_timing_bonus,
             max_inflation_rate,
             target_staked_ratio,
             duplicate_vote_min_slash_rate,
             light_client_attack_min_slash_rate,
+            multisig_timeout_epochs,
+            commission_change_notice_epochs,
+            beacon_epoch_interval,
         } = pos_params;
         let pos_params = PosParams {
             max_validator_slots,
@@ -655,10 +676,14 @@ pub mod genesis_config {
             tm_votes_per_token,
             block_proposer_reward,
             block_vote_reward,
+            proposer_timing_bonus,
             max_inflation_rate,
             target_staked_ratio,
             duplicate_vote_min_slash_rate,
             light_client_attack_min_slash_rate,
+            multisig_timeout_epochs,
+            commission_change_notice_epochs,
+            beacon_epoch_interval,
         };
 
         let mut genesis = Genesis {
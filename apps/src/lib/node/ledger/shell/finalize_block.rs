@@ -1,26 +1,31 @@
 //! Implementation of the `FinalizeBlock` ABCI++ method for the Shell
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use data_encoding::HEXUPPER;
 use namada::ledger::parameters::storage as params_storage;
 use namada::ledger::pos::types::{decimal_mult_u64, into_tm_voting_power};
-use namada::ledger::pos::{namada_proof_of_stake, staking_token_address};
+use namada::ledger::pos::{
+    namada_proof_of_stake, staking_token_address, BondId, PosEvent,
+};
 use namada::ledger::storage::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada::ledger::storage_api::token::credit_tokens;
 use namada::ledger::storage_api::{StorageRead, StorageWrite};
 use namada::ledger::{inflation, protocol, replay_protection};
 use namada::proof_of_stake::{
     delegator_rewards_products_handle, find_validator_by_raw_hash,
-    read_last_block_proposer_address, read_pos_params, read_total_stake,
-    read_validator_stake, rewards_accumulator_handle,
-    validator_commission_rate_handle, validator_rewards_products_handle,
-    write_last_block_proposer_address,
+    read_consensus_validator_set_addresses_with_stake,
+    read_last_block_proposer_address, read_total_stake, read_validator_stake,
+    reset_rewards_accumulator, rewards_accumulator_handle,
+    save_rewards_accumulator_snapshot, validator_commission_rate_handle,
+    validator_rewards_products_handle, write_last_block_proposer_address,
 };
+use namada::proto::SignedTxData;
 use namada::types::address::Address;
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::types::token::{total_supply_key, Amount};
+use namada::types::transaction;
 use rust_decimal::prelude::Decimal;
 
 use super::governance::execute_governance_proposals;
@@ -31,6 +36,9 @@ use crate::facade::tendermint_proto::abci::{
 use crate::facade::tendermint_proto::crypto::PublicKey as TendermintPublicKey;
 use crate::node::ledger::shell::stats::InternalStats;
 
+/// Number of blocks between logs of the wasm compilation cache statistics
+const WASM_CACHE_STATS_LOG_INTERVAL: u64 = 100;
+
 impl<D, H> Shell<D, H>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -85,13 +93,16 @@ where
                 &mut self.wl_storage,
             )?;
 
+            // The PoS parameters may change on a new epoch (e.g. via a
+            // governance proposal)
+            self.clear_pos_params_cache();
             let _proposals_result =
                 execute_governance_proposals(self, &mut response)?;
+            self.clear_pos_params_cache();
 
             // Copy the new_epoch + pipeline_len - 1 validator set into
             // new_epoch + pipeline_len
-            let pos_params =
-                namada_proof_of_stake::read_pos_params(&self.wl_storage)?;
+            let pos_params = self.get_pos_params();
             namada_proof_of_stake::copy_validator_sets_and_positions(
                 &mut self.wl_storage,
                 current_epoch,
@@ -99,6 +110,51 @@ where
                 &namada_proof_of_stake::consensus_validator_set_handle(),
                 &namada_proof_of_stake::below_capacity_validator_set_handle(),
             )?;
+
+            // Return the tokens of the multisig bonds that have not been
+            // approved in time
+            namada_proof_of_stake::remove_expired_multisig_bonds(
+                &mut self.wl_storage,
+                current_epoch,
+            )?;
+
+            // Refresh the bond statistics for monitoring
+            namada_proof_of_stake::update_bonds_summary(
+                &mut self.wl_storage,
+                current_epoch,
+            )?;
+
+            // Cache the total amount of tokens that become withdrawable in
+            // the last epoch that can no longer receive new unbonds
+            namada_proof_of_stake::update_total_unbonding(
+                &mut self.wl_storage,
+                &pos_params,
+                current_epoch,
+            )?;
+
+            // Index the bonds that precede the delegator validator index and
+            // drop the entries of the bonds that are now fully unbonded
+            namada_proof_of_stake::migrate_delegator_validator_index(
+                &mut self.wl_storage,
+            )?;
+            namada_proof_of_stake::prune_delegator_validator_index(
+                &mut self.wl_storage,
+                current_epoch,
+            )?;
+
+            // Drop the block votes that are too old to be used for evidence
+            namada_proof_of_stake::prune_epoch_votes(
+                &mut self.wl_storage,
+                &pos_params,
+                current_epoch,
+            )?;
+
+            // Take a snapshot of the PoS system if this is a beacon epoch
+            namada_proof_of_stake::write_beacon_snapshot(
+                &mut self.wl_storage,
+                &pos_params,
+                current_epoch,
+            )?;
         }
 
         // Invariant: This has to be applied after
@@ -328,6 +384,36 @@ where
                 }
             };
 
+            // Don't apply any more txs once the gas accumulated by the
+            // previous txs in this block has reached the block gas limit
+            if self.gas_meter.is_block_gas_exhausted() {
+                tracing::info!(
+                    "Block gas limit reached, transaction {} is not applied",
+                    tx_event["hash"]
+                );
+                stats.increment_errored_txs();
+                // Allow to rewrap the transaction
+                if let Some(hash) = tx_unsigned_hash {
+                    let tx_hash_key = replay_protection::get_tx_hash_key(&hash);
+                    self.wl_storage.storage.delete(&tx_hash_key).expect(
+                        "Error while deleting tx hash key from storage",
+                    );
+                }
+                tx_event["gas_used"] = "0".into();
+                tx_event["info"] = Error::GasOverflow.to_string();
+                tx_event["code"] = ErrorCodes::WasmRuntimeError.into();
+                response.events.push(tx_event);
+                continue;
+            }
+
+            // Keep the inner tx data to find the PoS events of the tx
+            let inner_tx_data = match &tx_type {
+                TxType::Decrypted(DecryptedTx::Decrypted { tx, .. }) => {
+                    tx.data.clone()
+                }
+                _ => None,
+            };
+
             match protocol::apply_tx(
                 tx_type,
                 tx_length,
@@ -341,6 +427,7 @@ where
                 &self.wl_storage.storage,
                 &mut self.vp_wasm_cache,
                 &mut self.tx_wasm_cache,
+                false,
             )
             .map_err(Error::TxApply)
             {
@@ -367,6 +454,24 @@ where
                             let event = Event::from(ibc_event.clone());
                             response.events.push(event);
                         }
+                        if !result.pos_events.is_empty() {
+                            // Add the PoS events emitted by the tx besides
+                            // the tx_event
+                            response.events.extend(
+                                result
+                                    .pos_events
+                                    .iter()
+                                    .cloned()
+                                    .map(Event::from),
+                            );
+                        } else if let Some(pos_event) =
+                            inner_tx_data.as_ref().and_then(|data| {
+                                self.get_pos_event(data, &result.changed_keys)
+                            })
+                        {
+                            // Add the PoS event besides the tx_event
+                            response.events.push(Event::from(pos_event));
+                        }
                         match serde_json::to_string(
                             &result.initialized_accounts,
                         ) {
@@ -458,14 +563,23 @@ where
                     "Found last block proposer: {proposer_address}"
                 );
                 let votes = pos_votes_from_abci(&self.wl_storage, &req.votes);
+                let votes_epoch = if new_epoch {
+                    current_epoch.prev()
+                } else {
+                    current_epoch
+                };
+                // The votes were cast for the previous block
+                namada_proof_of_stake::record_block_votes(
+                    &mut self.wl_storage,
+                    votes_epoch,
+                    height.0 - 1,
+                    &votes,
+                )?;
                 namada_proof_of_stake::log_block_rewards(
                     &mut self.wl_storage,
-                    if new_epoch {
-                        current_epoch.prev()
-                    } else {
-                        current_epoch
-                    },
+                    votes_epoch,
                     &proposer_address,
+                    req.round,
                     votes,
                 )?;
             }
@@ -498,6 +612,22 @@ where
                 "Unable to find native validator address of block proposer \
                  from tendermint raw hash",
             );
+            let is_valid_proposer = self.is_valid_block_proposer(
+                &native_proposer_address,
+                current_epoch,
+            )?;
+            if !is_valid_proposer {
+                tracing::warn!(
+                    "The block proposer {native_proposer_address} is not a \
+                     consensus validator with non-zero voting power in epoch \
+                     {current_epoch}"
+                );
+            }
+            debug_assert!(
+                is_valid_proposer,
+                "The block proposer must be a consensus validator with \
+                 non-zero voting power"
+            );
             write_last_block_proposer_address(
                 &mut self.wl_storage,
                 native_proposer_address,
@@ -509,12 +639,107 @@ where
             .finalize_transaction()
             .map_err(|_| Error::GasOverflow)?;
 
+        // Catch any PoS token leaks in tests
+        #[cfg(test)]
+        namada_proof_of_stake::verify_pos_balance_invariant(
+            &self.wl_storage,
+            &self.get_pos_params(),
+            current_epoch,
+        )
+        .expect("The PoS balance invariant must hold");
+
+        if height.0 % WASM_CACHE_STATS_LOG_INTERVAL == 0 {
+            tracing::info!(
+                "Wasm compilation cache statistics: {:?}",
+                self.wasm_cache_stats()
+            );
+        }
+
         self.event_log_mut().log_events(response.events.clone());
         tracing::debug!("End finalize_block {height} of epoch {current_epoch}");
 
         Ok(response)
     }
 
+    /// Check that the given block proposer is in the consensus validator set
+    /// with non-zero voting power. Around an epoch boundary Tendermint may
+    /// still be using the validator set of the previous epoch, so that set is
+    /// also accepted.
+    fn is_valid_block_proposer(
+        &self,
+        proposer: &Address,
+        current_epoch: Epoch,
+    ) -> Result<bool> {
+        let params = self.get_pos_params();
+        let mut epochs = vec![current_epoch];
+        if current_epoch > Epoch::default() {
+            epochs.push(current_epoch.prev());
+        }
+        for epoch in epochs {
+            let consensus_validators =
+                read_consensus_validator_set_addresses_with_stake(
+                    &self.wl_storage,
+                    epoch,
+                )?;
+            let is_valid = consensus_validators.iter().any(|validator| {
+                &validator.address == proposer
+                    && into_tm_voting_power(
+                        params.tm_votes_per_token,
+                        validator.bonded_stake,
+                    ) > 0
+            });
+            if is_valid {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Find the PoS event of an applied tx, if it's a bond or an unbond. The
+    /// bond and unbond txs carry the same data, so they are told apart by the
+    /// storage keys that the tx has changed.
+    fn get_pos_event(
+        &self,
+        tx_data: &[u8],
+        changed_keys: &BTreeSet<Key>,
+    ) -> Option<PosEvent> {
+        let signed = SignedTxData::try_from_slice(tx_data).ok()?;
+        let bond =
+            transaction::pos::Bond::try_from_slice(&signed.data?).ok()?;
+        let bond_id = BondId {
+            source: bond.source.unwrap_or_else(|| bond.validator.clone()),
+            validator: bond.validator,
+        };
+        let withdraw_epoch = changed_keys
+            .iter()
+            .filter_map(|key| match pos::is_unbond_key(key) {
+                Some((id, _start, withdraw)) if id == bond_id => Some(withdraw),
+                _ => None,
+            })
+            .max();
+        if let Some(withdraw_epoch) = withdraw_epoch {
+            return Some(PosEvent::Unbond {
+                source: bond_id.source,
+                validator: bond_id.validator,
+                amount: bond.amount,
+                withdraw_epoch,
+            });
+        }
+        let epoch = changed_keys
+            .iter()
+            .filter_map(|key| match pos::is_bond_key(key) {
+                Some((id, epoch)) if id == bond_id => Some(epoch),
+                _ => None,
+            })
+            .max()?;
+        Some(PosEvent::Bond {
+            source: bond_id.source,
+            validator: bond_id.validator,
+            amount: bond.amount,
+            epoch,
+        })
+    }
+
     /// Sets the metadata necessary for a new block, including
     /// the hash, height, validator changes, and evidence of
     /// byzantine behavior. Applies slashes if necessary.
@@ -555,9 +780,7 @@ where
     fn update_epoch(&self, response: &mut shim::response::FinalizeBlock) {
         // Apply validator set update
         let (current_epoch, _gas) = self.wl_storage.storage.get_current_epoch();
-        let pos_params =
-            namada_proof_of_stake::read_pos_params(&self.wl_storage)
-                .expect("Could not find the PoS parameters");
+        let pos_params = self.get_pos_params();
         // TODO ABCI validator updates on block H affects the validator set
         // on block H+2, do we need to update a block earlier?
         response.validator_updates =
@@ -569,14 +792,9 @@ where
                     let (consensus_key, power) = match update {
                         ValidatorSetUpdate::Consensus(ConsensusValidator {
                             consensus_key,
-                            bonded_stake,
-                        }) => {
-                            let power: i64 = into_tm_voting_power(
-                                pos_params.tm_votes_per_token,
-                                bonded_stake,
-                            );
-                            (consensus_key, power)
-                        }
+                            weight,
+                            ..
+                        }) => (consensus_key, weight),
                         ValidatorSetUpdate::Deactivated(consensus_key) => {
                             // Any validators that have been dropped from the
                             // consensus set must have voting power set to 0 to
@@ -606,7 +824,7 @@ where
         //
         // MASP is included below just for some completeness.
 
-        let params = read_pos_params(&self.wl_storage)?;
+        let params = self.get_pos_params();
 
         // Read from Parameters storage
         let epochs_per_year: u64 = self
@@ -796,16 +1014,10 @@ where
             .write(&params_storage::get_staked_ratio_key(), locked_ratio)
             .expect("unable to write new locked ratio");
 
-        // Delete the accumulators from storage
-        // TODO: refactor with https://github.com/anoma/namada/issues/1225
-        let addresses_to_drop: HashSet<Address> = rewards_accumulator_handle()
-            .iter(&self.wl_storage)?
-            .map(|a| a.unwrap().0)
-            .collect();
-        for address in addresses_to_drop.into_iter() {
-            rewards_accumulator_handle()
-                .remove(&mut self.wl_storage, &address)?;
-        }
+        // Keep a snapshot of the last epoch's accumulators and then delete
+        // them from storage, before any rewards are logged for the new epoch
+        save_rewards_accumulator_snapshot(&mut self.wl_storage, last_epoch)?;
+        reset_rewards_accumulator(&mut self.wl_storage)?;
 
         Ok(())
     }
@@ -893,7 +1105,7 @@ mod test_finalize_block {
     use namada::proof_of_stake::btree_set::BTreeSetShims;
     use namada::proof_of_stake::types::WeightedValidator;
     use namada::proof_of_stake::{
-        read_consensus_validator_set_addresses_with_stake,
+        read_consensus_validator_set_addresses_with_stake, read_pos_params,
         rewards_accumulator_handle, validator_consensus_key_handle,
         validator_rewards_products_handle,
     };
@@ -7,17 +7,44 @@ use namada::ledger::governance::{
 use namada::ledger::native_vp::governance::utils::{
     compute_tally, get_proposal_votes, ProposalEvent,
 };
+use namada::ledger::pos::PosParams;
 use namada::ledger::protocol;
 use namada::ledger::storage::types::encode;
 use namada::ledger::storage::{DBIter, StorageHasher, DB};
 use namada::ledger::storage_api::{token, StorageWrite};
-use namada::proof_of_stake::read_total_stake;
+use namada::proof_of_stake::parameters::ValidationError;
+use namada::proof_of_stake::{
+    read_blocks_per_epoch, read_consensus_validator_set_addresses,
+    read_total_stake, write_pos_params,
+};
 use namada::types::address::Address;
 use namada::types::governance::{Council, Tally, TallyResult, VotePower};
 use namada::types::storage::Epoch;
+use rust_decimal::Decimal;
 
 use super::*;
 
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum ParamChangeError {
+    #[error("Pipeline length cannot be decreased. Got old: {0}, new: {1}")]
+    PipelineLenDecreased(u64, u64),
+    #[error(
+        "Unbonding length must be >= pipeline length. Got unbonding: {0}, \
+         pipeline: {1}"
+    )]
+    UnbondingLenTooShort(u64, u64),
+    #[error("Invalid PoS parameters change: {0:?}")]
+    InvalidChange(Vec<ValidationError>),
+    #[error("Invalid PoS parameters: {0:?}")]
+    InvalidParams(Vec<ValidationError>),
+    #[error(
+        "Max validator slots cannot be less than the number of consensus \
+         validators. Got slots: {0}, consensus validators: {1}"
+    )]
+    TooFewValidatorSlots(u64, u64),
+}
+
 #[derive(Default)]
 pub struct ProposalsResult {
     passed: Vec<u64>,
@@ -76,7 +103,11 @@ where
         let transfer_address = match tally_result {
             TallyResult::Passed(tally) => {
                 let (successful_execution, proposal_event) = match tally {
-                    Tally::Default => execute_default_proposal(shell, id),
+                    Tally::Default => execute_default_proposal(
+                        shell,
+                        id,
+                        &mut response.events,
+                    ),
                     Tally::PGFCouncil(council) => {
                         execute_pgf_proposal(id, council)
                     }
@@ -135,9 +166,124 @@ where
     Ok(proposals_result)
 }
 
+/// Validate a change of the PoS parameters applied by a governance proposal.
+/// The pipeline length can only increase, the unbonding length must not be
+/// shorter than the pipeline length and no parameter can change by more than
+/// `max_change_rate` of its old value, as checked by
+/// [`PosParams::validate_change`].
+pub fn validate_pos_params_proposal(
+    old: &PosParams,
+    new: &PosParams,
+    max_change_rate: Decimal,
+) -> std::result::Result<(), ParamChangeError> {
+    if new.pipeline_len < old.pipeline_len {
+        return Err(ParamChangeError::PipelineLenDecreased(
+            old.pipeline_len,
+            new.pipeline_len,
+        ));
+    }
+    if new.unbonding_len < new.pipeline_len {
+        return Err(ParamChangeError::UnbondingLenTooShort(
+            new.unbonding_len,
+            new.pipeline_len,
+        ));
+    }
+    let errors = old.validate_change(new, max_change_rate);
+    if !errors.is_empty() {
+        return Err(ParamChangeError::InvalidChange(errors));
+    }
+    Ok(())
+}
+
+/// Read the maximum rate of change of the PoS parameters in a single
+/// governance proposal.
+fn read_max_pos_params_change_rate<S>(
+    storage: &S,
+) -> storage_api::Result<Decimal>
+where
+    S: StorageRead,
+{
+    storage
+        .read(&gov_storage::get_max_pos_params_change_rate_key())?
+        .ok_or_else(|| {
+            storage_api::Error::new_const(
+                "The maximum PoS parameters change rate must be in storage",
+            )
+        })
+}
+
+/// Apply a change of the PoS parameters from a governance proposal. The
+/// change is validated against the old parameters and the validator set at the
+/// proposal epoch before the new parameters are written. When it's invalid,
+/// nothing is written and the validation error is returned.
+pub fn execute_pos_params_change<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    old_params: &PosParams,
+    new_params: PosParams,
+    proposal_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    if let Err(err) = validate_pos_params_change(
+        wl_storage,
+        old_params,
+        &new_params,
+        proposal_epoch,
+    ) {
+        let blocks_per_epoch = read_blocks_per_epoch(wl_storage)?;
+        tracing::info!(
+            "Rejected PoS parameters change at {}: {err}",
+            proposal_epoch.format_with_block(blocks_per_epoch)
+        );
+        return Err(err);
+    }
+    write_pos_params(wl_storage, new_params)
+}
+
+/// Validate the PoS parameters change applied by
+/// [`execute_pos_params_change`] against the old parameters and the validator
+/// set at the proposal epoch.
+fn validate_pos_params_change<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    old_params: &PosParams,
+    new_params: &PosParams,
+    proposal_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let errors = new_params.validate();
+    if !errors.is_empty() {
+        return Err(storage_api::Error::new(ParamChangeError::InvalidParams(
+            errors,
+        )));
+    }
+    let max_change_rate = read_max_pos_params_change_rate(wl_storage)?;
+    validate_pos_params_proposal(old_params, new_params, max_change_rate)
+        .map_err(storage_api::Error::new)?;
+    let num_consensus_validators =
+        read_consensus_validator_set_addresses(wl_storage, proposal_epoch)?
+            .len() as u64;
+    if new_params.max_validator_slots < num_consensus_validators {
+        return Err(storage_api::Error::new(
+            ParamChangeError::TooFewValidatorSlots(
+                new_params.max_validator_slots,
+                num_consensus_validators,
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Execute the code of an accepted proposal. The PoS events emitted by the
+/// proposal's code are added to the given `events`.
 fn execute_default_proposal<D, H>(
     shell: &mut Shell<D, H>,
     id: u64,
+    events: &mut Vec<Event>,
 ) -> (bool, Event)
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -158,6 +304,8 @@ where
                 #[cfg(not(feature = "mainnet"))]
                 has_valid_pow: false,
             });
+            let old_pos_params = read_pos_params(&shell.wl_storage)
+                .expect("PoS parameters should be in storage");
             let pending_execution_key =
                 gov_storage::get_proposal_execution_key(id);
             shell
@@ -175,15 +323,55 @@ where
                 &shell.wl_storage.storage,
                 &mut shell.vp_wasm_cache,
                 &mut shell.tx_wasm_cache,
+                // Only the proposal's acceptance matters, not its gas cost
+                true,
             );
             shell
                 .wl_storage
                 .storage
                 .delete(&pending_execution_key)
                 .expect("Should be able to delete the storage.");
+            // Reject any invalid change of the PoS parameters
+            let is_valid_pos_params_change =
+                match read_pos_params(&shell.wl_storage) {
+                    Ok(new_pos_params) if new_pos_params == old_pos_params => {
+                        true
+                    }
+                    Ok(new_pos_params) => {
+                        let current_epoch =
+                            shell.wl_storage.storage.block.epoch;
+                        match execute_pos_params_change(
+                            &mut shell.wl_storage,
+                            &old_pos_params,
+                            new_pos_params,
+                            current_epoch,
+                        ) {
+                            Ok(()) => true,
+                            Err(err) => {
+                                tracing::info!(
+                                    "Proposal {id} has an invalid PoS \
+                                     parameters change: {err}"
+                                );
+                                false
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        tracing::info!(
+                            "Proposal {id} has invalid PoS parameters: {err}"
+                        );
+                        false
+                    }
+                };
             match tx_result {
-                Ok(tx_result) if tx_result.is_accepted() => {
+                Ok(tx_result)
+                    if tx_result.is_accepted()
+                        && is_valid_pos_params_change =>
+                {
                     shell.wl_storage.commit_tx();
+                    events.extend(
+                        tx_result.pos_events.iter().cloned().map(Event::from),
+                    );
                     (
                         tx_result.is_accepted(),
                         ProposalEvent::new(
@@ -258,3 +446,109 @@ fn execute_eth_proposal(id: u64) -> (bool, Event) {
         .into(),
     )
 }
+
+#[cfg(test)]
+mod test_governance {
+    use namada::ledger::parameters::EpochDuration;
+    use namada::ledger::storage::testing::TestWlStorage;
+    use namada::types::time::DurationSecs;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    /// Test the validation of PoS parameters changes
+    #[test]
+    fn test_validate_pos_params_proposal() {
+        let old = PosParams::default();
+        let rate = dec!(0.5);
+        assert!(validate_pos_params_proposal(&old, &old, rate).is_ok());
+
+        let new = PosParams {
+            pipeline_len: old.pipeline_len + 1,
+            ..old.clone()
+        };
+        assert!(validate_pos_params_proposal(&old, &new, rate).is_ok());
+
+        let new = PosParams {
+            pipeline_len: old.pipeline_len - 1,
+            ..old.clone()
+        };
+        assert!(matches!(
+            validate_pos_params_proposal(&old, &new, rate),
+            Err(ParamChangeError::PipelineLenDecreased(_, _))
+        ));
+
+        let new = PosParams {
+            pipeline_len: 3,
+            unbonding_len: 2,
+            ..old.clone()
+        };
+        let old_short = PosParams {
+            pipeline_len: 3,
+            unbonding_len: 4,
+            ..old.clone()
+        };
+        assert!(matches!(
+            validate_pos_params_proposal(&old_short, &new, rate),
+            Err(ParamChangeError::UnbondingLenTooShort(2, 3))
+        ));
+
+        let new = PosParams {
+            max_inflation_rate: old.max_inflation_rate * dec!(2),
+            ..old.clone()
+        };
+        assert!(matches!(
+            validate_pos_params_proposal(&old, &new, rate),
+            Err(ParamChangeError::InvalidChange(_))
+        ));
+    }
+
+    /// Test that an invalid PoS parameters change is not applied
+    #[test]
+    fn test_execute_pos_params_change() {
+        let mut wl_storage = TestWlStorage::default();
+        let old = PosParams::default();
+        write_pos_params(&mut wl_storage, old.clone()).unwrap();
+        wl_storage
+            .write(
+                &gov_storage::get_max_pos_params_change_rate_key(),
+                dec!(0.5),
+            )
+            .unwrap();
+        let epoch_duration = EpochDuration {
+            min_num_of_blocks: 10,
+            min_duration: DurationSecs(0),
+        };
+        namada::ledger::parameters::update_epoch_parameter(
+            &mut wl_storage,
+            &epoch_duration,
+        )
+        .unwrap();
+        wl_storage.commit_tx();
+        let epoch = wl_storage.storage.block.epoch;
+
+        // A valid change is applied
+        let new = PosParams {
+            pipeline_len: old.pipeline_len + 1,
+            ..old.clone()
+        };
+        execute_pos_params_change(&mut wl_storage, &old, new.clone(), epoch)
+            .unwrap();
+        let params = read_pos_params(&wl_storage).unwrap();
+        assert_eq!(params.pipeline_len, new.pipeline_len);
+
+        // An invalid change is not applied, without touching other changes
+        let other_key = Key::parse("other").unwrap();
+        wl_storage.write(&other_key, 1_u64).unwrap();
+        let invalid = PosParams {
+            pipeline_len: old.pipeline_len,
+            ..new.clone()
+        };
+        let result =
+            execute_pos_params_change(&mut wl_storage, &new, invalid, epoch);
+        assert!(result.is_err());
+        let params = read_pos_params(&wl_storage).unwrap();
+        assert_eq!(params.pipeline_len, new.pipeline_len);
+        assert_eq!(wl_storage.read::<u64>(&other_key).unwrap(), Some(1));
+    }
+}
@@ -23,11 +23,12 @@ use std::rc::Rc;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada::ledger::events::log::EventLog;
-use namada::ledger::events::Event;
+use namada::ledger::events::{Event, EventLevel, EventType};
 use namada::ledger::gas::BlockGasMeter;
 use namada::ledger::pos::namada_proof_of_stake::types::{
     ConsensusValidator, ValidatorSetUpdate,
 };
+use namada::ledger::pos::{PosEvent, PosParams};
 use namada::ledger::storage::write_log::WriteLog;
 use namada::ledger::storage::{
     DBIter, Sha256Hasher, Storage, StorageHasher, WlStorage, DB,
@@ -51,9 +52,10 @@ use namada::types::transaction::{
 };
 use namada::types::{address, hash};
 use namada::vm::wasm::{TxCache, VpCache};
-use namada::vm::WasmCacheRwAccess;
+use namada::vm::{WasmCacheRwAccess, WasmCacheStats};
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
+use once_cell::sync::OnceCell;
 use thiserror::Error;
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -141,6 +143,7 @@ pub enum ErrorCodes {
     ReplayTx = 10,
     InvalidChainId = 11,
     ExpiredTx = 12,
+    GasOverflow = 13,
 }
 
 impl ErrorCodes {
@@ -157,7 +160,7 @@ impl ErrorCodes {
             | WasmRuntimeError => true,
             InvalidTx | InvalidSig | InvalidOrder | ExtraTxs
             | Undecryptable | AllocationError | ReplayTx | InvalidChainId
-            | ExpiredTx => false,
+            | ExpiredTx | GasOverflow => false,
         }
     }
 }
@@ -220,11 +223,32 @@ pub(super) enum ShellMode {
 impl ShellMode {
     /// Get the validator address if ledger is in validator mode
     pub fn get_validator_address(&self) -> Option<&address::Address> {
+        self.validator_data().map(|data| &data.address)
+    }
+
+    /// Get the validator data if ledger is in validator mode
+    pub fn validator_data(&self) -> Option<&ValidatorData> {
         match &self {
-            ShellMode::Validator { data, .. } => Some(&data.address),
+            ShellMode::Validator { data, .. } => Some(data),
             _ => None,
         }
     }
+
+    /// Check if the ledger is running a validator node
+    pub fn is_validator(&self) -> bool {
+        matches!(self, ShellMode::Validator { .. })
+    }
+
+    /// Check if the ledger is running a full node
+    pub fn is_full(&self) -> bool {
+        matches!(self, ShellMode::Full)
+    }
+
+    /// Check if the ledger is running a seed node, which doesn't handle
+    /// transactions
+    pub fn is_seed(&self) -> bool {
+        matches!(self, ShellMode::Seed)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -268,10 +292,15 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `query_gas_limit`. When set, will limit the
+    /// estimated gas that a single query may use.
+    query_gas_limit: Option<u64>,
     /// Proposal execution tracking
     pub proposal_data: HashSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// PoS parameters cached on first read, cleared on epoch transitions
+    pos_params: OnceCell<PosParams>,
 }
 
 impl<D, H> Shell<D, H>
@@ -296,6 +325,7 @@ where
         let mode = config.tendermint.tendermint_mode;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        let query_gas_limit = config.shell.query_gas_limit;
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -389,9 +419,11 @@ where
                 tx_wasm_compilation_cache as usize,
             ),
             storage_read_past_height_limit,
+            query_gas_limit,
             proposal_data: HashSet::new(),
             // TODO: config event log params
             event_log: EventLog::default(),
+            pos_params: OnceCell::new(),
         }
     }
 
@@ -407,6 +439,28 @@ where
         &mut self.event_log
     }
 
+    /// Get the PoS parameters. They are read from storage on first use and
+    /// then cached until the next epoch transition.
+    pub fn get_pos_params(&self) -> PosParams {
+        self.pos_params
+            .get_or_init(|| {
+                read_pos_params(&self.wl_storage)
+                    .expect("PoS parameters should be in storage")
+            })
+            .clone()
+    }
+
+    /// Clear the cached PoS parameters, so that they are read again from
+    /// storage on the next use.
+    pub(super) fn clear_pos_params_cache(&mut self) {
+        self.pos_params.take();
+    }
+
+    /// Get the usage statistics of the VP and tx wasm compilation caches
+    pub fn wasm_cache_stats(&self) -> WasmCacheStats {
+        WasmCacheStats::new(&self.vp_wasm_cache, &self.tx_wasm_cache)
+    }
+
     /// Iterate over the wrapper txs in order
     #[allow(dead_code)]
     fn iter_tx_queue(&mut self) -> impl Iterator<Item = &WrapperTxInQueue> {
@@ -488,13 +542,37 @@ where
         }
     }
 
+    /// Log an event for evidence of an unknown type, which cannot be slashed,
+    /// so that its occurrence is externally observable
+    fn log_unknown_evidence(&mut self, evidence: &Evidence) {
+        let mut event = Event {
+            event_type: EventType::UnknownEvidence,
+            level: EventLevel::Block,
+            attributes: Default::default(),
+        };
+        event["evidence_type"] = evidence.r#type.to_string();
+        event["height"] = evidence.height.to_string();
+        if let Some(validator) = evidence.validator.as_ref() {
+            let validator_raw_hash = tm_raw_hash_to_string(&validator.address);
+            if let Some(validator) = proof_of_stake::find_validator_by_raw_hash(
+                &self.wl_storage,
+                &validator_raw_hash,
+            )
+            .expect("Must be able to read storage")
+            {
+                event["validator"] = validator.to_string();
+            }
+            event["validator_raw_hash"] = validator_raw_hash;
+        }
+        self.event_log_mut().log_events(vec![event]);
+    }
+
     /// Apply PoS slashes from the evidence
     fn slash(&mut self) {
         if !self.byzantine_validators.is_empty() {
             let byzantine_validators =
                 mem::take(&mut self.byzantine_validators);
-            // TODO: resolve this unwrap() better
-            let pos_params = read_pos_params(&self.wl_storage).unwrap();
+            let pos_params = self.get_pos_params();
             let current_epoch = self.wl_storage.storage.block.epoch;
             for evidence in byzantine_validators {
                 tracing::info!("Processing evidence {evidence:?}.");
@@ -525,9 +603,12 @@ where
                     }
                 };
                 if evidence_epoch + pos_params.unbonding_len <= current_epoch {
+                    let blocks_per_epoch =
+                        proof_of_stake::read_blocks_per_epoch(&self.wl_storage)
+                            .expect("Must be able to read storage");
                     tracing::info!(
-                        "Skipping outdated evidence from epoch \
-                         {evidence_epoch}"
+                        "Skipping outdated evidence from {}",
+                        evidence_epoch.format_with_block(blocks_per_epoch)
                     );
                     continue;
                 }
@@ -544,6 +625,7 @@ where
                                 "Unknown evidence: {:#?}",
                                 evidence
                             );
+                            self.log_unknown_evidence(&evidence);
                             continue;
                         }
                     },
@@ -589,7 +671,13 @@ where
                     evidence_epoch,
                     evidence_height
                 );
-                if let Err(err) = slash(
+                let slash_event = PosEvent::Slash {
+                    validator: validator.clone(),
+                    slash_type: slash_type.to_string(),
+                    rate: slash_type.get_slash_rate(&pos_params),
+                    epoch: evidence_epoch,
+                };
+                match slash(
                     &mut self.wl_storage,
                     &pos_params,
                     current_epoch,
@@ -598,7 +686,13 @@ where
                     slash_type,
                     &validator,
                 ) {
-                    tracing::error!("Error in slashing: {}", err);
+                    Ok(()) => {
+                        self.event_log_mut()
+                            .log_events(vec![Event::from(slash_event)]);
+                    }
+                    Err(err) => {
+                        tracing::error!("Error in slashing: {}", err);
+                    }
                 }
             }
         }
@@ -663,6 +757,13 @@ where
     ) -> response::CheckTx {
         let mut response = response::CheckTx::default();
 
+        // Seed nodes don't accept txs into their mempool
+        if self.mode.is_seed() {
+            response.code = ErrorCodes::InvalidTx.into();
+            response.log = "Transactions are not accepted by seed nodes".into();
+            return response;
+        }
+
         // Tx format check
         let tx = match Tx::try_from(tx_bytes).map_err(Error::TxDecoding) {
             Ok(t) => t,
@@ -810,6 +911,7 @@ where
                     &self.wl_storage.storage,
                     &mut vp_wasm_cache,
                     &mut tx_wasm_cache,
+                    false,
                 )
                 .map_err(Error::TxApply)
                 {
@@ -1136,6 +1238,7 @@ mod test_utils {
                 txs: vec![],
                 proposer_address: vec![],
                 votes: vec![],
+                round: 0,
             }
         }
     }
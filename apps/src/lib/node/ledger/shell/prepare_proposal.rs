@@ -22,7 +22,7 @@ use super::block_space_alloc::{AllocFailure, BlockSpaceAllocator};
 use crate::facade::tendermint_proto::abci::ExtendedCommitInfo;
 use crate::facade::tendermint_proto::abci::RequestPrepareProposal;
 use crate::facade::tendermint_proto::google::protobuf::Timestamp;
-use crate::node::ledger::shell::{process_tx, ShellMode};
+use crate::node::ledger::shell::process_tx;
 use crate::node::ledger::shims::abcipp_shim_types::shim::{response, TxBytes};
 
 impl<D, H> Shell<D, H>
@@ -42,7 +42,7 @@ where
         &self,
         req: RequestPrepareProposal,
     ) -> response::PrepareProposal {
-        let txs = if let ShellMode::Validator { .. } = self.mode {
+        let txs = if self.mode.is_validator() {
             // start counting allotted space for txs
             let alloc = self.get_encrypted_txs_allocator();
 
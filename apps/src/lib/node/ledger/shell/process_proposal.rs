@@ -26,6 +26,9 @@ pub struct ValidationMeta {
     pub encrypted_txs_bin: TxBin,
     /// Space utilized by all txs.
     pub txs_bin: TxBin,
+    /// Minimum gas that will be charged for all txs, proportional to their
+    /// size.
+    pub gas_meter: BlockGasMeter,
     /// Check if the decrypted tx queue has any elements
     /// left.
     ///
@@ -52,6 +55,7 @@ where
             has_decrypted_txs: false,
             encrypted_txs_bin,
             txs_bin,
+            gas_meter: BlockGasMeter::default(),
         }
     }
 }
@@ -210,6 +214,18 @@ where
                 .into(),
             };
         }
+        // charge gas proportional to the size of this tx, to reject proposals
+        // that are guaranteed to exceed the block gas limit
+        let gas_res = metadata
+            .gas_meter
+            .add_compiling_fee(tx_bytes.len())
+            .and_then(|()| metadata.gas_meter.finalize_transaction());
+        if let Err(err) = gas_res {
+            return TxResult {
+                code: ErrorCodes::GasOverflow.into(),
+                info: format!("The block gas limit was exceeded: {}", err),
+            };
+        }
 
         let maybe_tx = Tx::try_from(tx_bytes).map_or_else(
             |err| {
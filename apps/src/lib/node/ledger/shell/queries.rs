@@ -3,14 +3,16 @@
 use borsh::BorshSerialize;
 use ferveo_common::TendermintValidator;
 use namada::ledger::pos::into_tm_voting_power;
-use namada::ledger::queries::{RequestCtx, ResponseQuery};
+use namada::ledger::queries::{RequestCtx, ResponseQuery, RPC};
 use namada::ledger::storage_api::token;
 use namada::proof_of_stake::{
-    read_consensus_validator_set_addresses_with_stake, read_pos_params,
+    read_consensus_validator_set_addresses_with_stake,
+    read_consensus_validator_set_voting_powers,
 };
 use namada::types::address::Address;
 use namada::types::key;
 use namada::types::key::dkg_session_keys::DkgPublicKey;
+use namada::types::storage::Epoch;
 
 use super::*;
 use crate::node::ledger::response;
@@ -31,6 +33,7 @@ where
             vp_wasm_cache: self.vp_wasm_cache.read_only(),
             tx_wasm_cache: self.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: self.storage_read_past_height_limit,
+            query_gas_limit: self.query_gas_limit,
         };
 
         // Convert request to domain-type
@@ -48,6 +51,17 @@ where
             }
         };
 
+        // Seed nodes don't run transactions
+        if self.mode.is_seed() && request.path == RPC.shell().dry_run_tx_path()
+        {
+            return response::Query {
+                code: 1,
+                info: "Dry-running transactions is not supported on seed nodes"
+                    .into(),
+                ..Default::default()
+            };
+        }
+
         // Invoke the root RPC handler - returns borsh-encoded data on success
         let result = namada::ledger::queries::handle_path(ctx, &request);
         match result {
@@ -78,6 +92,21 @@ where
             .expect("Token balance read in the protocol must not fail")
     }
 
+    /// Get the validators in the consensus set of the given epoch (or the
+    /// current epoch if `None`) with their Tendermint voting power
+    pub fn get_consensus_validators(
+        &self,
+        epoch: Option<Epoch>,
+    ) -> storage_api::Result<Vec<(Address, u64)>> {
+        let epoch = epoch.unwrap_or(self.wl_storage.storage.block.epoch);
+        let params = self.get_pos_params();
+        read_consensus_validator_set_voting_powers(
+            &self.wl_storage,
+            &params,
+            epoch,
+        )
+    }
+
     /// Lookup data about a validator from their protocol signing key
     #[allow(dead_code)]
     pub fn get_validator_from_protocol_pk(
@@ -93,7 +122,7 @@ where
         // TODO: resolve both unwrap() instances better below
 
         // get the PoS params
-        let pos_params = read_pos_params(&self.wl_storage).unwrap();
+        let pos_params = self.get_pos_params();
         // get the consensus validator set
         let consensus_vals = read_consensus_validator_set_addresses_with_stake(
             &self.wl_storage,
@@ -129,3 +158,105 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod test_queries {
+    use borsh::BorshDeserialize;
+    use namada::proof_of_stake::bonds_and_unbonds;
+    use namada::proof_of_stake::types::{
+        BondsAndUnbondsDetails, ValidatorState,
+    };
+    use namada::types::address;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils;
+
+    /// Test that the bonds and unbonds can be queried via the ABCI query
+    /// path and that the response matches the storage
+    #[test]
+    fn test_bonds_and_unbonds_query() {
+        let (shell, _) = test_utils::setup(1);
+
+        let path = RPC.vp().pos().bonds_and_unbonds_path(&None, &None);
+        let response = shell.query(request::Query {
+            path,
+            ..Default::default()
+        });
+        assert_eq!(response.code, 0, "{}", response.info);
+
+        let details =
+            BondsAndUnbondsDetails::try_from_slice(&response.value[..])
+                .expect("Response should decode");
+        // The genesis validator's self-bond
+        assert_eq!(details.len(), 1);
+        let (bond_id, detail) = details.iter().next().unwrap();
+        assert_eq!(bond_id.source, bond_id.validator);
+        assert!(!detail.bonds.is_empty());
+
+        let expected =
+            bonds_and_unbonds(&shell.wl_storage, None, None).unwrap();
+        assert_eq!(details.len(), expected.len());
+        assert!(expected.keys().all(|bond_id| details.contains_key(bond_id)));
+    }
+
+    /// Test that the consensus validators' voting powers can be queried via
+    /// the ABCI query path and that they match the shell's view
+    #[test]
+    fn test_consensus_voting_powers_query() {
+        let (shell, _) = test_utils::setup(1);
+
+        let path = RPC.vp().pos().consensus_voting_powers_path(&None);
+        let response = shell.query(request::Query {
+            path,
+            ..Default::default()
+        });
+        assert_eq!(response.code, 0, "{}", response.info);
+
+        let voting_powers =
+            Vec::<(Address, u64)>::try_from_slice(&response.value[..])
+                .expect("Response should decode");
+        let expected = shell.get_consensus_validators(None).unwrap();
+        assert_eq!(voting_powers, expected);
+        // The genesis validator
+        assert_eq!(voting_powers.len(), 1);
+        assert!(voting_powers[0].1 > 0);
+    }
+
+    /// Test that a validator's state can be queried via the ABCI query path
+    #[test]
+    fn test_validator_state_query() {
+        let (shell, _) = test_utils::setup(1);
+        let (validator, _) = shell
+            .get_consensus_validators(None)
+            .unwrap()
+            .into_iter()
+            .next()
+            .expect("There should be a genesis validator");
+
+        let path = RPC.vp().pos().validator_state_path(&validator, &None);
+        let response = shell.query(request::Query {
+            path,
+            ..Default::default()
+        });
+        assert_eq!(response.code, 0, "{}", response.info);
+        let state =
+            Option::<ValidatorState>::try_from_slice(&response.value[..])
+                .expect("Response should decode");
+        assert_eq!(state, Some(ValidatorState::Consensus));
+
+        // A non-validator has no state
+        let path = RPC.vp().pos().validator_state_path(
+            &address::testing::established_address_4(),
+            &None,
+        );
+        let response = shell.query(request::Query {
+            path,
+            ..Default::default()
+        });
+        assert_eq!(response.code, 0, "{}", response.info);
+        let state =
+            Option::<ValidatorState>::try_from_slice(&response.value[..])
+                .expect("Response should decode");
+        assert_eq!(state, None);
+    }
+}
@@ -227,11 +227,14 @@ pub mod shim {
             pub txs: Vec<ProcessedTx>,
             pub proposer_address: Vec<u8>,
             pub votes: Vec<VoteInfo>,
+            /// The consensus round in which the last block was committed
+            pub round: u32,
         }
 
         #[cfg(feature = "abcipp")]
         impl From<RequestFinalizeBlock> for FinalizeBlock {
             fn from(req: RequestFinalizeBlock) -> FinalizeBlock {
+                let last_commit = req.decided_last_commit.unwrap();
                 FinalizeBlock {
                     hash: BlockHash::try_from(req.hash.as_slice()).unwrap(),
                     header: Header {
@@ -245,7 +248,8 @@ pub mod shim {
                     byzantine_validators: req.byzantine_validators,
                     txs: vec![],
                     proposer_address: req.proposer_address,
-                    votes: req.decided_last_commit.unwrap().votes,
+                    votes: last_commit.votes,
+                    round: u32::try_from(last_commit.round).unwrap_or_default(),
                 }
             }
         }
@@ -254,6 +258,7 @@ pub mod shim {
         impl From<RequestBeginBlock> for FinalizeBlock {
             fn from(req: RequestBeginBlock) -> FinalizeBlock {
                 let header = req.header.unwrap();
+                let last_commit = req.last_commit_info.unwrap();
                 FinalizeBlock {
                     hash: BlockHash::default(),
                     header: Header {
@@ -269,7 +274,8 @@ pub mod shim {
                     byzantine_validators: req.byzantine_validators,
                     txs: vec![],
                     proposer_address: header.proposer_address,
-                    votes: req.last_commit_info.unwrap().votes,
+                    votes: last_commit.votes,
+                    round: u32::try_from(last_commit.round).unwrap_or_default(),
                 }
             }
         }
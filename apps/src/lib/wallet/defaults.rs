@@ -18,7 +18,7 @@ pub fn addresses_from_genesis(genesis: GenesisConfig) -> Vec<(Alias, Address)> {
     // Internal addresses
     let mut addresses: Vec<(Alias, Address)> = vec![
         ("pos".into(), pos::ADDRESS),
-        ("pos_slash_pool".into(), pos::SLASH_POOL_ADDRESS),
+        ("pos_slash_pool".into(), pos::slash_pool_address()),
         ("governance".into(), governance::ADDRESS),
         ("eth_bridge".into(), eth_bridge::vp::ADDRESS),
     ];
@@ -131,7 +131,7 @@ mod dev {
     pub fn addresses() -> Vec<(Alias, Address)> {
         let mut addresses: Vec<(Alias, Address)> = vec![
             ("pos".into(), pos::ADDRESS),
-            ("pos_slash_pool".into(), pos::SLASH_POOL_ADDRESS),
+            ("pos_slash_pool".into(), pos::slash_pool_address()),
             ("governance".into(), governance::ADDRESS),
             ("validator".into(), validator_address()),
             ("albert".into(), albert_address()),
@@ -115,6 +115,18 @@ impl BlockGasMeter {
         self.transaction_gas
     }
 
+    /// Get the total gas accumulated by the finalized transactions in the
+    /// current block.
+    pub fn get_current_block_gas(&self) -> u64 {
+        self.block_gas
+    }
+
+    /// Check if the gas accumulated in the current block has reached the
+    /// block gas limit, in which case no more transactions can be applied.
+    pub fn is_block_gas_exhausted(&self) -> bool {
+        self.block_gas >= BLOCK_GAS_LIMIT
+    }
+
     /// Add the gas cost used in validity predicates to the current transaction.
     pub fn add_vps_gas(&mut self, vps_gas: &VpsGas) -> Result<()> {
         self.add(vps_gas.get_current_gas()?)
@@ -289,6 +301,7 @@ mod tests {
 
         // add the maximum tx gas
         for _ in 0..(BLOCK_GAS_LIMIT / TRANSACTION_GAS_LIMIT) {
+            assert!(!meter.is_block_gas_exhausted());
             meter
                 .add(TRANSACTION_GAS_LIMIT)
                 .expect("over the tx gas limit");
@@ -296,6 +309,8 @@ mod tests {
                 .finalize_transaction()
                 .expect("over the block gas limit");
         }
+        assert_eq!(meter.get_current_block_gas(), BLOCK_GAS_LIMIT);
+        assert!(meter.is_block_gas_exhausted());
 
         meter
             .add(TRANSACTION_GAS_LIMIT)
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 
 use super::storage as gov_storage;
 use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
@@ -31,6 +33,8 @@ pub struct GovParams {
     pub max_proposal_content_size: u64,
     /// Minimum epochs between end and grace epochs
     pub min_proposal_grace_epochs: u64,
+    /// Maximum relative change of any PoS parameter in a single proposal
+    pub max_pos_params_change_rate: Decimal,
 }
 
 impl Display for GovParams {
@@ -39,13 +43,15 @@ impl Display for GovParams {
             f,
             "Min. proposal fund: {}\nMax. proposal code size: {}\nMin. \
              proposal period: {}\nMax. proposal period: {}\nMax. proposal \
-             content size: {}\nMin. proposal grace epochs: {}",
+             content size: {}\nMin. proposal grace epochs: {}\nMax. PoS \
+             parameters change rate: {}",
             self.min_proposal_fund,
             self.max_proposal_code_size,
             self.min_proposal_period,
             self.max_proposal_period,
             self.max_proposal_content_size,
-            self.min_proposal_grace_epochs
+            self.min_proposal_grace_epochs,
+            self.max_pos_params_change_rate
         )
     }
 }
@@ -59,6 +65,7 @@ impl Default for GovParams {
             max_proposal_period: 27,
             max_proposal_content_size: 10_000,
             min_proposal_grace_epochs: 6,
+            max_pos_params_change_rate: dec!(0.5),
         }
     }
 }
@@ -76,6 +83,7 @@ impl GovParams {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            max_pos_params_change_rate,
         } = self;
 
         let min_proposal_fund_key = gov_storage::get_min_proposal_fund_key();
@@ -104,6 +112,13 @@ impl GovParams {
         storage
             .write(&min_proposal_grace_epoch_key, min_proposal_grace_epochs)?;
 
+        let max_pos_params_change_rate_key =
+            gov_storage::get_max_pos_params_change_rate_key();
+        storage.write(
+            &max_pos_params_change_rate_key,
+            max_pos_params_change_rate,
+        )?;
+
         let counter_key = gov_storage::get_counter_key();
         storage.write(&counter_key, u64::MIN)
     }
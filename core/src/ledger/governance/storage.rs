@@ -20,6 +20,7 @@ const MIN_PROPOSAL_PERIOD_KEY: &str = "min_period";
 const MAX_PROPOSAL_PERIOD_KEY: &str = "max_period";
 const MAX_PROPOSAL_CONTENT_SIZE_KEY: &str = "max_content";
 const MIN_GRACE_EPOCH_KEY: &str = "min_grace_epoch";
+const MAX_POS_PARAMS_CHANGE_RATE_KEY: &str = "max_pos_params_change_rate";
 const COUNTER_KEY: &str = "counter";
 const PENDING_PROPOSAL: &str = "pending";
 
@@ -264,6 +265,15 @@ pub fn is_min_grace_epoch_key(key: &Key) -> bool {
                     && min_grace_epoch_param == MIN_GRACE_EPOCH_KEY)
 }
 
+/// Check if key is a max PoS parameters change rate param key
+pub fn is_max_pos_params_change_rate_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(max_change_rate_param),
+         ] if addr == &ADDRESS
+             && max_change_rate_param == MAX_POS_PARAMS_CHANGE_RATE_KEY)
+}
+
 /// Check if key is parameter key
 pub fn is_parameter_key(key: &Key) -> bool {
     is_min_proposal_fund_key(key)
@@ -272,6 +282,7 @@ pub fn is_parameter_key(key: &Key) -> bool {
         || is_min_proposal_period_key(key)
         || is_max_proposal_period_key(key)
         || is_min_grace_epoch_key(key)
+        || is_max_pos_params_change_rate_key(key)
 }
 
 /// Check if key is start epoch or end epoch key
@@ -328,6 +339,13 @@ pub fn get_min_proposal_grace_epoch_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get max PoS parameters change rate key
+pub fn get_max_pos_params_change_rate_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&MAX_POS_PARAMS_CHANGE_RATE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal ids counter
 pub fn get_counter_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -133,8 +133,7 @@ where
     // Update the MASP's transparent reward token balance to ensure that it
     // is sufficiently backed to redeem rewards
     let reward_key = token::balance_key(&address::nam(), &masp_addr);
-    let addr_bal: token::Amount =
-        wl_storage.read(&reward_key)?.unwrap_or_default();
+    let addr_bal: token::Amount = wl_storage.read_or_default(&reward_key)?;
     let new_bal = addr_bal + total_reward;
     wl_storage.write(&reward_key, new_bal)?;
     // Try to distribute Merkle tree construction as evenly as possible
@@ -140,6 +140,22 @@ where
         self.write_log.drop_tx()
     }
 
+    /// Apply `f` on top of a copy of the current write log and return its
+    /// result together with the resulting write log, leaving the original
+    /// write log untouched. Only the changes that go through the write log
+    /// are isolated, so `f` must not write to the storage directly or commit
+    /// the block.
+    pub fn simulate_tx<F, R>(&mut self, f: F) -> (R, WriteLog)
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let write_log = self.write_log.clone();
+        let original = std::mem::replace(&mut self.write_log, write_log);
+        let result = f(self);
+        let simulated = std::mem::replace(&mut self.write_log, original);
+        (result, simulated)
+    }
+
     /// Commit the current block's write log to the storage and commit the block
     /// to DB. Starts a new block write log.
     pub fn commit_block(&mut self) -> storage_api::Result<()> {
@@ -622,6 +638,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_simulate_tx() {
+        let mut s = TestWlStorage::default();
+        let key_pre = storage::Key::parse("pre").unwrap();
+        let key_sim = storage::Key::parse("sim").unwrap();
+        s.write(&key_pre, 1_u64).unwrap();
+
+        let (result, write_log) = s.simulate_tx(|s| {
+            s.write(&key_sim, 2_u64)?;
+            s.read::<u64>(&key_pre)
+        });
+
+        // The simulation sees the prior changes
+        assert_eq!(result.unwrap(), Some(1));
+        // The simulated write log contains both the prior and the new changes
+        assert!(write_log.read(&key_pre).0.is_some());
+        assert!(write_log.read(&key_sim).0.is_some());
+        // The original write log is left untouched
+        assert_eq!(s.read::<u64>(&key_pre).unwrap(), Some(1));
+        assert!(!s.has_key(&key_sim).unwrap());
+    }
+
     /// WlStorage key written in the write log or storage
     type KeyVal<VAL> = (storage::Key, Level<VAL>);
 
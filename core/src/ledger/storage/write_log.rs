@@ -3,6 +3,7 @@
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+use borsh::BorshSerialize;
 use itertools::Itertools;
 use thiserror::Error;
 
@@ -12,6 +13,7 @@ use crate::types::address::{Address, EstablishedAddressGen};
 use crate::types::hash::Hash;
 use crate::types::ibc::IbcEvent;
 use crate::types::storage;
+use crate::types::transaction::pos::PosEvent;
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -71,6 +73,8 @@ pub struct WriteLog {
     tx_write_log: HashMap<storage::Key, StorageModification>,
     /// The IBC event for the current transaction
     ibc_event: Option<IbcEvent>,
+    /// The PoS events for the current transaction
+    pos_events: Vec<PosEvent>,
 }
 
 /// Write log prefix iterator
@@ -96,6 +100,7 @@ impl Default for WriteLog {
             block_write_log: HashMap::with_capacity(100_000),
             tx_write_log: HashMap::with_capacity(100),
             ibc_event: None,
+            pos_events: Vec::new(),
         }
     }
 }
@@ -339,6 +344,16 @@ impl WriteLog {
         len as _
     }
 
+    /// Add a PoS event and return the gas cost.
+    pub fn set_pos_event(&mut self, event: PosEvent) -> u64 {
+        let len = event
+            .try_to_vec()
+            .expect("Serializing a PoS event shouldn't fail")
+            .len();
+        self.pos_events.push(event);
+        len as _
+    }
+
     /// Get the storage keys changed and accounts keys initialized in the
     /// current transaction. The account keys point to the validity predicates
     /// of the newly created accounts.
@@ -391,6 +406,16 @@ impl WriteLog {
         self.ibc_event.as_ref()
     }
 
+    /// Get the PoS events of the current transaction
+    pub fn get_pos_events(&self) -> &[PosEvent] {
+        &self.pos_events
+    }
+
+    /// Take the PoS events of the current transaction
+    pub fn take_pos_events(&mut self) -> Vec<PosEvent> {
+        std::mem::take(&mut self.pos_events)
+    }
+
     /// Commit the current transaction's write log to the block when it's
     /// accepted by all the triggered validity predicates. Starts a new
     /// transaction write log.
@@ -404,12 +429,14 @@ impl WriteLog {
         );
         self.block_write_log.extend(tx_write_log);
         self.take_ibc_event();
+        self.take_pos_events();
     }
 
     /// Drop the current transaction's write log when it's declined by any of
     /// the triggered validity predicates. Starts a new transaction write log.
     pub fn drop_tx(&mut self) {
         self.tx_write_log.clear();
+        self.take_pos_events();
     }
 
     /// Commit the current block's write log to the storage. Starts a new block
@@ -411,6 +411,19 @@ where
             storage_api::iter_prefix_bytes(storage, &self.get_data_prefix())?;
         Ok(iter.next().is_none())
     }
+
+    /// Reads the number of elements in the inner-most collections of the map.
+    ///
+    /// Note that this function shouldn't be used in transactions and VPs code
+    /// on unbounded maps to avoid gas usage increasing with the length of the
+    /// map.
+    pub fn count<S>(&self, storage: &S) -> Result<u64>
+    where
+        S: StorageRead,
+    {
+        self.iter(storage)?
+            .try_fold(0_u64, |count, res| res.map(|_| count + 1))
+    }
 }
 
 // `LazyMap` methods with borsh encoded values `V`
@@ -469,6 +482,16 @@ where
         Self::read_key_val(storage, &data_key)
     }
 
+    /// Returns the value corresponding to the key, or the default value if
+    /// the key is not present.
+    pub fn get_or_default<S>(&self, storage: &S, key: &K) -> Result<V>
+    where
+        S: StorageRead,
+        V: Default,
+    {
+        Ok(self.get(storage, key)?.unwrap_or_default())
+    }
+
     /// Returns whether the map contains no elements.
     pub fn is_empty<S>(&self, storage: &S) -> Result<bool>
     where
@@ -560,6 +583,7 @@ mod test {
         assert!(lazy_map.iter(&storage)?.next().is_none());
         assert!(lazy_map.get(&storage, &0)?.is_none());
         assert!(lazy_map.get(&storage, &1)?.is_none());
+        assert_eq!(lazy_map.get_or_default(&storage, &0)?, String::default());
         assert!(lazy_map.remove(&mut storage, &0)?.is_none());
         assert!(lazy_map.remove(&mut storage, &1)?.is_none());
 
@@ -582,6 +606,7 @@ mod test {
         assert!(lazy_map.get(&storage, &0)?.is_none());
         assert_eq!(lazy_map.get(&storage, &key)?.unwrap(), val);
         assert_eq!(lazy_map.get(&storage, &key2)?.unwrap(), val2);
+        assert_eq!(lazy_map.get_or_default(&storage, &key)?, val);
 
         // Remove the values and check the map contents
         let removed = lazy_map.remove(&mut storage, &key)?.unwrap();
@@ -715,6 +740,8 @@ mod test {
         assert!(map_it.next().is_none());
         drop(map_it);
 
+        assert_eq!(lazy_map.count(&storage)?, 2);
+
         Ok(())
     }
 }
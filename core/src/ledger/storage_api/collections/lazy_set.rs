@@ -1,5 +1,6 @@
 //! Lazy set.
 
+use std::collections::BTreeSet;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
@@ -237,6 +238,20 @@ where
             Ok(key)
         }))
     }
+
+    /// An iterator visiting all keys in the order of their `Ord`
+    /// implementation, rather than in the order of their storage sub-keys.
+    ///
+    /// All the keys are read into memory first, so the same gas caveat as for
+    /// [`LazySet::iter`] applies.
+    pub fn iter_sorted<S>(&self, storage: &S) -> Result<impl Iterator<Item = K>>
+    where
+        S: StorageRead,
+        K: Ord,
+    {
+        let keys = self.iter(storage)?.collect::<Result<BTreeSet<K>>>()?;
+        Ok(keys.into_iter())
+    }
 }
 
 /// Determine what action was taken from the pre/post state
@@ -378,6 +393,16 @@ mod test {
         assert!(iter.next().is_none());
         drop(iter);
 
+        let key3 = address::testing::gen_implicit_address();
+        lazy_set.insert(&mut storage, key3.clone())?;
+
+        let mut expected =
+            lazy_set.iter(&storage)?.collect::<Result<Vec<_>>>()?;
+        expected.sort();
+        let sorted: Vec<Address> = lazy_set.iter_sorted(&storage)?.collect();
+        assert_eq!(sorted, expected);
+        assert!(sorted.contains(&key3));
+
         let storage_key = lazy_set.get_key(&key);
         assert_eq!(
             lazy_set.is_valid_sub_key(&storage_key).unwrap(),
@@ -50,6 +50,15 @@ pub trait StorageRead {
         }
     }
 
+    /// Storage read Borsh encoded value. It will try to read from the storage
+    /// and decode it if found, or return the default value if not found.
+    fn read_or_default<T: BorshDeserialize + Default>(
+        &self,
+        key: &storage::Key,
+    ) -> Result<T> {
+        Ok(self.read(key)?.unwrap_or_default())
+    }
+
     /// Storage read raw bytes. It will try to read from the storage.
     fn read_bytes(&self, key: &storage::Key) -> Result<Option<Vec<u8>>>;
 
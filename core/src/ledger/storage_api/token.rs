@@ -19,7 +19,7 @@ where
     S: StorageRead,
 {
     let key = token::balance_key(token, owner);
-    let balance = storage.read::<token::Amount>(&key)?.unwrap_or_default();
+    let balance = storage.read_or_default::<token::Amount>(&key)?;
     Ok(balance)
 }
 
@@ -32,7 +32,7 @@ where
     S: StorageRead,
 {
     let key = token::total_supply_key(token);
-    let balance = storage.read::<token::Amount>(&key)?.unwrap_or_default();
+    let balance = storage.read_or_default::<token::Amount>(&key)?;
     Ok(balance)
 }
 
@@ -92,9 +92,7 @@ where
     })?;
 
     let total_supply_key = token::total_supply_key(token);
-    let cur_supply = storage
-        .read::<Amount>(&total_supply_key)?
-        .unwrap_or_default();
+    let cur_supply = storage.read_or_default::<Amount>(&total_supply_key)?;
     let new_supply = cur_supply.checked_add(amount).ok_or_else(|| {
         storage_api::Error::new_const("Token total supply overflow")
     })?;
@@ -8,6 +8,7 @@ use crate::types::address::Address;
 use crate::types::ibc::IbcEvent;
 use crate::types::storage;
 use crate::types::time::Rfc3339String;
+use crate::types::transaction::pos::PosEvent;
 
 /// Transaction host functions
 pub trait TxEnv: StorageRead + StorageWrite {
@@ -58,6 +59,12 @@ pub trait TxEnv: StorageRead + StorageWrite {
         event: &IbcEvent,
     ) -> Result<(), storage_api::Error>;
 
+    /// Emit a PoS event. All the events emitted by a transaction are kept.
+    fn emit_pos_event(
+        &mut self,
+        event: &PosEvent,
+    ) -> Result<(), storage_api::Error>;
+
     /// Get time of the current block header as rfc 3339 string
     fn get_block_time(&self) -> Result<Rfc3339String, storage_api::Error>;
 }
@@ -44,6 +44,8 @@ pub const POS: Address = Address::Internal(InternalAddress::PoS);
 /// Internal PoS slash pool address
 pub const POS_SLASH_POOL: Address =
     Address::Internal(InternalAddress::PosSlashPool);
+/// Internal PoS burn address
+pub const POS_BURN: Address = Address::Internal(InternalAddress::PosBurn);
 /// Internal Governance address
 pub const GOV: Address = Address::Internal(InternalAddress::Governance);
 
@@ -55,6 +57,8 @@ mod internal {
         "ano::Proof of Stake                          ";
     pub const POS_SLASH_POOL: &str =
         "ano::Proof of Stake Slash Pool               ";
+    pub const POS_BURN: &str =
+        "ano::Proof of Stake Burn Address             ";
     pub const PARAMETERS: &str =
         "ano::Protocol Parameters                     ";
     pub const GOVERNANCE: &str =
@@ -188,6 +192,7 @@ impl Address {
                     InternalAddress::PosSlashPool => {
                         internal::POS_SLASH_POOL.to_string()
                     }
+                    InternalAddress::PosBurn => internal::POS_BURN.to_string(),
                     InternalAddress::Parameters => {
                         internal::PARAMETERS.to_string()
                     }
@@ -253,6 +258,9 @@ impl Address {
                 internal::POS_SLASH_POOL => {
                     Ok(Address::Internal(InternalAddress::PosSlashPool))
                 }
+                internal::POS_BURN => {
+                    Ok(Address::Internal(InternalAddress::PosBurn))
+                }
                 internal::PARAMETERS => {
                     Ok(Address::Internal(InternalAddress::Parameters))
                 }
@@ -465,6 +473,8 @@ pub enum InternalAddress {
     PoS,
     /// Proof-of-stake slash pool contains slashed tokens
     PosSlashPool,
+    /// Proof-of-stake burn address holds the burned slashed tokens
+    PosBurn,
     /// Protocol parameters
     Parameters,
     /// Inter-blockchain communication
@@ -510,6 +520,7 @@ impl Display for InternalAddress {
             match self {
                 Self::PoS => "PoS".to_string(),
                 Self::PosSlashPool => "PosSlashPool".to_string(),
+                Self::PosBurn => "PosBurn".to_string(),
                 Self::Parameters => "Parameters".to_string(),
                 Self::Governance => "Governance".to_string(),
                 Self::SlashFund => "SlashFund".to_string(),
@@ -779,6 +790,7 @@ pub mod testing {
         match InternalAddress::PoS {
             InternalAddress::PoS => {}
             InternalAddress::PosSlashPool => {}
+            InternalAddress::PosBurn => {}
             InternalAddress::Governance => {}
             InternalAddress::SlashFund => {}
             InternalAddress::Parameters => {}
@@ -795,6 +807,7 @@ pub mod testing {
         prop_oneof![
             Just(InternalAddress::PoS),
             Just(InternalAddress::PosSlashPool),
+            Just(InternalAddress::PosBurn),
             Just(InternalAddress::Ibc),
             Just(InternalAddress::Parameters),
             Just(InternalAddress::Ibc),
@@ -978,6 +978,55 @@ impl Epoch {
     pub fn sub_or_default(self, rhs: Epoch) -> Self {
         self.checked_sub(rhs).unwrap_or_default()
     }
+
+    /// Format the epoch together with the approximate height of its first
+    /// block, e.g. `"epoch 42 (~block 42000)"`.
+    pub fn format_with_block(self, blocks_per_epoch: u64) -> String {
+        format!(
+            "epoch {} (~block {})",
+            self.0,
+            self.0.saturating_mul(blocks_per_epoch)
+        )
+    }
+}
+
+/// Epoch arithmetic with a `u64` number of epochs that cannot overflow or
+/// panic.
+pub trait SafeEpochArithmetic: Sized {
+    /// Checked epoch addition. Computes self + rhs, returning None if
+    /// overflow occurred.
+    fn checked_add(self, rhs: u64) -> Option<Self>;
+
+    /// Saturating epoch addition. Computes self + rhs, saturating at the
+    /// maximum epoch.
+    fn saturating_add(self, rhs: u64) -> Self;
+
+    /// Checked epoch subtraction. Computes self - rhs, returning None if
+    /// overflow occurred. Unlike [`Epoch::checked_sub`], `rhs` is a number of
+    /// epochs rather than an [`Epoch`].
+    fn checked_sub_epochs(self, rhs: u64) -> Option<Self>;
+
+    /// Saturating epoch subtraction. Computes self - rhs, saturating at
+    /// `Epoch(0)`.
+    fn saturating_sub(self, rhs: u64) -> Self;
+}
+
+impl SafeEpochArithmetic for Epoch {
+    fn checked_add(self, rhs: u64) -> Option<Self> {
+        self.0.checked_add(rhs).map(Self)
+    }
+
+    fn saturating_add(self, rhs: u64) -> Self {
+        Self(self.0.saturating_add(rhs))
+    }
+
+    fn checked_sub_epochs(self, rhs: u64) -> Option<Self> {
+        self.0.checked_sub(rhs).map(Self)
+    }
+
+    fn saturating_sub(self, rhs: u64) -> Self {
+        Self(self.0.saturating_sub(rhs))
+    }
 }
 
 impl From<u64> for Epoch {
@@ -1283,6 +1332,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_safe_epoch_arithmetic() {
+        let max = Epoch(u64::MAX);
+        assert_eq!(Epoch(1).checked_add(2), Some(Epoch(3)));
+        assert_eq!(max.checked_add(1), None);
+        assert_eq!(max.saturating_add(1), max);
+        assert_eq!(Epoch(3).checked_sub_epochs(2), Some(Epoch(1)));
+        assert_eq!(Epoch(1).checked_sub_epochs(2), None);
+        assert_eq!(Epoch(1).saturating_sub(2), Epoch(0));
+    }
+
+    #[test]
+    fn test_epoch_format_with_block() {
+        assert_eq!(
+            Epoch(42).format_with_block(1000),
+            "epoch 42 (~block 42000)"
+        );
+        assert_eq!(
+            Epoch(u64::MAX).format_with_block(2),
+            format!("epoch {} (~block {})", u64::MAX, u64::MAX)
+        );
+    }
+
     #[test]
     fn test_predecessor_epochs() {
         let mut epochs = Epochs::default();
@@ -53,6 +53,8 @@ pub struct TxResult {
     pub initialized_accounts: Vec<Address>,
     /// Optional IBC event emitted by the transaction
     pub ibc_event: Option<IbcEvent>,
+    /// PoS events emitted by the transaction
+    pub pos_events: Vec<pos::PosEvent>,
 }
 
 impl TxResult {
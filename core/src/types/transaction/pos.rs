@@ -3,9 +3,13 @@
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
+use crate::proto::SignedTxData;
 use crate::types::address::Address;
+use crate::types::storage::Epoch;
 use crate::types::token;
+use crate::types::transaction::InitValidator;
 
 /// A bond is a validator's self-bond or a delegation from non-validator to a
 /// validator.
@@ -74,3 +78,205 @@ pub struct CommissionChange {
     /// The new commission rate
     pub new_rate: Decimal,
 }
+
+/// An approval of a pending multisig bond by one of its sources.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct MultisigBondApproval {
+    /// The ID of the pending multisig bond
+    pub id: u64,
+    /// The source that approves the bond
+    pub approver: Address,
+}
+
+/// Events emitted when PoS operations are applied
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum PosEvent {
+    /// Tokens were bonded to a validator
+    Bond {
+        /// Owner of the bond
+        source: Address,
+        /// Validator that the tokens were bonded to
+        validator: Address,
+        /// Bonded amount
+        amount: token::Amount,
+        /// Epoch at which the bond becomes active
+        epoch: Epoch,
+    },
+    /// Tokens were unbonded from a validator
+    Unbond {
+        /// Owner of the bond
+        source: Address,
+        /// Validator that the tokens were unbonded from
+        validator: Address,
+        /// Unbonded amount
+        amount: token::Amount,
+        /// Epoch at which the unbonded tokens can be withdrawn
+        withdraw_epoch: Epoch,
+    },
+    /// A validator was slashed
+    Slash {
+        /// The slashed validator
+        validator: Address,
+        /// The type of the slashable event
+        slash_type: String,
+        /// The slash rate
+        rate: Decimal,
+        /// Epoch at which the slashable event occurred
+        epoch: Epoch,
+    },
+    /// Tokens were burned from the slash pool
+    Burn {
+        /// Burned amount
+        amount: token::Amount,
+    },
+}
+
+impl PosEvent {
+    /// The kind of the event, which is also its event type when it's emitted
+    /// by a tx
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PosEvent::Bond { .. } => "bond",
+            PosEvent::Unbond { .. } => "unbond",
+            PosEvent::Slash { .. } => "slash",
+            PosEvent::Burn { .. } => "burn",
+        }
+    }
+}
+
+/// The wasm file name of the bond tx
+pub const TX_BOND_WASM: &str = "tx_bond.wasm";
+/// The wasm file name of the unbond tx
+pub const TX_UNBOND_WASM: &str = "tx_unbond.wasm";
+/// The wasm file name of the withdraw tx
+pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
+/// The wasm file name of the init validator tx
+pub const TX_INIT_VALIDATOR_WASM: &str = "tx_init_validator.wasm";
+/// The wasm file name of the change validator commission tx
+pub const TX_CHANGE_COMMISSION_WASM: &str =
+    "tx_change_validator_commission.wasm";
+
+/// The wasm file names of all the PoS txs whose data can be decoded with
+/// [`decode_pos_tx_data`]
+pub const POS_TX_WASMS: [&str; 5] = [
+    TX_BOND_WASM,
+    TX_UNBOND_WASM,
+    TX_WITHDRAW_WASM,
+    TX_INIT_VALIDATOR_WASM,
+    TX_CHANGE_COMMISSION_WASM,
+];
+
+/// The inner data of a PoS transaction, by the kind of the PoS operation it
+/// applies.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PosTxData {
+    /// Bond tokens to a validator
+    Bond(Bond),
+    /// Unbond tokens from a validator
+    Unbond(Unbond),
+    /// Withdraw unbonded tokens
+    Withdraw(Withdraw),
+    /// Initialize a new validator account
+    BecomeValidator(InitValidator),
+    /// Change a validator's commission rate
+    ChangeCommission(CommissionChange),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum DecodeError {
+    #[error("Error decoding signed tx data: {0}")]
+    InvalidSignedData(std::io::Error),
+    #[error("The signed tx data is empty")]
+    MissingData,
+    #[error("Error decoding PoS tx data: {0}")]
+    InvalidPosTxData(std::io::Error),
+}
+
+/// Decode the inner data of a signed PoS transaction, as received by the PoS
+/// validity predicate. The PoS txs write their data without any tag, so the
+/// kind of the data is determined by the wasm file name of the tx's code (one
+/// of [`POS_TX_WASMS`]). Returns `None` for the code of any other tx.
+pub fn decode_pos_tx_data(
+    tx_code_path: &str,
+    data: &[u8],
+) -> Result<Option<PosTxData>, DecodeError> {
+    if !POS_TX_WASMS.contains(&tx_code_path) {
+        return Ok(None);
+    }
+    let signed = SignedTxData::try_from_slice(data)
+        .map_err(DecodeError::InvalidSignedData)?;
+    let data = signed.data.ok_or(DecodeError::MissingData)?;
+    let data = &data[..];
+    let tx_data = match tx_code_path {
+        TX_BOND_WASM => Bond::try_from_slice(data).map(PosTxData::Bond),
+        TX_UNBOND_WASM => Unbond::try_from_slice(data).map(PosTxData::Unbond),
+        TX_WITHDRAW_WASM => {
+            Withdraw::try_from_slice(data).map(PosTxData::Withdraw)
+        }
+        TX_INIT_VALIDATOR_WASM => {
+            InitValidator::try_from_slice(data).map(PosTxData::BecomeValidator)
+        }
+        TX_CHANGE_COMMISSION_WASM => CommissionChange::try_from_slice(data)
+            .map(PosTxData::ChangeCommission),
+        _ => return Ok(None),
+    }
+    .map_err(DecodeError::InvalidPosTxData)?;
+    Ok(Some(tx_data))
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+
+    use super::*;
+    use crate::proto::Tx;
+    use crate::types::chain::ChainId;
+    use crate::types::key::testing::keypair_1;
+
+    /// Test that the data of a signed PoS tx can be decoded back
+    #[test]
+    fn test_decode_pos_tx_data() {
+        let withdraw = Withdraw {
+            validator: crate::types::address::testing::established_address_1(),
+            source: None,
+        };
+        let tx = Tx::new(
+            vec![],
+            Some(withdraw.try_to_vec().unwrap()),
+            ChainId::default(),
+            None,
+        )
+        .sign(&keypair_1());
+        let data = tx.data.unwrap();
+        let decoded = decode_pos_tx_data(TX_WITHDRAW_WASM, &data).unwrap();
+        assert_eq!(decoded, Some(PosTxData::Withdraw(withdraw)));
+
+        // The data doesn't match the tx code
+        assert!(matches!(
+            decode_pos_tx_data(TX_INIT_VALIDATOR_WASM, &data),
+            Err(DecodeError::InvalidPosTxData(_))
+        ));
+
+        // Not a PoS tx
+        let decoded = decode_pos_tx_data("tx_transfer.wasm", &data).unwrap();
+        assert_eq!(decoded, None);
+
+        // Not a signed tx data
+        assert!(matches!(
+            decode_pos_tx_data(TX_WITHDRAW_WASM, &[0, 1, 2]),
+            Err(DecodeError::InvalidSignedData(_))
+        ));
+    }
+}
@@ -14,8 +14,10 @@ use namada_core::ledger::storage_api::collections::lazy_map::{
 use namada_core::ledger::storage_api::collections::{self, LazyCollection};
 use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
 use namada_core::types::storage::{self, Epoch};
+use namada_core::types::token;
 
 use crate::parameters::PosParams;
+use crate::types::{decimal_mult_i128, Slash};
 
 /// Sub-key holding a lazy map in storage
 pub const LAZY_MAP_SUB_KEY: &str = "lazy_map";
@@ -561,6 +563,14 @@ where
         LazyMap::open(key)
     }
 
+    /// Reads the number of delta values in storage.
+    pub fn count<S>(&self, storage: &S) -> storage_api::Result<u64>
+    where
+        S: StorageRead,
+    {
+        self.get_data_handler().len(storage)
+    }
+
     /// Read all the data into a `HashMap`
     pub fn to_hashmap<S>(
         &self,
@@ -573,11 +583,99 @@ where
         handle.iter(storage)?.collect()
     }
 
+    /// Remove the delta values at epochs before the given epoch and add their
+    /// sum to the value at the given epoch, so that the sums at and after it
+    /// are preserved.
+    pub fn prune_before_epoch<S>(
+        &self,
+        storage: &mut S,
+        keep_from_epoch: Epoch,
+    ) -> storage_api::Result<()>
+    where
+        S: StorageWrite + StorageRead,
+    {
+        let data_handler = self.get_data_handler();
+        let mut pruned_epochs = Vec::new();
+        for res in data_handler.iter(storage)? {
+            let (epoch, _delta) = res?;
+            if epoch >= keep_from_epoch {
+                break;
+            }
+            pruned_epochs.push(epoch);
+        }
+
+        let mut sum: Option<Data> = None;
+        for epoch in pruned_epochs {
+            if let Some(removed) = data_handler.remove(storage, &epoch)? {
+                match sum.as_mut() {
+                    Some(sum) => *sum += removed,
+                    None => sum = Some(removed),
+                }
+            }
+        }
+        if let Some(sum) = sum {
+            let new_base = match data_handler.get(storage, &keep_from_epoch)? {
+                Some(delta) => delta + sum,
+                None => sum,
+            };
+            data_handler.insert(storage, keep_from_epoch, new_base)?;
+        }
+        Ok(())
+    }
+
     fn sub_past_epochs(epoch: Epoch) -> Epoch {
         Epoch(epoch.0.checked_sub(NUM_PAST_EPOCHS).unwrap_or_default())
     }
 }
 
+impl<FutureEpochs, const NUM_PAST_EPOCHS: u64>
+    EpochedDelta<token::Change, FutureEpochs, NUM_PAST_EPOCHS>
+where
+    FutureEpochs: EpochOffset,
+{
+    /// Get the sum of the delta values up through the given epoch, with the
+    /// given slashes applied. Each slash that occurred at or before the given
+    /// epoch reduces the deltas recorded at or before the slash epoch by its
+    /// rate.
+    pub fn get_sum_after_slashing<S>(
+        &self,
+        storage: &S,
+        epoch: Epoch,
+        params: &PosParams,
+        slashes: &[Slash],
+    ) -> storage_api::Result<token::Change>
+    where
+        S: StorageRead,
+    {
+        let last_update = match self.get_last_update(storage)? {
+            Some(last_update) => last_update,
+            None => return Ok(token::Change::default()),
+        };
+        let data_handler = self.get_data_handler();
+        let start_epoch = Self::sub_past_epochs(last_update);
+        let future_most_epoch = last_update + FutureEpochs::value(params);
+        let epoch = std::cmp::min(epoch, future_most_epoch);
+
+        let mut sum = token::Change::default();
+        for ep in (start_epoch.0)..=(epoch.0) {
+            let ep = Epoch(ep);
+            if let Some(delta) = data_handler.get(storage, &ep)? {
+                let mut slashed = token::Change::default();
+                for slash in slashes {
+                    if slash.epoch >= ep && slash.epoch <= epoch {
+                        slashed += decimal_mult_i128(
+                            slash.r#type.get_slash_rate(params),
+                            delta,
+                        );
+                    }
+                }
+                sum += delta - slashed;
+            }
+        }
+        Ok(sum)
+    }
+}
+
 /// Offset at pipeline length.
 #[derive(
     Debug,
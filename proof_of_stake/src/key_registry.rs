@@ -0,0 +1,502 @@
+//! Registry of all the PoS storage key patterns.
+//!
+//! Every pattern is a template of the key's segments separated by `/`. The
+//! first segment `{pos}` is always the PoS address. Other placeholders in
+//! braces stand for a variable segment. The keys themselves are constructed
+//! and recognized by the functions in the [`crate::storage`] module.
+
+/// PoS parameters. Type: [`crate::parameters::PosParams`].
+pub const PARAMS: &str = "{pos}/params";
+
+/// Look-up of a validator's address from the raw hash of its consensus key.
+/// Type: [`namada_core::types::address::Address`].
+pub const VALIDATOR_ADDRESS_RAW_HASH: &str =
+    "{pos}/address_raw_hash/{raw_hash}";
+
+/// A validator's epoched consensus key. Type:
+/// [`crate::types::ValidatorConsensusKeys`].
+pub const VALIDATOR_CONSENSUS_KEY: &str =
+    "{pos}/validator/{validator}/consensus_key";
+
+/// A validator's epoched commission rate. Type:
+/// [`crate::types::CommissionRates`].
+pub const VALIDATOR_COMMISSION_RATE: &str =
+    "{pos}/validator/{validator}/commission_rate";
+
+/// A validator's maximum commission rate change per epoch. Type:
+/// [`rust_decimal::Decimal`].
+pub const VALIDATOR_MAX_COMMISSION_RATE_CHANGE: &str =
+    "{pos}/validator/{validator}/max_commission_rate_change";
+
+/// A validator's announced commission rate change. Type:
+/// [`crate::types::CommissionRateAnnouncement`].
+pub const VALIDATOR_COMMISSION_RATE_ANNOUNCEMENT: &str =
+    "{pos}/validator/{validator}/commission_rate_announcement";
+
+/// A validator's self rewards products, keyed by epoch. Type:
+/// [`crate::types::RewardsProducts`].
+pub const VALIDATOR_SELF_REWARDS_PRODUCT: &str =
+    "{pos}/validator/{validator}/validator_rewards_product";
+
+/// A validator's delegation rewards products, keyed by epoch. Type:
+/// [`crate::types::RewardsProducts`].
+pub const VALIDATOR_DELEGATION_REWARDS_PRODUCT: &str =
+    "{pos}/validator/{validator}/delegation_rewards_product";
+
+/// The last epoch in which a validator's rewards products were updated. Type:
+/// [`namada_core::types::storage::Epoch`].
+pub const VALIDATOR_LAST_KNOWN_PRODUCT_EPOCH: &str =
+    "{pos}/validator/{validator}/last_known_rewards_product_epoch";
+
+/// A validator's epoched state. Type: [`crate::types::ValidatorStates`].
+pub const VALIDATOR_STATE: &str = "{pos}/validator/{validator}/state";
+
+/// A validator's epoched stake deltas. Type:
+/// [`crate::types::ValidatorDeltas`].
+pub const VALIDATOR_DELTAS: &str =
+    "{pos}/validator/{validator}/deltas/lazy_map/data/{epoch}";
+
+/// A validator's slashes. Type: [`crate::types::Slashes`].
+pub const VALIDATOR_SLASHES: &str = "{pos}/slash/{validator}/data/{index}";
+
+/// A validator's stake in the epoch of the slashable event of each of its
+/// slashes, keyed by the slash's index. Type:
+/// [`crate::types::SlashesStakeAtInfraction`].
+pub const VALIDATOR_SLASHES_STAKE: &str =
+    "{pos}/validator/{validator}/slashes_stake_at_infraction/data/{index}";
+
+/// A bond's epoched amounts, keyed by the bond's start epoch. Type:
+/// [`crate::types::Bonds`].
+pub const BOND: &str =
+    "{pos}/bond/{source}/{validator}/lazy_map/data/{start_epoch}";
+
+/// An unbond's amounts, keyed by the withdrawable epoch and the start epoch of
+/// the unbonded bond. Type: [`crate::types::Unbonds`].
+pub const UNBOND: &str = "{pos}/unbond/{source}/{validator}/data/\
+                          {withdraw_epoch}/data/{start_epoch}";
+
+/// The index of validators to which a source has bonds. Type: `()`.
+pub const DELEGATOR_VALIDATOR_INDEX: &str =
+    "{pos}/delegator_validators/{source}/{validator}";
+
+/// The delegator validator index entries of fully unbonded bonds, keyed by the
+/// epoch from which they can be removed. Type:
+/// [`crate::types::DelegatorValidatorIndexRemovals`].
+pub const DELEGATOR_VALIDATOR_INDEX_REMOVALS: &str =
+    "{pos}/delegator_validators_removals";
+
+/// Marker of the bonds that precede the delegator validator index having been
+/// indexed. Type: `()`.
+pub const DELEGATOR_VALIDATOR_INDEX_MIGRATED: &str =
+    "{pos}/delegator_validators_migrated";
+
+/// A position in the epoched consensus validator set. Type:
+/// [`namada_core::types::address::Address`].
+pub const CONSENSUS_VALIDATOR_SET: &str = "{pos}/validator_sets/consensus/\
+                                           lazy_map/data/{epoch}/data/\
+                                           {amount}/data/{position}";
+
+/// The epoched number of consensus validators. Type: `u64`.
+pub const NUM_CONSENSUS_VALIDATORS: &str = "{pos}/validator_sets/num_consensus";
+
+/// A position in the epoched below-capacity validator set. Type:
+/// [`namada_core::types::address::Address`].
+pub const BELOW_CAPACITY_VALIDATOR_SET: &str =
+    "{pos}/validator_sets/below_capacity/lazy_map/data/{epoch}/data/{amount}/\
+     data/{position}";
+
+/// The epoched positions of validators in the validator sets. Type:
+/// [`crate::types::ValidatorSetPositions`].
+pub const VALIDATOR_SET_POSITIONS: &str = "{pos}/validator_set_positions";
+
+/// The epoched total stake deltas of all validators. Type:
+/// [`crate::types::TotalDeltas`].
+pub const TOTAL_DELTAS: &str = "{pos}/total_deltas/lazy_map/data/{epoch}";
+
+/// The proposer of the previous block. Type:
+/// [`namada_core::types::address::Address`].
+pub const LAST_BLOCK_PROPOSER: &str = "{pos}/last_block_proposer";
+
+/// A consensus validator's accumulated rewards fractions in the current
+/// epoch. Type: [`rust_decimal::Decimal`].
+pub const CONSENSUS_VALIDATOR_REWARDS_ACCUMULATOR: &str =
+    "{pos}/validator_rewards_accumulator/data/{validator}";
+
+/// Snapshots of the rewards accumulator, keyed by epoch. Type:
+/// [`crate::types::RewardsAccumulatorSnapshots`].
+pub const REWARDS_ACCUMULATOR_SNAPSHOTS: &str =
+    "{pos}/validator_rewards_accumulator_snapshots";
+
+/// The set of consensus keys in use. Type:
+/// [`namada_core::types::key::common::PublicKey`].
+pub const CONSENSUS_KEYS: &str = "{pos}/consensus_keys";
+
+/// The set of tombstoned validators. Type:
+/// [`namada_core::types::address::Address`].
+pub const TOMBSTONED_VALIDATORS: &str = "{pos}/tombstoned_validators";
+
+/// Multisig bonds waiting for approvals, keyed by ID. Type:
+/// [`crate::types::PendingMultisigBond`].
+pub const PENDING_MULTISIG_BONDS: &str =
+    "{pos}/pending_multisig_bonds/data/{id}";
+
+/// The counter used to assign IDs to multisig bonds. Type: `u64`.
+pub const MULTISIG_BOND_COUNTER: &str = "{pos}/multisig_bond_counter";
+
+/// Statistics of the bonds, refreshed at the start of every epoch. Type:
+/// [`crate::types::BondsSummary`].
+pub const BONDS_SUMMARY: &str = "{pos}/bonds_summary";
+
+/// The votes included in a block, keyed by the epoch and the height of the
+/// signed block. Type: `Vec<`[`crate::types::VoteInfo`]`>`.
+pub const EPOCH_VOTES: &str = "{pos}/epoch_votes/data/{epoch}/data/{height}";
+
+/// The total amount of tokens burned from the slash pool. Type:
+/// [`token::Amount`](namada_core::types::token::Amount).
+pub const TOTAL_BURNED: &str = "{pos}/total_burned";
+
+/// Snapshots of the PoS system, keyed by the beacon epoch at the start of
+/// which they were taken. Type: [`crate::types::BeaconSnapshot`].
+pub const BEACON_SNAPSHOTS: &str = "{pos}/beacon_snapshots/data/{epoch}";
+
+/// The total amount of unbonded tokens that become withdrawable in an epoch,
+/// keyed by that epoch. Type:
+/// [`token::Amount`](namada_core::types::token::Amount).
+pub const TOTAL_UNBONDING: &str = "{pos}/total_unbonding/data/{epoch}";
+
+#[cfg(test)]
+mod tests {
+    use namada_core::ledger::storage_api::collections::lazy_map;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::address::Address;
+    use namada_core::types::storage::{Epoch, Key, KeySeg};
+
+    use super::*;
+    use crate::epoched::LAZY_MAP_SUB_KEY;
+    use crate::storage::*;
+    use crate::ADDRESS;
+
+    /// Render a key pattern with the given placeholder values.
+    fn render(pattern: &str, values: &[(&str, String)]) -> String {
+        let mut key = pattern.replace("{pos}", &ADDRESS.raw());
+        for (name, value) in values {
+            key = key.replace(&format!("{{{}}}", name), value);
+        }
+        key
+    }
+
+    /// Append the given string segments to a key.
+    fn push_segs(key: Key, segs: &[&str]) -> Key {
+        segs.iter().fold(key, |key, seg| {
+            key.push(&seg.to_string())
+                .expect("Cannot obtain a storage key")
+        })
+    }
+
+    /// Generate an example key for each pattern, check that it matches the
+    /// pattern and that it is recognized by the corresponding `is_*_key`
+    /// function.
+    #[test]
+    fn test_key_registry_patterns() {
+        let source = established_address_1();
+        let validator = established_address_2();
+        let bond_id = BondId {
+            source: source.clone(),
+            validator: validator.clone(),
+        };
+        let epoch = Epoch(5);
+        let withdraw_epoch = Epoch(12);
+        let source_seg = source.raw();
+        let validator_seg = validator.raw();
+        let epoch_seg = epoch.to_db_key().raw();
+        let withdraw_seg = withdraw_epoch.to_db_key().raw();
+        let data = lazy_map::DATA_SUBKEY;
+
+        let key = params_key();
+        assert_eq!(key.to_string(), render(PARAMS, &[]));
+        assert!(is_params_key(&key));
+
+        let key = validator_address_raw_hash_key("raw_hash");
+        assert_eq!(
+            key.to_string(),
+            render(
+                VALIDATOR_ADDRESS_RAW_HASH,
+                &[("raw_hash", "raw_hash".to_string())]
+            )
+        );
+        assert_eq!(is_validator_address_raw_hash_key(&key), Some("raw_hash"));
+
+        let validator_keys: [(&str, Key, fn(&Key) -> Option<&Address>); 7] = [
+            (
+                VALIDATOR_CONSENSUS_KEY,
+                validator_consensus_key_key(&validator),
+                is_validator_consensus_key_key,
+            ),
+            (
+                VALIDATOR_COMMISSION_RATE,
+                validator_commission_rate_key(&validator),
+                is_validator_commission_rate_key,
+            ),
+            (
+                VALIDATOR_MAX_COMMISSION_RATE_CHANGE,
+                validator_max_commission_rate_change_key(&validator),
+                is_validator_max_commission_rate_change_key,
+            ),
+            (
+                VALIDATOR_COMMISSION_RATE_ANNOUNCEMENT,
+                validator_commission_rate_announcement_key(&validator),
+                is_validator_commission_rate_announcement_key,
+            ),
+            (
+                VALIDATOR_SELF_REWARDS_PRODUCT,
+                validator_self_rewards_product_key(&validator),
+                is_validator_self_rewards_product_key,
+            ),
+            (
+                VALIDATOR_DELEGATION_REWARDS_PRODUCT,
+                validator_delegation_rewards_product_key(&validator),
+                is_validator_delegation_rewards_product_key,
+            ),
+            (
+                VALIDATOR_LAST_KNOWN_PRODUCT_EPOCH,
+                validator_last_known_product_epoch_key(&validator),
+                is_validator_last_known_product_epoch_key,
+            ),
+        ];
+        for (pattern, key, is_key) in validator_keys {
+            assert_eq!(
+                key.to_string(),
+                render(pattern, &[("validator", validator_seg.clone())])
+            );
+            assert_eq!(is_key(&key), Some(&validator), "{pattern}");
+        }
+
+        let key = validator_state_key(&validator);
+        assert_eq!(
+            key.to_string(),
+            render(VALIDATOR_STATE, &[("validator", validator_seg.clone())])
+        );
+        assert_eq!(is_validator_state_key(&key), Some(&validator));
+
+        let key = push_segs(
+            validator_deltas_key(&validator),
+            &[LAZY_MAP_SUB_KEY, data, &epoch_seg],
+        );
+        assert_eq!(
+            key.to_string(),
+            render(
+                VALIDATOR_DELTAS,
+                &[
+                    ("validator", validator_seg.clone()),
+                    ("epoch", epoch_seg.clone())
+                ]
+            )
+        );
+        assert_eq!(is_validator_deltas_key(&key), Some(&validator));
+
+        let index_seg = 0_u64.raw();
+        let key =
+            push_segs(validator_slashes_key(&validator), &[data, &index_seg]);
+        assert_eq!(
+            key.to_string(),
+            render(
+                VALIDATOR_SLASHES,
+                &[
+                    ("validator", validator_seg.clone()),
+                    ("index", index_seg.clone())
+                ]
+            )
+        );
+        assert_eq!(is_validator_slashes_key(&key), Some(validator.clone()));
+
+        let key = push_segs(
+            validator_slashes_stake_key(&validator),
+            &[data, &index_seg],
+        );
+        assert_eq!(
+            key.to_string(),
+            render(
+                VALIDATOR_SLASHES_STAKE,
+                &[("validator", validator_seg.clone()), ("index", index_seg)]
+            )
+        );
+
+        let key = push_segs(
+            bond_key(&bond_id),
+            &[LAZY_MAP_SUB_KEY, data, &epoch_seg],
+        );
+        assert_eq!(
+            key.to_string(),
+            render(
+                BOND,
+                &[
+                    ("source", source_seg.clone()),
+                    ("validator", validator_seg.clone()),
+                    ("start_epoch", epoch_seg.clone())
+                ]
+            )
+        );
+        assert_eq!(is_bond_key(&key), Some((bond_id.clone(), epoch)));
+
+        let key = push_segs(
+            unbond_key(&bond_id),
+            &[data, &withdraw_seg, data, &epoch_seg],
+        );
+        assert_eq!(
+            key.to_string(),
+            render(
+                UNBOND,
+                &[
+                    ("source", source_seg.clone()),
+                    ("validator", validator_seg.clone()),
+                    ("withdraw_epoch", withdraw_seg),
+                    ("start_epoch", epoch_seg.clone())
+                ]
+            )
+        );
+        assert_eq!(
+            is_unbond_key(&key),
+            Some((bond_id.clone(), epoch, withdraw_epoch))
+        );
+
+        let key = delegator_validator_index_key(&source, &validator);
+        assert_eq!(
+            key.to_string(),
+            render(
+                DELEGATOR_VALIDATOR_INDEX,
+                &[("source", source_seg), ("validator", validator_seg.clone())]
+            )
+        );
+        assert_eq!(is_delegator_validator_index_key(&key), Some(bond_id));
+
+        let key = delegator_validator_index_removals_key();
+        assert_eq!(
+            key.to_string(),
+            render(DELEGATOR_VALIDATOR_INDEX_REMOVALS, &[])
+        );
+
+        let key = delegator_validator_index_migrated_key();
+        assert_eq!(
+            key.to_string(),
+            render(DELEGATOR_VALIDATOR_INDEX_MIGRATED, &[])
+        );
+
+        let set_values = [
+            ("epoch", epoch_seg.clone()),
+            ("amount", "1000".to_string()),
+            ("position", "0".to_string()),
+        ];
+        let set_sub_key =
+            [LAZY_MAP_SUB_KEY, data, &epoch_seg, data, "1000", data, "0"];
+        let key = push_segs(consensus_validator_set_key(), &set_sub_key);
+        assert_eq!(
+            key.to_string(),
+            render(CONSENSUS_VALIDATOR_SET, &set_values)
+        );
+        assert!(is_consensus_validator_set_key(&key));
+        assert!(!is_below_capacity_validator_set_key(&key));
+
+        let key = push_segs(below_capacity_validator_set_key(), &set_sub_key);
+        assert_eq!(
+            key.to_string(),
+            render(BELOW_CAPACITY_VALIDATOR_SET, &set_values)
+        );
+        assert!(is_below_capacity_validator_set_key(&key));
+        assert!(!is_consensus_validator_set_key(&key));
+
+        let key = num_consensus_validators_key();
+        assert_eq!(key.to_string(), render(NUM_CONSENSUS_VALIDATORS, &[]));
+
+        let key = validator_set_positions_key();
+        assert_eq!(key.to_string(), render(VALIDATOR_SET_POSITIONS, &[]));
+
+        let key = push_segs(
+            total_deltas_key(),
+            &[LAZY_MAP_SUB_KEY, data, &epoch_seg],
+        );
+        assert_eq!(
+            key.to_string(),
+            render(TOTAL_DELTAS, &[("epoch", epoch_seg.clone())])
+        );
+        assert_eq!(is_total_deltas_key(&key), Some(&epoch_seg));
+
+        let key = last_block_proposer_key();
+        assert_eq!(key.to_string(), render(LAST_BLOCK_PROPOSER, &[]));
+        assert!(is_last_block_proposer_key(&key));
+
+        let key = consensus_validator_rewards_accumulator_key()
+            .push(&data.to_string())
+            .unwrap()
+            .push(&validator.to_db_key())
+            .unwrap();
+        assert_eq!(
+            key.to_string(),
+            render(
+                CONSENSUS_VALIDATOR_REWARDS_ACCUMULATOR,
+                &[("validator", validator_seg)]
+            )
+        );
+        assert_eq!(
+            is_consensus_validator_rewards_accumulator_key(&key),
+            Some(&validator)
+        );
+
+        let key = rewards_accumulator_snapshots_key();
+        assert_eq!(key.to_string(), render(REWARDS_ACCUMULATOR_SNAPSHOTS, &[]));
+
+        let key = consensus_keys_key();
+        assert_eq!(key.to_string(), render(CONSENSUS_KEYS, &[]));
+        assert!(is_consensus_keys_key(&key));
+
+        let key = tombstoned_validators_key();
+        assert_eq!(key.to_string(), render(TOMBSTONED_VALIDATORS, &[]));
+        assert!(is_tombstoned_validators_key(&key));
+
+        let id_seg = 3_u64.raw();
+        let key = push_segs(pending_multisig_bonds_key(), &[data, &id_seg]);
+        assert_eq!(
+            key.to_string(),
+            render(PENDING_MULTISIG_BONDS, &[("id", id_seg)])
+        );
+        assert!(is_pending_multisig_bonds_key(&key));
+        assert_eq!(is_pending_multisig_bond_key(&key), Some(3));
+
+        let key = multisig_bond_counter_key();
+        assert_eq!(key.to_string(), render(MULTISIG_BOND_COUNTER, &[]));
+        assert!(is_multisig_bond_counter_key(&key));
+
+        let key = bonds_summary_key();
+        assert_eq!(key.to_string(), render(BONDS_SUMMARY, &[]));
+
+        let height_seg = 10_u64.raw();
+        let key = push_segs(
+            epoch_votes_key(),
+            &[data, &epoch_seg, data, &height_seg],
+        );
+        assert_eq!(
+            key.to_string(),
+            render(
+                EPOCH_VOTES,
+                &[("epoch", epoch_seg.clone()), ("height", height_seg)]
+            )
+        );
+
+        let key = total_burned_key();
+        assert_eq!(key.to_string(), render(TOTAL_BURNED, &[]));
+        assert!(is_total_burned_key(&key));
+
+        let key = push_segs(beacon_snapshots_key(), &[data, &epoch_seg]);
+        assert_eq!(
+            key.to_string(),
+            render(BEACON_SNAPSHOTS, &[("epoch", epoch_seg.clone())])
+        );
+
+        let key = push_segs(total_unbonding_key(), &[data, &epoch_seg]);
+        assert_eq!(
+            key.to_string(),
+            render(TOTAL_UNBONDING, &[("epoch", epoch_seg)])
+        );
+    }
+}
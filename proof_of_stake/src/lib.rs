@@ -14,6 +14,7 @@
 
 pub mod btree_set;
 pub mod epoched;
+pub mod key_registry;
 pub mod parameters;
 pub mod pos_queries;
 pub mod rewards;
@@ -25,11 +26,15 @@ pub mod types;
 mod tests;
 
 use core::fmt::Debug;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::num::TryFromIntError;
 
 use borsh::BorshDeserialize;
 use epoched::{EpochOffset, OffsetPipelineLen};
+use namada_core::ledger::parameters::storage::{
+    get_epoch_duration_storage_key, get_epochs_per_year_key,
+};
+use namada_core::ledger::parameters::EpochDuration;
 use namada_core::ledger::storage_api::collections::lazy_map::{
     NestedSubKey, SubKey,
 };
@@ -43,28 +48,38 @@ use namada_core::types::key::{
     common, tm_consensus_key_raw_hash, PublicKeyTmRawHash,
 };
 pub use namada_core::types::storage::Epoch;
+use namada_core::types::storage::SafeEpochArithmetic;
 use namada_core::types::token;
 use once_cell::unsync::Lazy;
 use parameters::PosParams;
 use rewards::PosRewardsCalculator;
 use rust_decimal::Decimal;
 use storage::{
-    bonds_for_source_prefix, bonds_prefix, consensus_keys_key,
+    bonds_for_source_prefix, bonds_prefix, bonds_summary_key,
+    consensus_keys_key, delegator_validator_index_key,
+    delegator_validator_index_migrated_key, delegator_validator_index_prefix,
     get_validator_address_from_bond, into_tm_voting_power, is_bond_key,
-    is_unbond_key, is_validator_slashes_key, last_block_proposer_key,
-    mult_amount, mult_change_to_amount, num_consensus_validators_key,
-    params_key, slashes_prefix, unbonds_for_source_prefix, unbonds_prefix,
-    validator_address_raw_hash_key, validator_max_commission_rate_change_key,
-    BondDetails, BondsAndUnbondsDetail, BondsAndUnbondsDetails,
-    ReverseOrdTokenAmount, RewardsAccumulator, UnbondDetails,
+    is_delegator_validator_index_key, is_unbond_key, is_validator_slashes_key,
+    last_block_proposer_key, mult_amount, mult_change_to_amount,
+    num_consensus_validators_key, params_key, slashes_prefix, total_burned_key,
+    unbonds_for_source_prefix, unbonds_prefix, validator_address_raw_hash_key,
+    validator_commission_rate_announcement_key,
+    validator_max_commission_rate_change_key, BondDetails,
+    BondsAndUnbondsDetail, BondsAndUnbondsDetails, ReverseOrdTokenAmount,
+    RewardsAccumulator, RewardsAccumulatorSnapshots, SlashPoolDestination,
+    UnbondDetails,
 };
 use thiserror::Error;
 use types::{
-    decimal_mult_i128, decimal_mult_u64, BelowCapacityValidatorSet,
-    BelowCapacityValidatorSets, BondId, Bonds, CommissionRates,
+    decimal_mult_i128, decimal_mult_u64, BeaconSnapshot, BeaconSnapshots,
+    BelowCapacityValidatorSet, BelowCapacityValidatorSets, BondId, Bonds,
+    BondsSummary, CommissionRateAnnouncement, CommissionRates,
     ConsensusValidator, ConsensusValidatorSet, ConsensusValidatorSets,
-    GenesisValidator, Position, RewardsProducts, Slash, SlashType, Slashes,
-    TotalDeltas, Unbonds, ValidatorConsensusKeys, ValidatorDeltas,
+    DelegatorValidatorIndexRemovals, EpochedVotes, GenesisValidator,
+    MultisigBond, PendingMultisigBond, PendingMultisigBonds, Position,
+    RewardsProducts, SimulatedEpochResult, Slash, SlashType, Slashes,
+    SlashesStakeAtInfraction, TombstonedValidators, TotalDeltas,
+    TotalUnbonding, Unbonds, ValidatorConsensusKeys, ValidatorDeltas,
     ValidatorPositionAddresses, ValidatorSetPositions, ValidatorSetUpdate,
     ValidatorState, ValidatorStates, VoteInfo, WeightedValidator,
 };
@@ -76,6 +91,19 @@ pub const ADDRESS: Address = Address::Internal(InternalAddress::PoS);
 pub const SLASH_POOL_ADDRESS: Address =
     Address::Internal(InternalAddress::PosSlashPool);
 
+/// Address of the PoS slash pool account
+pub fn slash_pool_address() -> Address {
+    SLASH_POOL_ADDRESS
+}
+
+/// Address of the PoS burn account
+pub const BURN_ADDRESS: Address = Address::Internal(InternalAddress::PosBurn);
+
+/// Address of the PoS burn account
+pub fn burn_address() -> Address {
+    BURN_ADDRESS
+}
+
 /// Address of the staking token (i.e. the native token)
 pub fn staking_token_address(storage: &impl StorageRead) -> Address {
     storage
@@ -83,6 +111,19 @@ pub fn staking_token_address(storage: &impl StorageRead) -> Address {
         .expect("Must be able to read native token address")
 }
 
+/// Estimate the number of blocks per epoch from the minimum number of blocks
+/// of the epoch duration parameter, to show the approximate block height of
+/// an epoch in logs with [`Epoch::format_with_block`].
+pub fn read_blocks_per_epoch<S>(storage: &S) -> storage_api::Result<u64>
+where
+    S: StorageRead,
+{
+    let epoch_duration: EpochDuration = storage
+        .read(&get_epoch_duration_storage_key())?
+        .ok_or_err_msg("Epoch duration should always be set")?;
+    Ok(epoch_duration.min_num_of_blocks)
+}
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum GenesisError {
@@ -102,6 +143,8 @@ pub enum InflationError {
 pub enum BecomeValidatorError {
     #[error("The given address {0} is already a validator")]
     AlreadyValidator(Address),
+    #[error("The given address {0} belongs to a tombstoned validator")]
+    TombstonedValidator(Address),
 }
 
 #[allow(missing_docs)]
@@ -116,8 +159,15 @@ pub enum BondError {
     SourceMustNotBeAValidator(Address),
     #[error("The given validator address {0} is inactive")]
     InactiveValidator(Address),
+    #[error("The given validator address {0} is tombstoned")]
+    TombstonedValidator(Address),
     #[error("Voting power overflow: {0}")]
     VotingPowerOverflow(TryFromIntError),
+    #[error(
+        "The consensus validator set size {0} exceeds the maximum number of \
+         validator slots {1}"
+    )]
+    ConsensusSetOverflow(u64, u64),
 }
 
 #[allow(missing_docs)]
@@ -159,6 +209,11 @@ pub enum SlashError {
     VotingPowerOverflow(TryFromIntError),
     #[error("Unexpected negative stake {0} for validator {1}")]
     NegativeStake(i128, Address),
+    #[error(
+        "The validator {0} cannot be slashed for an infraction committed in \
+         the {1:?} state"
+    )]
+    ValidatorNotSlashable(Address, ValidatorState),
 }
 
 #[allow(missing_docs)]
@@ -168,6 +223,8 @@ pub enum CommissionRateChangeError {
     NegativeRate(Decimal, Address),
     #[error("Rate change of {0} is too large for validator {1}")]
     RateChangeTooLarge(Decimal, Address),
+    #[error("Unexpected commission rate {0} larger than 1 for validator {1}")]
+    RateAboveOne(Decimal, Address),
     #[error(
         "There is no maximum rate change written in storage for validator {0}"
     )]
@@ -176,6 +233,48 @@ pub enum CommissionRateChangeError {
     CannotWrite(Address),
     #[error("Cannot read storage for validator {0}")]
     CannotRead(Address),
+    #[error("No commission rate change was announced for validator {0}")]
+    NotAnnounced(Address),
+    #[error(
+        "Commission rate {0} differs from the rate {1} announced for \
+         validator {2}"
+    )]
+    NotAsAnnounced(Decimal, Decimal, Address),
+    #[error(
+        "Commission rate of validator {0} cannot be changed before epoch {1}"
+    )]
+    NoticePeriodNotElapsed(Address, Epoch),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum ValidatorStateError {
+    #[error("Invalid state transition from {1:?} to {2:?} for validator {0}")]
+    InvalidTransition(Address, ValidatorState, ValidatorState),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum MultisigBondError {
+    #[error("The sources of a multisig bond must be unique")]
+    DuplicateSources,
+    #[error(
+        "The threshold of a multisig bond must be between 1 and the number of \
+         sources {1}, got {0}"
+    )]
+    InvalidThreshold(u32, usize),
+    #[error("No pending multisig bond with ID {0} could be found")]
+    NotFound(u64),
+    #[error("The multisig bond with ID {0} has expired in epoch {1}")]
+    Expired(u64, Epoch),
+    #[error(
+        "The address {1} is not a source of the multisig bond with ID {0}"
+    )]
+    NotASource(u64, Address),
+    #[error(
+        "The address {1} has already approved the multisig bond with ID {0}"
+    )]
+    AlreadyApproved(u64, Address),
 }
 
 // ------------------------------------------------------------------------------------------
@@ -213,12 +312,30 @@ impl From<CommissionRateChangeError> for storage_api::Error {
     }
 }
 
+impl From<SlashError> for storage_api::Error {
+    fn from(err: SlashError) -> Self {
+        Self::new(err)
+    }
+}
+
 impl From<InflationError> for storage_api::Error {
     fn from(err: InflationError) -> Self {
         Self::new(err)
     }
 }
 
+impl From<MultisigBondError> for storage_api::Error {
+    fn from(err: MultisigBondError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<ValidatorStateError> for storage_api::Error {
+    fn from(err: ValidatorStateError) -> Self {
+        Self::new(err)
+    }
+}
+
 /// Get the storage handle to the epoched consensus validator set
 pub fn consensus_validator_set_handle() -> ConsensusValidatorSets {
     let key = storage::consensus_validator_set_key();
@@ -300,6 +417,36 @@ pub fn validator_slashes_handle(validator: &Address) -> Slashes {
     Slashes::open(key)
 }
 
+/// Get the storage handle to a PoS validator's stake at the infraction epochs
+/// of its slashes
+pub fn validator_slashes_stake_handle(
+    validator: &Address,
+) -> SlashesStakeAtInfraction {
+    let key = storage::validator_slashes_stake_key(validator);
+    SlashesStakeAtInfraction::open(key)
+}
+
+/// Get the storage handle to the set of tombstoned validators
+pub fn tombstoned_validators_handle() -> TombstonedValidators {
+    let key = storage::tombstoned_validators_key();
+    TombstonedValidators::open(key)
+}
+
+/// Get the storage handle to the delegator validator index entries of fully
+/// unbonded bonds, keyed by the epoch from which they can be removed
+pub fn delegator_validator_index_removals_handle(
+) -> DelegatorValidatorIndexRemovals {
+    let key = storage::delegator_validator_index_removals_key();
+    DelegatorValidatorIndexRemovals::open(key)
+}
+
+/// Get the storage handle to the multisig bonds that are waiting for
+/// approvals
+pub fn pending_multisig_bonds_handle() -> PendingMultisigBonds {
+    let key = storage::pending_multisig_bonds_key();
+    PendingMultisigBonds::open(key)
+}
+
 /// Get the storage handle to the rewards accumulator for the consensus
 /// validators in a given epoch
 pub fn rewards_accumulator_handle() -> RewardsAccumulator {
@@ -307,6 +454,34 @@ pub fn rewards_accumulator_handle() -> RewardsAccumulator {
     RewardsAccumulator::open(key)
 }
 
+/// Get the storage handle to the votes included in the blocks of every epoch,
+/// keyed by the epoch and the height of the signed block
+pub fn epoch_votes_handle() -> EpochedVotes {
+    let key = storage::epoch_votes_key();
+    EpochedVotes::open(key)
+}
+
+/// Get the storage handle to the total amounts of unbonded tokens that become
+/// withdrawable in an epoch, keyed by that epoch
+pub fn total_unbonding_handle() -> TotalUnbonding {
+    let key = storage::total_unbonding_key();
+    TotalUnbonding::open(key)
+}
+
+/// Get the storage handle to the snapshots of the PoS system, keyed by the
+/// beacon epoch at the start of which they were taken
+pub fn beacon_snapshots_handle() -> BeaconSnapshots {
+    let key = storage::beacon_snapshots_key();
+    BeaconSnapshots::open(key)
+}
+
+/// Get the storage handle to the snapshots of the rewards accumulator, keyed
+/// by the epoch at the end of which they were taken
+pub fn epoched_rewards_snapshot_handle() -> RewardsAccumulatorSnapshots {
+    let key = storage::rewards_accumulator_snapshots_key();
+    RewardsAccumulatorSnapshots::open(key)
+}
+
 /// Get the storage handle to a validator's self rewards products
 pub fn validator_rewards_products_handle(
     validator: &Address,
@@ -336,6 +511,8 @@ where
 {
     tracing::debug!("Initializing PoS genesis");
     write_pos_params(storage, params.clone())?;
+    // All the bonds are indexed from genesis on, there's nothing to migrate
+    storage.write(&delegator_validator_index_migrated_key(), ())?;
 
     let mut total_bonded = token::Amount::default();
     consensus_validator_set_handle().init(storage, current_epoch)?;
@@ -390,6 +567,8 @@ where
             delta,
             current_epoch,
         )?;
+        storage
+            .write(&delegator_validator_index_key(&address, &address), ())?;
         validator_commission_rate_handle(&address).init_at_genesis(
             storage,
             commission_rate,
@@ -470,6 +649,51 @@ where
     storage.write(&validator_address_raw_hash_key(raw_hash), validator)
 }
 
+/// Remove PoS validator's address raw hash of a consensus key that is no
+/// longer in use. The mapping is needed to find the validator from evidence
+/// of its misbehaviour, so it must be kept until such evidence can no longer
+/// be submitted for the given key.
+pub fn remove_validator_address_raw_hash<S>(
+    storage: &mut S,
+    consensus_key: &common::PublicKey,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let raw_hash = tm_consensus_key_raw_hash(consensus_key);
+    storage.delete(&validator_address_raw_hash_key(raw_hash))
+}
+
+/// Write PoS validator's state at the given offset from the current epoch.
+/// The transition from the validator's previous state at that epoch, if any,
+/// is validated before the new state is written.
+pub fn write_validator_state<S>(
+    storage: &mut S,
+    params: &PosParams,
+    validator: &Address,
+    state: ValidatorState,
+    current_epoch: Epoch,
+    offset: u64,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let handle = validator_state_handle(validator);
+    let prev_state =
+        handle.get(storage, current_epoch.saturating_add(offset), params)?;
+    if let Some(prev_state) = prev_state {
+        if !prev_state.can_transition_to(state) {
+            return Err(ValidatorStateError::InvalidTransition(
+                validator.clone(),
+                prev_state,
+                state,
+            )
+            .into());
+        }
+    }
+    handle.set(storage, state, current_epoch, offset)
+}
+
 /// Read PoS validator's max commission rate change.
 pub fn read_validator_max_commission_rate_change<S>(
     storage: &S,
@@ -482,6 +706,24 @@ where
     storage.read(&key)
 }
 
+/// Read PoS validator's max commission rate change, which must exist in
+/// storage. Returns [`CommissionRateChangeError::NoMaxSetInStorage`] if it
+/// doesn't.
+pub fn read_validator_max_commission_rate_change_must_exist<S>(
+    storage: &S,
+    validator: &Address,
+) -> storage_api::Result<Decimal>
+where
+    S: StorageRead,
+{
+    read_validator_max_commission_rate_change(storage, validator)?.ok_or_else(
+        || {
+            CommissionRateChangeError::NoMaxSetInStorage(validator.clone())
+                .into()
+        },
+    )
+}
+
 /// Write PoS validator's max commission rate change.
 pub fn write_validator_max_commission_rate_change<S>(
     storage: &mut S,
@@ -495,14 +737,61 @@ where
     storage.write(&key, change)
 }
 
+/// Read PoS validator's announced commission rate change.
+pub fn read_validator_commission_rate_announcement<S>(
+    storage: &S,
+    validator: &Address,
+) -> storage_api::Result<Option<CommissionRateAnnouncement>>
+where
+    S: StorageRead,
+{
+    let key = validator_commission_rate_announcement_key(validator);
+    storage.read(&key)
+}
+
+/// Announce a change of a validator's commission rate. The change can be
+/// applied with [`change_validator_commission_rate`] once the
+/// `commission_change_notice_epochs` PoS parameter have passed, giving
+/// delegators advance notice. A new announcement replaces the previous one.
+pub fn announce_validator_commission_rate_change<S>(
+    storage: &mut S,
+    validator: &Address,
+    new_rate: Decimal,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if new_rate < Decimal::ZERO {
+        return Err(CommissionRateChangeError::NegativeRate(
+            new_rate,
+            validator.clone(),
+        )
+        .into());
+    }
+    if new_rate > Decimal::ONE {
+        return Err(CommissionRateChangeError::RateAboveOne(
+            new_rate,
+            validator.clone(),
+        )
+        .into());
+    }
+    let key = validator_commission_rate_announcement_key(validator);
+    storage.write(
+        &key,
+        CommissionRateAnnouncement {
+            announced_epoch: current_epoch,
+            new_rate,
+        },
+    )
+}
+
 /// Read number of consensus PoS validators.
 pub fn read_num_consensus_validators<S>(storage: &S) -> storage_api::Result<u64>
 where
     S: StorageRead,
 {
-    Ok(storage
-        .read(&num_consensus_validators_key())?
-        .unwrap_or_default())
+    storage.read_or_default(&num_consensus_validators_key())
 }
 
 /// Read number of consensus PoS validators.
@@ -574,6 +863,27 @@ where
     Ok(amount)
 }
 
+/// Get the change of a validator's stake from `from_epoch` to `to_epoch`, in
+/// token units. A validator without any stake in an epoch is considered to
+/// have zero stake in it.
+pub fn get_validator_power_change<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    from_epoch: Epoch,
+    to_epoch: Epoch,
+) -> storage_api::Result<token::Change>
+where
+    S: StorageRead,
+{
+    let stake_from =
+        read_validator_stake(storage, params, validator, from_epoch)?
+            .unwrap_or_default();
+    let stake_to = read_validator_stake(storage, params, validator, to_epoch)?
+        .unwrap_or_default();
+    Ok(stake_to.change() - stake_from.change())
+}
+
 /// Add or remove PoS validator's stake delta value
 pub fn update_validator_deltas<S>(
     storage: &mut S,
@@ -588,7 +898,7 @@ where
     let handle = validator_deltas_handle(validator);
     let offset = OffsetPipelineLen::value(params);
     let val = handle
-        .get_delta_val(storage, current_epoch + offset, params)?
+        .get_delta_val(storage, current_epoch.saturating_add(offset), params)?
         .unwrap_or_default();
     handle.set(storage, val + delta, current_epoch, offset)
 }
@@ -670,6 +980,39 @@ where
         .collect()
 }
 
+/// Read all addresses from consensus validator set with their Tendermint
+/// voting power.
+pub fn read_consensus_validator_set_voting_powers<S>(
+    storage: &S,
+    params: &PosParams,
+    epoch: namada_core::types::storage::Epoch,
+) -> storage_api::Result<Vec<(Address, u64)>>
+where
+    S: StorageRead,
+{
+    consensus_validator_set_handle()
+        .at(&epoch)
+        .iter(storage)?
+        .map(|res| {
+            res.map(
+                |(
+                    NestedSubKey::Data {
+                        key: bonded_stake,
+                        nested_sub_key: _,
+                    },
+                    address,
+                )| {
+                    let voting_power = decimal_mult_u64(
+                        params.tm_votes_per_token,
+                        u64::from(bonded_stake),
+                    );
+                    (address, voting_power)
+                },
+            )
+        })
+        .collect()
+}
+
 /// Read all addresses from below-capacity validator set with their stake.
 pub fn read_below_capacity_validator_set_addresses_with_stake<S>(
     storage: &S,
@@ -700,6 +1043,56 @@ where
         .collect()
 }
 
+/// Preview the consensus validator set of the epoch following the
+/// `current_epoch`, so that validators and delegators can plan ahead.
+///
+/// The validator sets are normally already populated up to the pipeline
+/// epoch, in which case the set is read from storage. Otherwise, it's
+/// computed from the validators in the current epoch's consensus and
+/// below-capacity sets, using their stake in the next epoch, which includes
+/// any pending bond and unbond changes.
+pub fn preview_next_epoch_consensus_set<S>(
+    storage: &S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> storage_api::Result<HashSet<WeightedValidator>>
+where
+    S: StorageRead,
+{
+    let next_epoch = current_epoch.next();
+    if !consensus_validator_set_handle()
+        .at(&next_epoch)
+        .is_empty(storage)?
+    {
+        return read_consensus_validator_set_addresses_with_stake(
+            storage, next_epoch,
+        );
+    }
+
+    let consensus =
+        read_consensus_validator_set_addresses(storage, current_epoch)?;
+    let below_capacity =
+        read_below_capacity_validator_set_addresses(storage, current_epoch)?;
+    let mut candidates = Vec::new();
+    for address in consensus.into_iter().chain(below_capacity) {
+        let bonded_stake =
+            read_validator_stake(storage, params, &address, next_epoch)?
+                .unwrap_or_default();
+        if bonded_stake > token::Amount::default() {
+            candidates.push(WeightedValidator {
+                bonded_stake,
+                address,
+            });
+        }
+    }
+    // Sort by descending stake and take as many as there are slots
+    candidates.sort_by(|a, b| b.cmp(a));
+    Ok(candidates
+        .into_iter()
+        .take(params.max_validator_slots as usize)
+        .collect())
+}
+
 /// Read all validator addresses.
 pub fn read_all_validator_addresses<S>(
     storage: &S,
@@ -715,6 +1108,44 @@ where
     Ok(addresses)
 }
 
+/// Preview the changes to the consensus validator set that take effect with
+/// the transition to the next epoch, without writing to storage.
+///
+/// The validator sets are copied up to the pipeline offset at the start of
+/// every epoch and slashes are applied as soon as the evidence is processed,
+/// so the consensus set of the next epoch is already in storage and nothing
+/// has to be applied to a copy of the state.
+pub fn simulate_epoch_transition<S>(
+    storage: &S,
+    current_epoch: Epoch,
+) -> storage_api::Result<SimulatedEpochResult>
+where
+    S: StorageRead,
+{
+    let next_epoch = current_epoch.next();
+    let current_addresses =
+        read_consensus_validator_set_addresses(storage, current_epoch)?;
+    let new_consensus_set =
+        read_consensus_validator_set_addresses_with_stake(storage, next_epoch)?;
+    let new_addresses: HashSet<Address> = new_consensus_set
+        .iter()
+        .map(|validator| validator.address.clone())
+        .collect();
+    let promotions = new_addresses
+        .difference(&current_addresses)
+        .cloned()
+        .collect();
+    let demotions = current_addresses
+        .difference(&new_addresses)
+        .cloned()
+        .collect();
+    Ok(SimulatedEpochResult {
+        new_consensus_set,
+        promotions,
+        demotions,
+    })
+}
+
 /// Update PoS total deltas.
 /// Note: for EpochedDelta, write the value to change storage by
 pub fn update_total_deltas<S>(
@@ -729,7 +1160,7 @@ where
     let handle = total_deltas_handle();
     let offset = OffsetPipelineLen::value(params);
     let val = handle
-        .get_delta_val(storage, current_epoch + offset, params)?
+        .get_delta_val(storage, current_epoch.saturating_add(offset), params)?
         .unwrap_or_default();
     handle.set(storage, val + delta, current_epoch, offset)
 }
@@ -748,8 +1179,10 @@ where
     Ok(state.is_some())
 }
 
-/// Check if the provided address is a delegator address, optionally at a
-/// particular epoch
+/// Check if the provided address is a delegator address, optionally as of a
+/// particular epoch. With an epoch, the address is a delegator if the sum of
+/// its bond deltas up to and including the epoch is non-zero for at least one
+/// validator other than itself.
 pub fn is_delegator<S>(
     storage: &S,
     address: &Address,
@@ -761,13 +1194,23 @@ where
     let prefix = bonds_for_source_prefix(address);
     match epoch {
         Some(epoch) => {
+            let params = read_pos_params(storage)?;
+            let mut checked_validators: HashSet<Address> = HashSet::new();
             let iter = storage_api::iter_prefix_bytes(storage, &prefix)?;
             for res in iter {
                 let (key, _) = res?;
                 if let Some((bond_id, bond_epoch)) = is_bond_key(&key) {
-                    if bond_id.source != bond_id.validator
-                        && bond_epoch <= epoch
+                    if bond_id.is_self_bond()
+                        || bond_epoch > epoch
+                        || !checked_validators.insert(bond_id.validator.clone())
                     {
+                        continue;
+                    }
+                    let bonded =
+                        bond_handle(&bond_id.source, &bond_id.validator)
+                            .get_sum(storage, epoch, &params)?
+                            .unwrap_or_default();
+                    if bonded > 0 {
                         return Ok(true);
                     }
                 }
@@ -780,44 +1223,267 @@ where
     }
 }
 
-/// Self-bond tokens to a validator when `source` is `None` or equal to
-/// the `validator` address, or delegate tokens from the `source` to the
-/// `validator`.
-pub fn bond_tokens<S>(
+/// Find the IDs of all the bonds in the PoS system with a non-zero amount in
+/// any epoch.
+fn find_active_bond_ids<S>(storage: &S) -> storage_api::Result<HashSet<BondId>>
+where
+    S: StorageRead,
+{
+    let mut bond_ids = HashSet::new();
+    for res in storage_api::iter_prefix_bytes(storage, &bonds_prefix())? {
+        let (key, val_bytes) = res?;
+        if let Some((bond_id, _start)) = is_bond_key(&key) {
+            let change = token::Change::try_from_slice(&val_bytes)
+                .into_storage_result()?;
+            if change != token::Change::default() {
+                bond_ids.insert(bond_id);
+            }
+        }
+    }
+    Ok(bond_ids)
+}
+
+/// Count the bonds in the whole PoS system with a non-zero amount, including
+/// self-bonds. A bond is counted once regardless of the number of epochs in
+/// which it was bonded.
+pub fn count_total_active_bonds<S>(storage: &S) -> storage_api::Result<u64>
+where
+    S: StorageRead,
+{
+    Ok(find_active_bond_ids(storage)?.len() as u64)
+}
+
+/// Count the distinct sources of delegations with a non-zero amount in the
+/// whole PoS system. Self-bonds are not delegations and are not counted.
+pub fn count_unique_delegators<S>(storage: &S) -> storage_api::Result<u64>
+where
+    S: StorageRead,
+{
+    let delegators: HashSet<Address> = find_active_bond_ids(storage)?
+        .into_iter()
+        .filter(|bond_id| !bond_id.is_self_bond())
+        .map(|bond_id| bond_id.source)
+        .collect();
+    Ok(delegators.len() as u64)
+}
+
+/// Read the summary of the bonds in the PoS system, if it has been computed.
+pub fn read_bonds_summary<S>(
+    storage: &S,
+) -> storage_api::Result<Option<BondsSummary>>
+where
+    S: StorageRead,
+{
+    storage.read(&bonds_summary_key())
+}
+
+/// Recompute the summary of the bonds in the PoS system and write it to
+/// storage. This iterates all the bonds, so it should only be called
+/// periodically, e.g. at the start of a new epoch.
+pub fn update_bonds_summary<S>(
     storage: &mut S,
-    source: Option<&Address>,
-    validator: &Address,
-    amount: token::Amount,
     current_epoch: Epoch,
 ) -> storage_api::Result<()>
 where
     S: StorageRead + StorageWrite,
 {
-    let amount = amount.change();
-    tracing::debug!("Bonding token amount {amount} at epoch {current_epoch}");
-    let params = read_pos_params(storage)?;
-    let pipeline_epoch = current_epoch + params.pipeline_len;
-    if let Some(source) = source {
-        if source != validator
-            && is_validator(storage, source, &params, pipeline_epoch)?
-        {
-            return Err(
-                BondError::SourceMustNotBeAValidator(source.clone()).into()
-            );
-        }
-    }
-    let state = validator_state_handle(validator).get(
-        storage,
-        pipeline_epoch,
-        &params,
-    )?;
-    if state.is_none() {
-        return Err(BondError::NotAValidator(validator.clone()).into());
-    }
+    let bond_ids = find_active_bond_ids(storage)?;
+    let delegators: HashSet<&Address> = bond_ids
+        .iter()
+        .filter(|bond_id| !bond_id.is_self_bond())
+        .map(|bond_id| &bond_id.source)
+        .collect();
+    let summary = BondsSummary {
+        epoch: current_epoch,
+        active_bonds: bond_ids.len() as u64,
+        unique_delegators: delegators.len() as u64,
+    };
+    storage.write(&bonds_summary_key(), summary)
+}
 
-    let validator_state_handle = validator_state_handle(validator);
-    let source = source.unwrap_or(validator);
-    let bond_handle = bond_handle(source, validator);
+/// Check if the given epoch is a beacon epoch, i.e. a multiple of the
+/// `interval`. When the `interval` is `0`, there are no beacon epochs.
+pub fn is_beacon_epoch(epoch: Epoch, interval: u64) -> bool {
+    interval != 0 && epoch.0 % interval == 0
+}
+
+/// Find the first beacon epoch after the `current` epoch.
+///
+/// Panics if the `interval` is `0`.
+pub fn next_beacon_epoch(current: Epoch, interval: u64) -> Epoch {
+    assert_ne!(interval, 0, "The beacon epoch interval must be non-zero");
+    Epoch((current.0 / interval + 1) * interval)
+}
+
+/// Take a snapshot of the total stake, the number of validators and their
+/// average commission rate if the `current_epoch` is a beacon epoch. This
+/// must be called at the start of a new epoch.
+pub fn write_beacon_snapshot<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !is_beacon_epoch(current_epoch, params.beacon_epoch_interval) {
+        return Ok(());
+    }
+    let total_stake = read_total_stake(storage, params, current_epoch)?;
+    let validators = read_all_validator_addresses(storage, current_epoch)?;
+    let mut total_commission_rate = Decimal::ZERO;
+    for validator in &validators {
+        let rate = validator_commission_rate_handle(validator)
+            .get(storage, current_epoch, params)?
+            .unwrap_or_default();
+        total_commission_rate += rate;
+    }
+    let num_validators = validators.len() as u64;
+    let avg_commission_rate = if num_validators == 0 {
+        Decimal::ZERO
+    } else {
+        total_commission_rate / Decimal::from(num_validators)
+    };
+    let snapshot = BeaconSnapshot {
+        total_stake,
+        num_validators,
+        avg_commission_rate,
+    };
+    beacon_snapshots_handle().insert(storage, current_epoch, snapshot)?;
+    Ok(())
+}
+
+/// Read the snapshot of the PoS system taken at the start of the given beacon
+/// epoch, if any.
+pub fn read_beacon_snapshot<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> storage_api::Result<Option<BeaconSnapshot>>
+where
+    S: StorageRead,
+{
+    beacon_snapshots_handle().get(storage, &epoch)
+}
+
+/// Compute the total amount of unbonded tokens that become withdrawable in the
+/// given epoch, from all the sources and validators. The amounts are summed
+/// before any slashes are applied. This iterates all the unbonds, so use
+/// [`read_total_unbonding_at_epoch`] to read the cached value instead where
+/// possible.
+pub fn compute_total_unbonding_at_epoch<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> storage_api::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let mut total = token::Amount::default();
+    for res in storage_api::iter_prefix_bytes(storage, &unbonds_prefix())? {
+        let (key, val_bytes) = res?;
+        if let Some((_bond_id, _start, withdraw)) = is_unbond_key(&key) {
+            if withdraw == epoch {
+                total += token::Amount::try_from_slice(&val_bytes)
+                    .into_storage_result()?;
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Cache the total amount of unbonded tokens that become withdrawable in the
+/// last epoch that new unbonds can no longer target, i.e. the epoch before
+/// `current_epoch + pipeline_len + unbonding_len`. Until this epoch is reached
+/// no unbonds can be withdrawn, so the cached amount is final. This must be
+/// called at the start of a new epoch.
+pub fn update_total_unbonding<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let epoch = current_epoch
+        .saturating_add(params.pipeline_len)
+        .saturating_add(params.unbonding_len)
+        .saturating_sub(1);
+    let total = compute_total_unbonding_at_epoch(storage, epoch)?;
+    total_unbonding_handle().insert(storage, epoch, total)?;
+    Ok(())
+}
+
+/// Read the cached total amount of unbonded tokens that become withdrawable in
+/// the given epoch, if it has been computed.
+pub fn read_total_unbonding_at_epoch<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> storage_api::Result<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    total_unbonding_handle().get(storage, &epoch)
+}
+
+/// Self-bond tokens to a validator when `source` is `None` or equal to
+/// the `validator` address, or delegate tokens from the `source` to the
+/// `validator`.
+pub fn bond_tokens<S>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    apply_bond(storage, source, validator, amount, current_epoch)?;
+
+    // Transfer the bonded tokens from the source to PoS
+    let source = source.unwrap_or(validator);
+    let staking_token = staking_token_address(storage);
+    transfer_tokens(storage, &staking_token, amount, source, &ADDRESS)?;
+
+    Ok(())
+}
+
+/// Update the bond, the validator set and the deltas for a bond of the given
+/// amount. This doesn't transfer the bonded tokens to the PoS account.
+fn apply_bond<S>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let amount = amount.change();
+    tracing::debug!("Bonding token amount {amount} at epoch {current_epoch}");
+    let params = read_pos_params(storage)?;
+    let pipeline_epoch = current_epoch.saturating_add(params.pipeline_len);
+    if let Some(source) = source {
+        if source != validator
+            && is_validator(storage, source, &params, pipeline_epoch)?
+        {
+            return Err(
+                BondError::SourceMustNotBeAValidator(source.clone()).into()
+            );
+        }
+    }
+    let state = validator_state_handle(validator).get(
+        storage,
+        pipeline_epoch,
+        &params,
+    )?;
+    if state.is_none() {
+        return Err(BondError::NotAValidator(validator.clone()).into());
+    }
+
+    let validator_state_handle = validator_state_handle(validator);
+    let source = source.unwrap_or(validator);
+    let bond_handle = bond_handle(source, validator);
 
     // Check that validator is not inactive at anywhere between the current
     // epoch and pipeline offset
@@ -828,21 +1494,45 @@ where
             return Err(BondError::InactiveValidator(validator.clone()).into());
         }
     }
+    if tombstoned_validators_handle().contains(storage, validator)? {
+        return Err(BondError::TombstonedValidator(validator.clone()).into());
+    }
 
     // Initialize or update the bond at the pipeline offset
     let offset = params.pipeline_len;
     let cur_remain = bond_handle
-        .get_delta_val(storage, current_epoch + offset, &params)?
+        .get_delta_val(storage, current_epoch.saturating_add(offset), &params)?
         .unwrap_or_default();
     tracing::debug!(
         "Bond remain at offset epoch {}: {}",
-        current_epoch + offset,
+        current_epoch.saturating_add(offset),
         cur_remain
     );
     bond_handle.set(storage, cur_remain + amount, current_epoch, offset)?;
+    storage.write(&delegator_validator_index_key(source, validator), ())?;
 
     // Update the validator set
-    update_validator_set(storage, &params, validator, amount, current_epoch)?;
+    update_validator_set_for_stake_change(
+        storage,
+        &params,
+        validator,
+        amount,
+        current_epoch,
+    )?;
+
+    // The validator set update must never grow the consensus set beyond the
+    // maximum number of validator slots
+    let num_consensus_validators = consensus_validator_set_handle()
+        .at(&(current_epoch.saturating_add(offset)))
+        .iter(storage)?
+        .count() as u64;
+    if num_consensus_validators > params.max_validator_slots {
+        return Err(BondError::ConsensusSetOverflow(
+            num_consensus_validators,
+            params.max_validator_slots,
+        )
+        .into());
+    }
 
     // Update the validator and total deltas
     update_validator_deltas(
@@ -855,16 +1545,163 @@ where
 
     update_total_deltas(storage, &params, amount, current_epoch)?;
 
-    // Transfer the bonded tokens from the source to PoS
+    Ok(())
+}
+
+/// Submit a multisig bond for approval by its sources. The bonded tokens are
+/// transferred from the owner of the bond (the first source) to the PoS
+/// account until the bond is either committed or it expires. The submission
+/// counts as the owner's approval, so a bond with a threshold of 1 is
+/// committed right away. Returns the ID of the bond.
+pub fn submit_multisig_bond<S>(
+    storage: &mut S,
+    bond: MultisigBond,
+    current_epoch: Epoch,
+) -> storage_api::Result<u64>
+where
+    S: StorageRead + StorageWrite,
+{
+    let num_sources = bond.sources.len();
+    let unique_sources: HashSet<&Address> = bond.sources.iter().collect();
+    if unique_sources.len() != num_sources {
+        return Err(MultisigBondError::DuplicateSources.into());
+    }
+    if bond.threshold == 0 || bond.threshold as usize > num_sources {
+        return Err(MultisigBondError::InvalidThreshold(
+            bond.threshold,
+            num_sources,
+        )
+        .into());
+    }
+    let params = read_pos_params(storage)?;
+    let pipeline_epoch = current_epoch.saturating_add(params.pipeline_len);
+    if !is_validator(storage, &bond.validator, &params, pipeline_epoch)? {
+        return Err(BondError::NotAValidator(bond.validator).into());
+    }
+
+    let counter_key = storage::multisig_bond_counter_key();
+    let id: u64 = storage.read_or_default(&counter_key)?;
+    storage.write(&counter_key, id + 1)?;
+
     let staking_token = staking_token_address(storage);
     transfer_tokens(
         storage,
         &staking_token,
-        token::Amount::from_change(amount),
-        source,
+        bond.amount,
+        bond.owner(),
         &ADDRESS,
     )?;
 
+    if bond.threshold == 1 {
+        commit_multisig_bond(storage, bond, current_epoch)?;
+        return Ok(id);
+    }
+    let pending = PendingMultisigBond {
+        approvals: BTreeSet::from([bond.owner().clone()]),
+        bond,
+        submission_epoch: current_epoch,
+    };
+    pending_multisig_bonds_handle().insert(storage, id, pending)?;
+    Ok(id)
+}
+
+/// Approve a pending multisig bond by one of its sources. Once the bond has
+/// been approved by enough of its sources, it gets committed and removed from
+/// the pending bonds. Returns `true` if the bond has been committed.
+pub fn approve_multisig_bond<S>(
+    storage: &mut S,
+    id: u64,
+    approver: &Address,
+    current_epoch: Epoch,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    let params = read_pos_params(storage)?;
+    let handle = pending_multisig_bonds_handle();
+    let mut pending = handle
+        .get(storage, &id)?
+        .ok_or(MultisigBondError::NotFound(id))?;
+    let expiry_epoch = pending
+        .submission_epoch
+        .saturating_add(params.multisig_timeout_epochs);
+    if current_epoch > expiry_epoch {
+        return Err(MultisigBondError::Expired(id, expiry_epoch).into());
+    }
+    if !pending.bond.sources.contains(approver) {
+        return Err(MultisigBondError::NotASource(id, approver.clone()).into());
+    }
+    if !pending.approvals.insert(approver.clone()) {
+        return Err(
+            MultisigBondError::AlreadyApproved(id, approver.clone()).into()
+        );
+    }
+
+    if pending.approvals.len() < pending.bond.threshold as usize {
+        handle.insert(storage, id, pending)?;
+        return Ok(false);
+    }
+
+    handle.remove(storage, &id)?;
+    commit_multisig_bond(storage, pending.bond, current_epoch)?;
+    Ok(true)
+}
+
+/// Apply an approved multisig bond on behalf of its owner. The tokens are
+/// already held by the PoS account.
+fn commit_multisig_bond<S>(
+    storage: &mut S,
+    bond: MultisigBond,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    apply_bond(
+        storage,
+        Some(bond.owner()),
+        &bond.validator,
+        bond.amount,
+        current_epoch,
+    )
+}
+
+/// Remove the pending multisig bonds that have not been approved by enough of
+/// their sources within `multisig_timeout_epochs` and return their tokens to
+/// their owners.
+pub fn remove_expired_multisig_bonds<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let params = read_pos_params(storage)?;
+    let handle = pending_multisig_bonds_handle();
+    let expired: Vec<(u64, PendingMultisigBond)> = handle
+        .iter(storage)?
+        .filter(|res| match res {
+            Ok((_id, pending)) => {
+                pending
+                    .submission_epoch
+                    .saturating_add(params.multisig_timeout_epochs)
+                    < current_epoch
+            }
+            Err(_) => true,
+        })
+        .collect::<storage_api::Result<_>>()?;
+    let staking_token = staking_token_address(storage);
+    for (id, pending) in expired {
+        tracing::debug!("Removing expired multisig bond {id}");
+        handle.remove(storage, &id)?;
+        transfer_tokens(
+            storage,
+            &staking_token,
+            pending.bond.amount,
+            &ADDRESS,
+            pending.bond.owner(),
+        )?;
+    }
     Ok(())
 }
 
@@ -881,7 +1718,7 @@ fn insert_validator_into_validator_set<S>(
 where
     S: StorageRead + StorageWrite,
 {
-    let target_epoch = current_epoch + offset;
+    let target_epoch = current_epoch.saturating_add(offset);
     let consensus_set = &consensus_validator_set_handle().at(&target_epoch);
     let below_cap_set =
         &below_capacity_validator_set_handle().at(&target_epoch);
@@ -966,9 +1803,132 @@ where
     Ok(())
 }
 
+/// Update the validator sets at the pipeline offset for a change of a
+/// validator's state that doesn't change its stake.
+///
+/// A validator that becomes `Inactive` or `Tombstoned` is removed from its
+/// validator set, together with its validator set position, without changing
+/// the positions of the other validators and the max below-capacity
+/// validator, if any, is promoted into a freed consensus slot. A validator that
+/// becomes active again is inserted into a validator set according to its
+/// stake, which also determines whether it ends up in the `Consensus` or
+/// `BelowCapacity` state.
+fn update_validator_set_for_state_change<S>(
+    storage: &mut S,
+    params: &PosParams,
+    validator: &Address,
+    new_state: ValidatorState,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let epoch = current_epoch.saturating_add(params.pipeline_len);
+    let prev_state =
+        validator_state_handle(validator).get(storage, epoch, params)?;
+    let was_active = matches!(
+        prev_state,
+        Some(ValidatorState::Consensus | ValidatorState::BelowCapacity)
+    );
+    match new_state {
+        ValidatorState::Consensus | ValidatorState::BelowCapacity => {
+            if !was_active {
+                let stake =
+                    read_validator_stake(storage, params, validator, epoch)?
+                        .unwrap_or_default();
+                insert_validator_into_validator_set(
+                    storage,
+                    params,
+                    validator,
+                    stake,
+                    current_epoch,
+                    params.pipeline_len,
+                )?;
+            }
+            return Ok(());
+        }
+        ValidatorState::Inactive | ValidatorState::Tombstoned => {}
+    }
+
+    if was_active {
+        let consensus_val_handle = consensus_validator_set_handle().at(&epoch);
+        let below_capacity_val_handle =
+            below_capacity_validator_set_handle().at(&epoch);
+
+        let stake = read_validator_stake(storage, params, validator, epoch)?
+            .unwrap_or_default();
+        let position =
+            read_validator_set_position(storage, validator, epoch, params)?
+                .ok_or_err_msg(
+                    "Validator must have a stored validator set position",
+                )?;
+
+        let consensus_vals = consensus_val_handle.at(&stake);
+        if consensus_vals.get(storage, &position)?.as_ref() == Some(validator) {
+            consensus_vals.remove(storage, &position)?;
+
+            // Promote the max below-capacity validator, if any, into the freed
+            // consensus slot
+            let max_below_capacity_validator_amount =
+                get_max_below_capacity_validator_amount(
+                    &below_capacity_val_handle,
+                    storage,
+                )?;
+            let below_capacity_vals_max = below_capacity_val_handle
+                .at(&max_below_capacity_validator_amount.into());
+            match find_first_position(&below_capacity_vals_max, storage)? {
+                Some(lowest_position) => {
+                    let promoted = below_capacity_vals_max
+                        .remove(storage, &lowest_position)?
+                        .expect("Must have been removed");
+                    insert_validator_into_set(
+                        &consensus_val_handle
+                            .at(&max_below_capacity_validator_amount),
+                        storage,
+                        &epoch,
+                        &promoted,
+                    )?;
+                    validator_state_handle(&promoted).set(
+                        storage,
+                        ValidatorState::Consensus,
+                        current_epoch,
+                        params.pipeline_len,
+                    )?;
+                }
+                None => {
+                    let num_consensus_validators =
+                        read_num_consensus_validators(storage)?;
+                    write_num_consensus_validators(
+                        storage,
+                        num_consensus_validators - 1,
+                    )?;
+                }
+            }
+        } else {
+            let removed = below_capacity_val_handle
+                .at(&stake.into())
+                .remove(storage, &position)?;
+            debug_assert_eq!(removed.as_ref(), Some(validator));
+        }
+
+        // The validator is no longer in any validator set, so its position
+        // must not be kept either
+        validator_set_positions_handle()
+            .at(&epoch)
+            .remove(storage, validator)?;
+    }
+
+    validator_state_handle(validator).set(
+        storage,
+        new_state,
+        current_epoch,
+        params.pipeline_len,
+    )
+}
+
 /// Update validator set when a validator receives a new bond and when
 /// its bond is unbonded (self-bond or delegation).
-fn update_validator_set<S>(
+fn update_validator_set_for_stake_change<S>(
     storage: &mut S,
     params: &PosParams,
     validator: &Address,
@@ -981,7 +1941,11 @@ where
     if token_change == 0_i128 {
         return Ok(());
     }
-    let epoch = current_epoch + params.pipeline_len;
+    // Tombstoned validators are not in any validator set
+    if tombstoned_validators_handle().contains(storage, validator)? {
+        return Ok(());
+    }
+    let epoch = current_epoch.saturating_add(params.pipeline_len);
     tracing::debug!(
         "Update epoch for validator set: {epoch}, validator: {validator}"
     );
@@ -1102,6 +2066,16 @@ where
             // Place the validator into the consensus set and demote the last
             // position min consensus validator to the below-capacity set
 
+            // The cached min amount must still be valid, as the consensus set
+            // has not been modified since it was read
+            debug_assert_eq!(
+                min_consensus_validator_amount,
+                get_min_consensus_validator_amount(
+                    &consensus_val_handle,
+                    storage
+                )?
+            );
+
             // Remove the min consensus validator first
             let consensus_vals_min =
                 consensus_val_handle.at(&min_consensus_validator_amount);
@@ -1162,7 +2136,8 @@ where
     Ok(())
 }
 
-/// Validator sets and positions copying into a future epoch
+/// Validator sets and positions copying into a future epoch. Returns an error
+/// if the `target_epoch` is `0`, as there is no previous epoch to copy from.
 pub fn copy_validator_sets_and_positions<S>(
     storage: &mut S,
     current_epoch: Epoch,
@@ -1173,7 +2148,12 @@ pub fn copy_validator_sets_and_positions<S>(
 where
     S: StorageRead + StorageWrite,
 {
-    let prev_epoch = target_epoch - 1;
+    let prev_epoch = target_epoch.checked_sub_epochs(1).ok_or_else(|| {
+        storage_api::Error::new_const(
+            "Cannot copy the validator sets into epoch 0, it has no previous \
+             epoch to copy from",
+        )
+    })?;
 
     let (consensus, below_capacity) = (
         consensus_validator_set.at(&prev_epoch),
@@ -1337,6 +2317,29 @@ where
         .unwrap_or_default())
 }
 
+/// Find the maximum (highest) stake of the validators in the given consensus
+/// validator set. The set is ordered by ascending stake, so this is the stake
+/// of its last entry. Returns zero if the set is empty.
+pub fn get_max_consensus_validator_amount<S>(
+    handle: &ConsensusValidatorSet,
+    storage: &S,
+) -> storage_api::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    Ok(handle
+        .iter(storage)?
+        .last()
+        .transpose()?
+        .map(|(subkey, _address)| match subkey {
+            NestedSubKey::Data {
+                key,
+                nested_sub_key: _,
+            } => key,
+        })
+        .unwrap_or_default())
+}
+
 fn get_max_below_capacity_validator_amount<S>(
     handle: &BelowCapacityValidatorSet,
     storage: &S,
@@ -1397,7 +2400,7 @@ where
     let amount = amount.change();
     tracing::debug!("Unbonding token amount {amount} at epoch {current_epoch}");
     let params = read_pos_params(storage)?;
-    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let pipeline_epoch = current_epoch.saturating_add(params.pipeline_len);
     tracing::debug!(
         "Current validator stake at pipeline: {}",
         read_validator_stake(storage, &params, validator, pipeline_epoch)?
@@ -1433,7 +2436,7 @@ where
     let bond_remain_handle = bond_handle(source, validator);
 
     // Make sure there are enough tokens left in the bond at the pipeline offset
-    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let pipeline_epoch = current_epoch.saturating_add(params.pipeline_len);
     let remaining_at_pipeline = bond_remain_handle
         .get_sum(storage, pipeline_epoch, &params)?
         .unwrap_or_default();
@@ -1453,8 +2456,9 @@ where
     // TODO: do we want to apply slashing here? (It is done here previously)
 
     let unbond_handle = unbond_handle(source, validator);
-    let withdrawable_epoch =
-        current_epoch + params.pipeline_len + params.unbonding_len;
+    let withdrawable_epoch = current_epoch
+        .saturating_add(params.pipeline_len)
+        .saturating_add(params.unbonding_len);
     let mut to_decrement = token::Amount::from_change(amount);
 
     // We read all matched bonds into memory to do reverse iteration
@@ -1516,6 +2520,18 @@ where
             unbond_amnt,
         )?;
     }
+    // The bond still counts until the pipeline epoch, so its index entry is
+    // only removed at the start of that epoch
+    if amount == remaining_at_pipeline {
+        let removals = delegator_validator_index_removals_handle();
+        let mut bond_ids =
+            removals.get(storage, &pipeline_epoch)?.unwrap_or_default();
+        bond_ids.push(BondId {
+            source: source.clone(),
+            validator: validator.clone(),
+        });
+        removals.insert(storage, pipeline_epoch, bond_ids)?;
+    }
 
     // tracing::debug!("Bonds after decrementing:");
     // for ep in Epoch::default().iter_range(params.unbonding_len * 3) {
@@ -1530,7 +2546,13 @@ where
 
     tracing::debug!("Updating validator set for unbonding");
     // Update the validator set at the pipeline offset
-    update_validator_set(storage, &params, validator, -amount, current_epoch)?;
+    update_validator_set_for_stake_change(
+        storage,
+        &params,
+        validator,
+        -amount,
+        current_epoch,
+    )?;
 
     // Update the validator and total deltas at the pipeline offset
     update_validator_deltas(
@@ -1557,8 +2579,7 @@ where
 {
     let current = handle
         .at(withdraw_epoch)
-        .get(storage, start_epoch)?
-        .unwrap_or_default();
+        .get_or_default(storage, start_epoch)?;
     handle.at(withdraw_epoch).insert(
         storage,
         *start_epoch,
@@ -1580,6 +2601,12 @@ pub fn become_validator<S>(
 where
     S: StorageRead + StorageWrite,
 {
+    if tombstoned_validators_handle().contains(storage, address)? {
+        return Err(
+            BecomeValidatorError::TombstonedValidator(address.clone()).into()
+        );
+    }
+
     // This will fail if the key is already being used
     try_insert_consensus_key(storage, consensus_key)?;
 
@@ -1624,7 +2651,36 @@ where
     Ok(())
 }
 
-/// Withdraw.
+/// Tombstone a validator to permanently remove it from the validator sets.
+/// The validator is removed from its validator set at the pipeline offset and
+/// its state is [`ValidatorState::Tombstoned`] from then on. A tombstoned
+/// validator cannot receive new bonds and cannot become a validator again.
+pub fn tombstone_validator<S>(
+    storage: &mut S,
+    params: &PosParams,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let tombstoned_validators = tombstoned_validators_handle();
+    if tombstoned_validators.contains(storage, validator)? {
+        return Ok(());
+    }
+
+    update_validator_set_for_state_change(
+        storage,
+        params,
+        validator,
+        ValidatorState::Tombstoned,
+        current_epoch,
+    )?;
+    tombstoned_validators.insert(storage, validator.clone())?;
+    Ok(())
+}
+
+/// Withdraw.
 pub fn withdraw_tokens<S>(
     storage: &mut S,
     source: Option<&Address>,
@@ -1734,18 +2790,40 @@ where
         .into());
     }
 
-    let max_change =
-        read_validator_max_commission_rate_change(storage, validator)?;
-    if max_change.is_none() {
-        return Err(CommissionRateChangeError::NoMaxSetInStorage(
-            validator.clone(),
-        )
-        .into());
-    }
+    let max_change = read_validator_max_commission_rate_change_must_exist(
+        storage, validator,
+    )?;
 
     let params = read_pos_params(storage)?;
+    let needs_notice = params.commission_change_notice_epochs > 0;
+    if needs_notice {
+        let announcement =
+            read_validator_commission_rate_announcement(storage, validator)?
+                .ok_or_else(|| {
+                    CommissionRateChangeError::NotAnnounced(validator.clone())
+                })?;
+        if announcement.new_rate != new_rate {
+            return Err(CommissionRateChangeError::NotAsAnnounced(
+                new_rate,
+                announcement.new_rate,
+                validator.clone(),
+            )
+            .into());
+        }
+        let allowed_epoch = announcement
+            .announced_epoch
+            .saturating_add(params.commission_change_notice_epochs);
+        if current_epoch < allowed_epoch {
+            return Err(CommissionRateChangeError::NoticePeriodNotElapsed(
+                validator.clone(),
+                allowed_epoch,
+            )
+            .into());
+        }
+    }
+
     let commission_handle = validator_commission_rate_handle(validator);
-    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let pipeline_epoch = current_epoch.saturating_add(params.pipeline_len);
 
     let rate_at_pipeline = commission_handle
         .get(storage, pipeline_epoch, &params)?
@@ -1754,10 +2832,10 @@ where
         return Ok(());
     }
     let rate_before_pipeline = commission_handle
-        .get(storage, pipeline_epoch - 1, &params)?
+        .get(storage, pipeline_epoch.saturating_sub(1), &params)?
         .expect("Could not find a rate in given epoch");
     let change_from_prev = new_rate - rate_before_pipeline;
-    if change_from_prev.abs() > max_change.unwrap() {
+    if change_from_prev.abs() > max_change {
         return Err(CommissionRateChangeError::RateChangeTooLarge(
             change_from_prev,
             validator.clone(),
@@ -1765,10 +2843,23 @@ where
         .into());
     }
 
-    commission_handle.set(storage, new_rate, current_epoch, params.pipeline_len)
+    commission_handle.set(
+        storage,
+        new_rate,
+        current_epoch,
+        params.pipeline_len,
+    )?;
+    if needs_notice {
+        // The announcement has been used up
+        storage
+            .delete(&validator_commission_rate_announcement_key(validator))?;
+    }
+    Ok(())
 }
 
-/// apply a slash and write it to storage
+/// apply a slash and write it to storage. Only a validator that was in the
+/// consensus set in the `evidence_epoch` can be slashed, because only these
+/// validators participate in consensus.
 pub fn slash<S>(
     storage: &mut S,
     params: &PosParams,
@@ -1781,6 +2872,17 @@ pub fn slash<S>(
 where
     S: StorageRead + StorageWrite,
 {
+    let state_at_infraction = validator_state_handle(validator)
+        .get(storage, evidence_epoch, params)?
+        .ok_or_err_msg("Validator must have a stored state")?;
+    if state_at_infraction != ValidatorState::Consensus {
+        return Err(SlashError::ValidatorNotSlashable(
+            validator.clone(),
+            state_at_infraction,
+        )
+        .into());
+    }
+
     let rate = slash_type.get_slash_rate(params);
     let slash = Slash {
         epoch: evidence_epoch,
@@ -1795,7 +2897,7 @@ where
     let token_change = -token::Change::from(slashed_amount);
 
     // Update validator sets and deltas at the pipeline length
-    update_validator_set(
+    update_validator_set_for_stake_change(
         storage,
         params,
         validator,
@@ -1811,8 +2913,19 @@ where
     )?;
     update_total_deltas(storage, params, token_change, current_epoch)?;
 
-    // Write the validator slash to storage
-    validator_slashes_handle(validator).push(storage, slash)?;
+    // Write the validator slash to storage, together with the stake at the
+    // infraction epoch, so that it doesn't have to be read again when the
+    // slash is processed
+    let slashes = validator_slashes_handle(validator);
+    let stake_at_infraction =
+        read_validator_stake(storage, params, validator, evidence_epoch)?
+            .unwrap_or_default();
+    validator_slashes_stake_handle(validator).insert(
+        storage,
+        slashes.len(storage)?,
+        stake_at_infraction,
+    )?;
+    slashes.push(storage, slash)?;
 
     // Transfer the slashed tokens from PoS account to Slash Fund address
     let staking_token = staking_token_address(storage);
@@ -1821,12 +2934,141 @@ where
         &staking_token,
         token::Amount::from(slashed_amount),
         &ADDRESS,
-        &SLASH_POOL_ADDRESS,
+        &slash_pool_address(),
+    )?;
+
+    Ok(())
+}
+
+/// Burn the given `amount` of tokens from the PoS slash pool. The burned
+/// tokens are transferred to the PoS burn address, from which they can never
+/// be spent, and added to the total burned amount.
+///
+/// This must only be executed from an accepted governance proposal, which is
+/// enforced by the slash pool VP.
+pub fn burn_slash_pool<S>(
+    storage: &mut S,
+    amount: token::Amount,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let staking_token = staking_token_address(storage);
+    let pool_balance = storage_api::token::read_balance(
+        storage,
+        &staking_token,
+        &slash_pool_address(),
+    )?;
+    if amount > pool_balance {
+        return Err(storage_api::Error::new_const(
+            "The slash pool doesn't have sufficient balance",
+        ));
+    }
+    storage_api::token::transfer(
+        storage,
+        &staking_token,
+        &slash_pool_address(),
+        &burn_address(),
+        amount,
     )?;
 
+    let total_burned = read_total_burned(storage)?;
+    let new_total_burned =
+        total_burned.checked_add(amount).ok_or_else(|| {
+            storage_api::Error::new_const("PoS total burned amount overflow")
+        })?;
+    storage.write(&total_burned_key(), new_total_burned)?;
+    tracing::info!(
+        "Burned {} tokens from the PoS slash pool, total burned {}",
+        amount,
+        new_total_burned
+    );
     Ok(())
 }
 
+/// Read the total amount of tokens burned from the PoS slash pool.
+pub fn read_total_burned<S>(storage: &S) -> storage_api::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    storage.read_or_default(&total_burned_key())
+}
+
+/// Redistribute the tokens held in the PoS slash pool to the given
+/// destination. When no `amount` is given, the whole balance of the slash pool
+/// is redistributed.
+///
+/// This must only be executed from an accepted governance proposal, which is
+/// enforced by the slash pool VP.
+pub fn redistribute_slash_pool<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+    destination: SlashPoolDestination,
+    amount: Option<token::Amount>,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let staking_token = staking_token_address(storage);
+    let pool_balance = storage_api::token::read_balance(
+        storage,
+        &staking_token,
+        &slash_pool_address(),
+    )?;
+    let amount = amount.unwrap_or(pool_balance);
+    if amount > pool_balance {
+        return Err(storage_api::Error::new_const(
+            "The slash pool doesn't have sufficient balance",
+        ));
+    }
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    match destination {
+        SlashPoolDestination::Burn => burn_slash_pool(storage, amount),
+        SlashPoolDestination::Treasury(treasury) => {
+            storage_api::token::transfer(
+                storage,
+                &staking_token,
+                &slash_pool_address(),
+                &treasury,
+                amount,
+            )
+        }
+        SlashPoolDestination::ValidatorSet => {
+            let validators = read_consensus_validator_set_addresses_with_stake(
+                storage,
+                current_epoch,
+            )?;
+            let total_stake: u64 = validators
+                .iter()
+                .map(|validator| u64::from(validator.bonded_stake))
+                .sum();
+            if total_stake == 0 {
+                return Ok(());
+            }
+            // Any remainder from rounding stays in the slash pool
+            for validator in validators {
+                let share = Decimal::from(u64::from(validator.bonded_stake))
+                    / Decimal::from(total_stake);
+                let reward = decimal_mult_u64(share, u64::from(amount));
+                storage_api::token::transfer(
+                    storage,
+                    &staking_token,
+                    &slash_pool_address(),
+                    &validator.address,
+                    token::Amount::from(reward),
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
 /// Transfer tokens between accounts
 /// TODO: may want to move this into core crate
 pub fn transfer_tokens<S>(
@@ -1853,9 +3095,8 @@ where
             );
         }
         src_balance.spend(&amount);
-        let mut dest_balance = storage
-            .read::<token::Amount>(&dest_key)?
-            .unwrap_or_default();
+        let mut dest_balance: token::Amount =
+            storage.read_or_default(&dest_key)?;
 
         // let dest_balance = storage.read_bytes(&dest_key).unwrap_or_default();
         // let mut dest_balance: token::Amount = dest_balance
@@ -1902,6 +3143,76 @@ where
     handle.contains(storage, consensus_key)
 }
 
+/// Find the epochs in the inclusive range from `search_start` to `search_end`
+/// in which the given consensus key was the validator's active consensus key.
+pub fn find_epochs_for_consensus_key<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    consensus_key: &common::PublicKey,
+    search_start: Epoch,
+    search_end: Epoch,
+) -> storage_api::Result<Vec<Epoch>>
+where
+    S: StorageRead,
+{
+    let handle = validator_consensus_key_handle(validator);
+    let len = match search_end.checked_sub(search_start) {
+        Some(diff) => diff.0 + 1,
+        None => return Ok(vec![]),
+    };
+    let mut epochs = vec![];
+    for epoch in search_start.iter_range(len) {
+        if handle.get(storage, epoch, params)?.as_ref() == Some(consensus_key) {
+            epochs.push(epoch);
+        }
+    }
+    Ok(epochs)
+}
+
+/// Read the validator's consensus key that is active at the given epoch, if
+/// any.
+pub fn read_validator_consensus_key<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> storage_api::Result<Option<common::PublicKey>>
+where
+    S: StorageRead,
+{
+    validator_consensus_key_handle(validator).get(storage, epoch, params)
+}
+
+/// Find all the distinct consensus keys that were active for the validator in
+/// the inclusive range from `search_start` to `search_end`, in the order in
+/// which they became active.
+pub fn find_validator_consensus_keys<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    search_start: Epoch,
+    search_end: Epoch,
+) -> storage_api::Result<Vec<common::PublicKey>>
+where
+    S: StorageRead,
+{
+    let handle = validator_consensus_key_handle(validator);
+    let len = match search_end.checked_sub(search_start) {
+        Some(diff) => diff.0 + 1,
+        None => return Ok(vec![]),
+    };
+    let mut keys: Vec<common::PublicKey> = vec![];
+    for epoch in search_start.iter_range(len) {
+        if let Some(key) = handle.get(storage, epoch, params)? {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    Ok(keys)
+}
+
 /// Get the total bond amount for a given bond ID at a given epoch
 pub fn bond_amount<S>(
     storage: &S,
@@ -1944,6 +3255,177 @@ where
     Ok((total, total_active))
 }
 
+/// Compound the delegator rewards products of the given validator over the
+/// inclusive range from `start_epoch` to `end_epoch`. The product written for
+/// an epoch is the growth factor of the delegations in that epoch, so the
+/// result is the total growth factor of a delegation held throughout the
+/// range. Epochs without a rewards product don't change the result.
+pub fn compute_compounded_rewards_product<S>(
+    storage: &S,
+    validator: &Address,
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+) -> storage_api::Result<Decimal>
+where
+    S: StorageRead,
+{
+    let len = match end_epoch.checked_sub(start_epoch) {
+        Some(diff) => diff.0 + 1,
+        None => return Ok(Decimal::ONE),
+    };
+    let rewards_products = delegator_rewards_products_handle(validator);
+    let mut compounded = Decimal::ONE;
+    for epoch in start_epoch.iter_range(len) {
+        if let Some(product) = rewards_products.get(storage, &epoch)? {
+            compounded *= product;
+        }
+    }
+    Ok(compounded)
+}
+
+/// Estimate the annual staking yield of the bond from `source` to `validator`
+/// from the rewards it received in the `window_epochs` epochs preceding the
+/// `observation_epoch`. The rewards earned in the window are divided by the
+/// average bonded principal and the result is scaled by the number of epochs
+/// per year. The commission is already deducted from the delegation rewards
+/// products, so it doesn't need to be applied here.
+pub fn compute_annual_staking_yield<S>(
+    storage: &S,
+    params: &PosParams,
+    source: &Address,
+    validator: &Address,
+    observation_epoch: Epoch,
+    window_epochs: u64,
+) -> storage_api::Result<Decimal>
+where
+    S: StorageRead,
+{
+    let start_epoch = observation_epoch
+        .checked_sub(Epoch(window_epochs))
+        .unwrap_or_default();
+    let len = observation_epoch.0 - start_epoch.0;
+    if len == 0 {
+        return Ok(Decimal::ZERO);
+    }
+    let epochs_per_year: u64 = storage
+        .read(&get_epochs_per_year_key())?
+        .ok_or_err_msg("Epochs per year should always be set")?;
+
+    // Self-bonds accrue the validator's rewards products, while delegations
+    // accrue the products with the commission deducted
+    let rewards_products = if source == validator {
+        validator_rewards_products_handle(validator)
+    } else {
+        delegator_rewards_products_handle(validator)
+    };
+    let bonds = bond_handle(source, validator);
+
+    let mut rewards_earned = Decimal::ZERO;
+    let mut principal_sum = Decimal::ZERO;
+    for epoch in start_epoch.iter_range(len) {
+        let bonded = bonds
+            .get_sum(storage, epoch, params)?
+            .map(token::Amount::from_change)
+            .map(Decimal::from)
+            .unwrap_or_default();
+        principal_sum += bonded;
+        // The product written for an epoch is the growth factor of the bonds
+        // in that epoch
+        if let Some(product) = rewards_products.get(storage, &epoch)? {
+            rewards_earned += bonded * (product - Decimal::ONE);
+        }
+    }
+
+    let principal_bonded = principal_sum / Decimal::from(len);
+    if principal_bonded.is_zero() {
+        return Ok(Decimal::ZERO);
+    }
+    Ok(
+        rewards_earned / principal_bonded * Decimal::from(epochs_per_year)
+            / Decimal::from(len),
+    )
+}
+
+/// Compute the fraction of a validator's rewards in the given epoch that the
+/// bond from `source` receives, i.e. the bond's fraction of the validator's
+/// stake after the validator's commission is deducted. The validator's
+/// self-bond is not charged any commission. Both the bond and the validator's
+/// stake are taken after slashing.
+pub fn delegator_reward_share<S>(
+    storage: &S,
+    params: &PosParams,
+    source: &Address,
+    validator: &Address,
+    epoch: Epoch,
+) -> storage_api::Result<Decimal>
+where
+    S: StorageRead,
+{
+    let total_stake = read_validator_stake(storage, params, validator, epoch)?
+        .map(Decimal::from)
+        .unwrap_or_default();
+    if total_stake.is_zero() {
+        return Ok(Decimal::ZERO);
+    }
+    let bond_stake =
+        read_slashed_bond_stake(storage, params, source, validator, epoch)?;
+    // Guard against the rounding of the slashed amounts
+    let stake_share = (bond_stake / total_stake).min(Decimal::ONE);
+    if source == validator {
+        return Ok(stake_share);
+    }
+    let commission_rate = validator_commission_rate_handle(validator)
+        .get(storage, epoch, params)?
+        .unwrap_or_default();
+    Ok(stake_share * (Decimal::ONE - commission_rate))
+}
+
+/// Compute the fraction of a validator's rewards in the given epoch that the
+/// validator earns as a commission on the stake delegated to it, i.e. the
+/// sum of the delegations (all the stake apart from the self-bond) times the
+/// commission rate, divided by the validator's stake.
+pub fn validator_commission_earned<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> storage_api::Result<Decimal>
+where
+    S: StorageRead,
+{
+    let total_stake = read_validator_stake(storage, params, validator, epoch)?
+        .map(Decimal::from)
+        .unwrap_or_default();
+    if total_stake.is_zero() {
+        return Ok(Decimal::ZERO);
+    }
+    let self_bond =
+        read_slashed_bond_stake(storage, params, validator, validator, epoch)?
+            .min(total_stake);
+    let commission_rate = validator_commission_rate_handle(validator)
+        .get(storage, epoch, params)?
+        .unwrap_or_default();
+    Ok((total_stake - self_bond) * commission_rate / total_stake)
+}
+
+/// Read the stake of the bond from `source` to `validator` in the given epoch
+/// with the validator's slashes applied.
+fn read_slashed_bond_stake<S>(
+    storage: &S,
+    params: &PosParams,
+    source: &Address,
+    validator: &Address,
+    epoch: Epoch,
+) -> storage_api::Result<Decimal>
+where
+    S: StorageRead,
+{
+    let slashes = find_validator_slashes(storage, validator)?;
+    let stake = bond_handle(source, validator)
+        .get_sum_after_slashing(storage, epoch, params, &slashes)?;
+    Ok(Decimal::from(token::Amount::from_change(stake)))
+}
+
 /// Communicate imminent validator set updates to Tendermint. This function is
 /// called two blocks before the start of a new epoch because Tendermint
 /// validator updates become active two blocks after the updates are submitted.
@@ -1960,6 +3442,20 @@ where
     // give Tendermint updates for the next epoch
     let next_epoch: Epoch = current_epoch.next();
 
+    // The stake of a validator in the current epoch, from its stake in the
+    // next epoch and the change of its stake between the two epochs
+    let prev_validator_stake = |address: &Address, cur_stake: token::Amount| {
+        let power_change = get_validator_power_change(
+            storage,
+            params,
+            address,
+            current_epoch,
+            next_epoch,
+        )
+        .unwrap();
+        token::Amount::from_change(cur_stake.change() - power_change)
+    };
+
     let cur_consensus_validators =
         consensus_validator_set_handle().at(&next_epoch);
     let prev_consensus_validators =
@@ -1980,6 +3476,10 @@ where
                 "Consensus validator address {address}, stake {cur_stake}"
             );
 
+            let cur_tm_voting_power = Lazy::new(|| {
+                into_tm_voting_power(params.tm_votes_per_token, cur_stake)
+            });
+
             // Check if the validator was consensus in the previous epoch with
             // the same stake
             // Look up previous state and prev and current voting powers
@@ -1988,21 +3488,11 @@ where
                     .get(storage, current_epoch, params)
                     .unwrap();
                 let prev_tm_voting_power = Lazy::new(|| {
-                    let prev_validator_stake =
-                        validator_deltas_handle(&address)
-                            .get_sum(storage, current_epoch, params)
-                            .unwrap()
-                            .map(token::Amount::from_change)
-                            .unwrap_or_default();
                     into_tm_voting_power(
                         params.tm_votes_per_token,
-                        prev_validator_stake,
+                        prev_validator_stake(&address, cur_stake),
                     )
                 });
-                let cur_tm_voting_power = Lazy::new(|| {
-                    into_tm_voting_power(params.tm_votes_per_token, cur_stake)
-                });
-
                 // If it was in `Consensus` before and voting power has not
                 // changed, skip the update
                 if matches!(prev_state, Some(ValidatorState::Consensus))
@@ -2024,6 +3514,41 @@ where
                     );
                     return None;
                 }
+
+                // If the voting power dropped to 0, the validator has to be
+                // removed from Tendermint's validator set, even though it
+                // stays in the consensus set. This only applies if Tendermint
+                // knows the validator, i.e. if it was in `Consensus` before.
+                if *cur_tm_voting_power == 0 {
+                    if !matches!(prev_state, Some(ValidatorState::Consensus)) {
+                        tracing::info!(
+                            "skipping validator update, {address} is in \
+                             consensus set but without voting power and \
+                             wasn't previously in consensus set"
+                        );
+                        return None;
+                    }
+                    tracing::info!(
+                        "Deactivating validator {address}, it's in consensus \
+                         set but its voting power dropped to 0"
+                    );
+                    let consensus_key =
+                        validator_consensus_key_handle(&address)
+                            .get(storage, current_epoch, params)
+                            .unwrap()
+                            .unwrap();
+                    return Some(ValidatorSetUpdate::Deactivated(
+                        consensus_key,
+                    ));
+                }
+            } else if *cur_tm_voting_power == 0 {
+                // Without a previous consensus set, Tendermint doesn't know
+                // about a validator without voting power
+                tracing::info!(
+                    "skipping validator update, {address} is in consensus set \
+                     but without voting power"
+                );
+                return None;
             }
             let consensus_key = validator_consensus_key_handle(&address)
                 .get(storage, next_epoch, params)
@@ -2036,6 +3561,7 @@ where
             Some(ValidatorSetUpdate::Consensus(ConsensusValidator {
                 consensus_key,
                 bonded_stake: cur_stake.into(),
+                weight: *cur_tm_voting_power,
             }))
         });
     let cur_below_capacity_validators =
@@ -2060,14 +3586,9 @@ where
                 "Below-capacity validator address {address}, stake {cur_stake}"
             );
 
-            let prev_validator_stake = validator_deltas_handle(&address)
-                .get_sum(storage, current_epoch, params)
-                .unwrap()
-                .map(token::Amount::from_change)
-                .unwrap_or_default();
             let prev_tm_voting_power = into_tm_voting_power(
                 params.tm_votes_per_token,
-                prev_validator_stake,
+                prev_validator_stake(&address, cur_stake),
             );
 
             // If the validator previously had no voting power, it wasn't in
@@ -2108,8 +3629,28 @@ where
             );
             Some(ValidatorSetUpdate::Deactivated(consensus_key))
         });
+    // Validators tombstoned from the consensus set are no longer in any
+    // validator set, so they have to be deactivated explicitly
+    let tombstoned_validators = prev_consensus_validators
+        .iter(storage)?
+        .filter_map(|validator| {
+            let (_sub_key, address) = validator.unwrap();
+            let state = validator_state_handle(&address)
+                .get(storage, next_epoch, params)
+                .unwrap();
+            if !matches!(state, Some(ValidatorState::Tombstoned)) {
+                return None;
+            }
+            tracing::debug!("Deactivating tombstoned validator {address}");
+            let consensus_key = validator_consensus_key_handle(&address)
+                .get(storage, current_epoch, params)
+                .unwrap()
+                .unwrap();
+            Some(ValidatorSetUpdate::Deactivated(consensus_key))
+        });
     Ok(consensus_validators
         .chain(below_capacity_validators)
+        .chain(tombstoned_validators)
         .map(f)
         .collect())
 }
@@ -2140,7 +3681,9 @@ where
 }
 
 /// Find all validators to which a given bond `owner` (or source) has a
-/// delegation with the amount
+/// delegation with the amount. The validators are found from the delegator
+/// validator index, so every bond is only summed up once. Until the bonds
+/// that precede the index are migrated, the bond keys are used instead.
 pub fn find_delegations<S>(
     storage: &S,
     owner: &Address,
@@ -2149,9 +3692,141 @@ pub fn find_delegations<S>(
 where
     S: StorageRead,
 {
-    let bonds_prefix = bonds_for_source_prefix(owner);
     let params = read_pos_params(storage)?;
+    let validators = if storage
+        .has_key(&delegator_validator_index_migrated_key())?
+    {
+        let index_prefix = delegator_validator_index_prefix(owner);
+        let mut validators = HashSet::new();
+        for iter_result in
+            storage_api::iter_prefix_bytes(storage, &index_prefix)?
+        {
+            let (key, _) = iter_result?;
+            let BondId { validator, .. } =
+                is_delegator_validator_index_key(&key).ok_or_else(|| {
+                    storage_api::Error::new_const(
+                        "Delegation index key should contain validator \
+                         address.",
+                    )
+                })?;
+            validators.insert(validator);
+        }
+        validators
+    } else {
+        find_delegation_validators(storage, owner)?
+    };
+
     let mut delegations: HashMap<Address, token::Amount> = HashMap::new();
+    for validator in validators {
+        let amount = bond_handle(owner, &validator)
+            .get_sum(storage, *epoch, &params)?
+            .unwrap_or_default();
+        delegations.insert(validator, token::Amount::from_change(amount));
+    }
+    Ok(delegations)
+}
+
+/// Remove the delegator validator index entries of the bonds whose full
+/// unbond takes effect at or before the current epoch, unless they have been
+/// bonded to again since. This must be called at the start of a new epoch.
+pub fn prune_delegator_validator_index<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let removals = delegator_validator_index_removals_handle();
+    let mut due_epochs = Vec::new();
+    for res in removals.iter(storage)? {
+        let (epoch, _bond_ids) = res?;
+        if epoch <= current_epoch {
+            due_epochs.push(epoch);
+        }
+    }
+    for epoch in due_epochs {
+        let bond_ids = removals.remove(storage, &epoch)?.unwrap_or_default();
+        for BondId { source, validator } in bond_ids {
+            if is_bond_empty(storage, &source, &validator)? {
+                storage.delete(&delegator_validator_index_key(
+                    &source, &validator,
+                ))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Index the non-empty bonds that were created before the delegator validator
+/// index was introduced. This only does anything the first time it's called
+/// on a chain that didn't start with the index.
+pub fn migrate_delegator_validator_index<S>(
+    storage: &mut S,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let migrated_key = delegator_validator_index_migrated_key();
+    if storage.has_key(&migrated_key)? {
+        return Ok(());
+    }
+    let mut bond_ids = BTreeSet::new();
+    for iter_result in storage_api::iter_prefix_bytes(storage, &bonds_prefix())?
+    {
+        let (key, _) = iter_result?;
+        if let Some((bond_id, _epoch)) = is_bond_key(&key) {
+            bond_ids.insert(bond_id);
+        }
+    }
+    for BondId { source, validator } in bond_ids {
+        if !is_bond_empty(storage, &source, &validator)? {
+            storage.write(
+                &delegator_validator_index_key(&source, &validator),
+                (),
+            )?;
+        }
+    }
+    storage.write(&migrated_key, ())
+}
+
+/// Is the bond from the given source to the given validator zero at every
+/// epoch?
+fn is_bond_empty<S>(
+    storage: &S,
+    source: &Address,
+    validator: &Address,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    for res in bond_handle(source, validator)
+        .get_data_handler()
+        .iter(storage)?
+    {
+        let (_epoch, delta) = res?;
+        if delta != token::Change::default() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Find all validators to which a given bond `owner` (or source) has a
+/// delegation with the nominal amount and the effective amount after
+/// deducting the slashes that were applied to the delegations up to the
+/// given epoch.
+pub fn find_delegations_with_slashing<S>(
+    storage: &S,
+    params: &PosParams,
+    owner: &Address,
+    epoch: &Epoch,
+) -> storage_api::Result<HashMap<Address, (token::Amount, token::Amount)>>
+where
+    S: StorageRead,
+{
+    let bonds_prefix = bonds_for_source_prefix(owner);
+    let mut delegations: HashMap<Address, (token::Amount, token::Amount)> =
+        HashMap::new();
 
     for iter_result in storage_api::iter_prefix_bytes(storage, &bonds_prefix)? {
         let (key, _bond_bytes) = iter_result?;
@@ -2161,11 +3836,30 @@ where
                     "Delegation key should contain validator address.",
                 )
             })?;
-        let amount = bond_handle(owner, &validator_address)
-            .get_sum(storage, *epoch, &params)?
-            .unwrap_or_default();
-        delegations
-            .insert(validator_address, token::Amount::from_change(amount));
+        if delegations.contains_key(&validator_address) {
+            continue;
+        }
+        let slashes = find_validator_slashes(storage, &validator_address)?;
+
+        let mut nominal = token::Amount::default();
+        let mut slashed = token::Amount::default();
+        let bonds = find_bonds(storage, owner, &validator_address)?;
+        for (start, change) in bonds {
+            if start > *epoch || change <= token::Change::default() {
+                continue;
+            }
+            nominal += token::Amount::from_change(change);
+            for slash in &slashes {
+                if slash.epoch >= start && slash.epoch <= *epoch {
+                    slashed += mult_change_to_amount(
+                        slash.r#type.get_slash_rate(params),
+                        change,
+                    );
+                }
+            }
+        }
+        let effective = nominal.checked_sub(slashed).unwrap_or_default();
+        delegations.insert(validator_address, (nominal, effective));
     }
     Ok(delegations)
 }
@@ -2196,6 +3890,60 @@ where
         .collect()
 }
 
+/// Find bond deltas for the given source and validator address, skipping the
+/// dust entries whose absolute value is below the given minimum.
+pub fn find_bonds_above<S>(
+    storage: &S,
+    source: &Address,
+    validator: &Address,
+    min_amount: token::Change,
+) -> storage_api::Result<BTreeMap<Epoch, token::Change>>
+where
+    S: StorageRead,
+{
+    let bonds = find_bonds(storage, source, validator)?;
+    Ok(bonds
+        .into_iter()
+        .filter(|(_epoch, delta)| delta.abs() >= min_amount)
+        .collect())
+}
+
+/// Find the bond deltas of all the sources bonded to the given validator whose
+/// start epoch is in the inclusive range from `start_epoch` to `end_epoch`.
+/// The bonds are returned together with their source.
+///
+/// Note that this iterates the bonds of all the validators.
+pub fn find_bonds_in_epoch_range<S>(
+    storage: &S,
+    validator: &Address,
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+) -> storage_api::Result<Vec<(Address, Epoch, token::Change)>>
+where
+    S: StorageRead,
+{
+    let mut bonds = vec![];
+    for iter_result in storage_api::iter_prefix_bytes(storage, &bonds_prefix())?
+    {
+        let (key, bond_bytes) = iter_result?;
+        // Skip the epoched data that are not bond deltas, e.g. last update
+        let (bond_id, bond_epoch) = match is_bond_key(&key) {
+            Some(bond) => bond,
+            None => continue,
+        };
+        if &bond_id.validator != validator
+            || bond_epoch < start_epoch
+            || bond_epoch > end_epoch
+        {
+            continue;
+        }
+        let delta =
+            token::Change::try_from_slice(&bond_bytes).into_storage_result()?;
+        bonds.push((bond_id.source, bond_epoch, delta));
+    }
+    Ok(bonds)
+}
+
 /// Find unbond deltas for the given source and validator address.
 pub fn find_unbonds<S>(
     storage: &S,
@@ -2243,6 +3991,47 @@ where
     }
 }
 
+/// Check that the PoS account holds enough tokens to cover all the bonded
+/// stake and the unbonds that haven't been withdrawn yet. The slashed tokens
+/// are moved to the slash pool and deducted from the bonded stake, so they are
+/// not accounted for here. The PoS account may hold more tokens than that, as
+/// the rewards are minted into it.
+pub fn verify_pos_balance_invariant<S>(
+    storage: &S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead,
+{
+    // All the bonds and unbonds are applied at the pipeline offset at latest
+    let pipeline_epoch = current_epoch.saturating_add(params.pipeline_len);
+    let total_stake = read_total_stake(storage, params, pipeline_epoch)?;
+
+    let mut total_unbonded = token::Amount::default();
+    for result in storage_api::iter_prefix_bytes(storage, &unbonds_prefix())? {
+        let (key, val_bytes) = result?;
+        if is_unbond_key(&key).is_some() {
+            let amount = token::Amount::try_from_slice(&val_bytes)
+                .into_storage_result()?;
+            total_unbonded += amount;
+        }
+    }
+
+    let staking_token = staking_token_address(storage);
+    let pos_balance =
+        storage_api::token::read_balance(storage, &staking_token, &ADDRESS)?;
+    let expected = total_stake + total_unbonded;
+    if pos_balance < expected {
+        return Err(storage_api::Error::new(format!(
+            "The PoS account balance {pos_balance} is lower than the total \
+             bonded stake {total_stake} plus the unbonded tokens \
+             {total_unbonded} in epoch {current_epoch}"
+        )));
+    }
+    Ok(())
+}
+
 /// Find all slashes and the associated validators in the PoS system
 pub fn find_all_slashes<S>(
     storage: &S,
@@ -2416,20 +4205,30 @@ where
         Ok::<_, storage_api::Error>(())
     })?;
 
-    Ok(bonds_and_unbonds
-        .into_iter()
-        .map(|(bond_id, (bonds, unbonds))| {
-            let details = BondsAndUnbondsDetail {
-                bonds,
-                unbonds,
-                slashes: applied_slashes
-                    .get(&bond_id.validator)
-                    .cloned()
-                    .unwrap_or_default(),
-            };
-            (bond_id, details)
-        })
-        .collect())
+    let current_epoch = storage.get_block_epoch()?;
+    let mut states_cache = HashMap::<Address, Option<ValidatorState>>::new();
+    let mut details = BondsAndUnbondsDetails::new();
+    for (bond_id, (bonds, unbonds)) in bonds_and_unbonds {
+        if !states_cache.contains_key(&bond_id.validator) {
+            let state = validator_state_handle(&bond_id.validator).get(
+                storage,
+                current_epoch,
+                params,
+            )?;
+            states_cache.insert(bond_id.validator.clone(), state);
+        }
+        let detail = BondsAndUnbondsDetail {
+            bonds,
+            unbonds,
+            slashes: applied_slashes
+                .get(&bond_id.validator)
+                .cloned()
+                .unwrap_or_default(),
+            validator_state: states_cache[&bond_id.validator],
+        };
+        details.insert(bond_id, detail);
+    }
+    Ok(details)
 }
 
 fn find_bonds_and_unbonds_details<S>(
@@ -2475,13 +4274,20 @@ where
         })
         .collect();
 
+    let current_epoch = storage.get_block_epoch()?;
+    let validator_state = validator_state_handle(&validator).get(
+        storage,
+        current_epoch,
+        params,
+    )?;
     let details = BondsAndUnbondsDetail {
         bonds,
         unbonds,
         slashes: applied_slashes.get(&validator).cloned().unwrap_or_default(),
+        validator_state,
     };
     let bond_id = BondId { source, validator };
-    Ok(HashMap::from_iter([(bond_id, details)]))
+    Ok(BTreeMap::from([(bond_id, details)]))
 }
 
 fn make_bond_details<S>(
@@ -2563,13 +4369,125 @@ fn make_unbond_details<S>(
     }
 }
 
+/// Copy the current values of the rewards accumulator into a snapshot for the
+/// given epoch. This should be called at the end of the epoch, before the
+/// accumulator is reset, so that the fractions of the block rewards earned by
+/// the consensus validators in that epoch can be queried later.
+pub fn save_rewards_accumulator_snapshot<S>(
+    storage: &mut S,
+    epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let accumulated: Vec<(Address, Decimal)> = rewards_accumulator_handle()
+        .iter(storage)?
+        .collect::<storage_api::Result<_>>()?;
+    let snapshot = epoched_rewards_snapshot_handle().at(&epoch);
+    for (address, value) in accumulated {
+        snapshot.insert(storage, address, value)?;
+    }
+    Ok(())
+}
+
+/// Record the votes of the validators whose signature was included in the
+/// block at the given height, so that the signers of a block can be queried
+/// later.
+pub fn record_block_votes<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    block_height: impl Into<u64>,
+    votes: &[VoteInfo],
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    epoch_votes_handle().at(&epoch).insert(
+        storage,
+        block_height.into(),
+        votes.to_vec(),
+    )?;
+    Ok(())
+}
+
+/// Read the votes recorded for the block at the given height in the given
+/// epoch, if any.
+pub fn read_block_votes<S>(
+    storage: &S,
+    epoch: Epoch,
+    block_height: impl Into<u64>,
+) -> storage_api::Result<Option<Vec<VoteInfo>>>
+where
+    S: StorageRead,
+{
+    epoch_votes_handle()
+        .at(&epoch)
+        .get(storage, &block_height.into())
+}
+
+/// Remove the votes recorded in the epochs that are older than the unbonding
+/// length before the current epoch. Evidence of misbehaviour from these
+/// epochs can no longer be processed, so their votes are no longer needed.
+/// This should be called at the start of a new epoch.
+pub fn prune_epoch_votes<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let keep_from_epoch = current_epoch.saturating_sub(params.unbonding_len);
+    let handle = epoch_votes_handle();
+    let mut pruned: Vec<(Epoch, u64)> = Vec::new();
+    for res in handle.iter(storage)? {
+        let (
+            NestedSubKey::Data {
+                key: epoch,
+                nested_sub_key: SubKey::Data(height),
+            },
+            _votes,
+        ) = res?;
+        if epoch < keep_from_epoch {
+            pruned.push((epoch, height));
+        }
+    }
+    for (epoch, height) in pruned {
+        handle.at(&epoch).remove(storage, &height)?;
+    }
+    Ok(())
+}
+
+/// Clear all the entries of the rewards accumulator. This must be called at the
+/// start of a new epoch, once the rewards of the last epoch have been
+/// distributed, so that the accumulated values of the last epoch don't carry
+/// over to the new one.
+pub fn reset_rewards_accumulator<S>(storage: &mut S) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    // TODO: refactor with https://github.com/anoma/namada/issues/1225
+    let handle = rewards_accumulator_handle();
+    let addresses_to_drop: Vec<Address> = handle
+        .iter(storage)?
+        .map(|res| res.map(|(address, _)| address))
+        .collect::<storage_api::Result<_>>()?;
+    for address in addresses_to_drop {
+        handle.remove(storage, &address)?;
+    }
+    Ok(())
+}
+
 /// Tally a running sum of the fraction of rewards owed to each validator in
 /// the consensus set. This is used to keep track of the rewards due to each
-/// consensus validator over the lifetime of an epoch.
+/// consensus validator over the lifetime of an epoch. The `proposal_round` is
+/// the consensus round in which the block was committed, used to reduce the
+/// proposer reward of late proposals.
 pub fn log_block_rewards<S>(
     storage: &mut S,
     epoch: impl Into<Epoch>,
     proposer_address: &Address,
+    proposal_round: u32,
     votes: Vec<VoteInfo>,
 ) -> storage_api::Result<()>
 where
@@ -2582,17 +4500,29 @@ where
     let params = read_pos_params(storage)?;
     let consensus_validators = consensus_validator_set_handle().at(&epoch);
 
-    // Get total stake of the consensus validator set
+    // Get total stake of the consensus validator set and check that the
+    // proposer is one of the consensus validators
     let mut total_consensus_stake = token::Amount::default();
+    let mut is_proposer_consensus_validator = false;
     for validator in consensus_validators.iter(storage)? {
         let (
             NestedSubKey::Data {
                 key: amount,
                 nested_sub_key: _,
             },
-            _address,
+            address,
         ) = validator?;
         total_consensus_stake += amount;
+        if address == *proposer_address {
+            is_proposer_consensus_validator = true;
+        }
+    }
+    if !is_proposer_consensus_validator {
+        tracing::warn!(
+            "The block proposer {proposer_address} is not in the consensus \
+             validator set of epoch {epoch}, it will not receive the proposer \
+             reward"
+        );
     }
 
     // Get set of signing validator addresses and the combined stake of
@@ -2627,10 +4557,16 @@ where
         total_signing_stake += stake_from_deltas;
     }
 
+    // The proposer reward is reduced for every extra consensus round that was
+    // needed to commit the block
+    let proposer_reward = (params.block_proposer_reward
+        - params.proposer_timing_bonus * Decimal::from(proposal_round))
+    .max(Decimal::ZERO);
+
     // Get the block rewards coefficients (proposing, signing/voting,
     // consensus set status)
     let rewards_calculator = PosRewardsCalculator {
-        proposer_reward: params.block_proposer_reward,
+        proposer_reward,
         signer_reward: params.block_vote_reward,
         signing_stake: u64::from(total_signing_stake),
         total_stake: u64::from(total_consensus_stake),
@@ -2678,8 +4614,8 @@ where
         // {}",     epoch, stake
         // );
 
-        // Proposer reward
-        if address == *proposer_address {
+        // Proposer reward, only for a proposer from the consensus set
+        if is_proposer_consensus_validator && address == *proposer_address {
             rewards_frac += coeffs.proposer_coeff;
         }
         // Signer reward
@@ -2692,9 +4628,8 @@ where
             * (stake_unscaled / consensus_stake_unscaled);
 
         // Update the rewards accumulator
-        let prev = rewards_accumulator_handle()
-            .get(storage, &address)?
-            .unwrap_or_default();
+        let prev =
+            rewards_accumulator_handle().get_or_default(storage, &address)?;
         values.insert(address, prev + rewards_frac);
     }
     for (address, value) in values.into_iter() {
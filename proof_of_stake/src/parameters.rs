@@ -8,7 +8,7 @@ use thiserror::Error;
 
 /// Proof-of-Stake system parameters, set at genesis and can only be changed via
 /// governance
-#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
 pub struct PosParams {
     /// A maximum number of consensus validators
     pub max_validator_slots: u64,
@@ -29,6 +29,9 @@ pub struct PosParams {
     /// Amount of tokens rewarded to each validator that voted on a block
     /// proposal
     pub block_vote_reward: Decimal,
+    /// Reduction of the block proposer reward for every consensus round that
+    /// was needed to commit the proposed block, rewarding timely proposals
+    pub proposer_timing_bonus: Decimal,
     /// Maximum staking rewards rate per annum
     pub max_inflation_rate: Decimal,
     /// Target ratio of staked NAM tokens to total NAM tokens
@@ -39,6 +42,16 @@ pub struct PosParams {
     /// Fraction of validator's stake that should be slashed on a light client
     /// attack.
     pub light_client_attack_min_slash_rate: Decimal,
+    /// Number of epochs after the submission of a multisig bond within which
+    /// it must be approved by enough of its sources to be committed
+    pub multisig_timeout_epochs: u64,
+    /// Minimum number of epochs between the announcement of a validator's
+    /// commission rate change and the change itself. When `0`, the rate can
+    /// be changed without an announcement.
+    pub commission_change_notice_epochs: u64,
+    /// Number of epochs between two beacon epochs, at the start of which a
+    /// snapshot of the PoS system is taken. When `0`, no snapshots are taken.
+    pub beacon_epoch_interval: u64,
 }
 
 impl Default for PosParams {
@@ -52,6 +65,7 @@ impl Default for PosParams {
             tm_votes_per_token: dec!(1.0),
             block_proposer_reward: dec!(0.125),
             block_vote_reward: dec!(0.1),
+            proposer_timing_bonus: dec!(0.01),
             // PoS inflation of 10%
             max_inflation_rate: dec!(0.1),
             // target staked ratio of 2/3
@@ -60,6 +74,9 @@ impl Default for PosParams {
             duplicate_vote_min_slash_rate: dec!(0.001),
             // slash 0.1%
             light_client_attack_min_slash_rate: dec!(0.001),
+            multisig_timeout_epochs: 2,
+            commission_change_notice_epochs: 0,
+            beacon_epoch_interval: 10,
         }
     }
 }
@@ -81,6 +98,11 @@ pub enum ValidationError {
          pipeline: {1}"
     )]
     UnbondingLenTooShort(u64, u64),
+    #[error(
+        "Parameter {0} cannot be changed from {1} to {2}, the maximum change \
+         rate is {3}"
+    )]
+    ChangeTooLarge(&'static str, Decimal, Decimal, Decimal),
 }
 
 /// The number of fundamental units per whole token of the native staking token
@@ -142,6 +164,106 @@ impl PosParams {
 
         errors
     }
+
+    /// Validate a change of PoS parameters values from `self` to `new`. Every
+    /// value may change by at most `max_change_rate` relative to its current
+    /// value. A value that is currently `0` has no reference magnitude and may
+    /// change freely, so that the features disabled by a `0` value can be
+    /// enabled. Returns an empty list if the change is valid.
+    #[must_use]
+    pub fn validate_change(
+        &self,
+        new: &PosParams,
+        max_change_rate: Decimal,
+    ) -> Vec<ValidationError> {
+        let changes = [
+            (
+                "max_validator_slots",
+                Decimal::from(self.max_validator_slots),
+                Decimal::from(new.max_validator_slots),
+            ),
+            (
+                "pipeline_len",
+                Decimal::from(self.pipeline_len),
+                Decimal::from(new.pipeline_len),
+            ),
+            (
+                "unbonding_len",
+                Decimal::from(self.unbonding_len),
+                Decimal::from(new.unbonding_len),
+            ),
+            (
+                "tm_votes_per_token",
+                self.tm_votes_per_token,
+                new.tm_votes_per_token,
+            ),
+            (
+                "block_proposer_reward",
+                self.block_proposer_reward,
+                new.block_proposer_reward,
+            ),
+            (
+                "block_vote_reward",
+                self.block_vote_reward,
+                new.block_vote_reward,
+            ),
+            (
+                "proposer_timing_bonus",
+                self.proposer_timing_bonus,
+                new.proposer_timing_bonus,
+            ),
+            (
+                "max_inflation_rate",
+                self.max_inflation_rate,
+                new.max_inflation_rate,
+            ),
+            (
+                "target_staked_ratio",
+                self.target_staked_ratio,
+                new.target_staked_ratio,
+            ),
+            (
+                "duplicate_vote_min_slash_rate",
+                self.duplicate_vote_min_slash_rate,
+                new.duplicate_vote_min_slash_rate,
+            ),
+            (
+                "light_client_attack_min_slash_rate",
+                self.light_client_attack_min_slash_rate,
+                new.light_client_attack_min_slash_rate,
+            ),
+            (
+                "multisig_timeout_epochs",
+                Decimal::from(self.multisig_timeout_epochs),
+                Decimal::from(new.multisig_timeout_epochs),
+            ),
+            (
+                "commission_change_notice_epochs",
+                Decimal::from(self.commission_change_notice_epochs),
+                Decimal::from(new.commission_change_notice_epochs),
+            ),
+            (
+                "beacon_epoch_interval",
+                Decimal::from(self.beacon_epoch_interval),
+                Decimal::from(new.beacon_epoch_interval),
+            ),
+        ];
+        changes
+            .into_iter()
+            .filter(|(_, old, new)| {
+                !old.is_zero()
+                    && (*new - *old).abs() > old.abs() * max_change_rate
+            })
+            .map(|(param, old, new)| {
+                ValidationError::ChangeTooLarge(
+                    param,
+                    old,
+                    new,
+                    max_change_rate,
+                )
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -163,6 +285,64 @@ mod tests {
                 errors
             );
         }
+
+        /// The Tendermint voting power of a validator holding all the tokens
+        /// must not exceed Tendermint's limit for any valid parameters
+        #[test]
+        fn test_max_tm_voting_power(pos_params in arb_pos_params(None)) {
+            let power = crate::types::into_tm_voting_power(
+                pos_params.tm_votes_per_token,
+                TOKEN_MAX_AMOUNT,
+            );
+            assert!((0..=MAX_TOTAL_VOTING_POWER).contains(&power));
+        }
+    }
+
+    #[test]
+    fn test_validate_change() {
+        let params = PosParams::default();
+        let max_change_rate = dec!(0.5);
+
+        // No change is always valid
+        assert!(params.validate_change(&params, max_change_rate).is_empty());
+
+        // Changes within the rate are valid
+        let new_params = PosParams {
+            max_validator_slots: params.max_validator_slots * 3 / 2,
+            max_inflation_rate: params.max_inflation_rate / dec!(2),
+            ..params.clone()
+        };
+        let errors = params.validate_change(&new_params, max_change_rate);
+        assert!(errors.is_empty());
+
+        // Changes above the rate are invalid
+        let new_params = PosParams {
+            unbonding_len: params.unbonding_len * 2,
+            target_staked_ratio: params.target_staked_ratio / dec!(4),
+            ..params.clone()
+        };
+        let errors = params.validate_change(&new_params, max_change_rate);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            ValidationError::ChangeTooLarge("unbonding_len", _, _, _)
+        ));
+        assert!(matches!(
+            errors[1],
+            ValidationError::ChangeTooLarge("target_staked_ratio", _, _, _)
+        ));
+
+        // A value that is currently zero may change freely
+        let params = PosParams {
+            commission_change_notice_epochs: 0,
+            ..params
+        };
+        let new_params = PosParams {
+            commission_change_notice_epochs: 10,
+            ..params.clone()
+        };
+        let errors = params.validate_change(&new_params, max_change_rate);
+        assert!(errors.is_empty());
     }
 }
 
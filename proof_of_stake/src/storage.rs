@@ -17,14 +17,22 @@ const VALIDATOR_DELTAS_STORAGE_KEY: &str = "deltas";
 const VALIDATOR_COMMISSION_RATE_STORAGE_KEY: &str = "commission_rate";
 const VALIDATOR_MAX_COMMISSION_CHANGE_STORAGE_KEY: &str =
     "max_commission_rate_change";
+const VALIDATOR_COMMISSION_RATE_ANNOUNCEMENT_STORAGE_KEY: &str =
+    "commission_rate_announcement";
 const VALIDATOR_SELF_REWARDS_PRODUCT_KEY: &str = "validator_rewards_product";
 const VALIDATOR_DELEGATION_REWARDS_PRODUCT_KEY: &str =
     "delegation_rewards_product";
 const VALIDATOR_LAST_KNOWN_PRODUCT_EPOCH_KEY: &str =
     "last_known_rewards_product_epoch";
+const VALIDATOR_SLASHES_STAKE_KEY: &str = "slashes_stake_at_infraction";
 const SLASHES_PREFIX: &str = "slash";
 const BOND_STORAGE_KEY: &str = "bond";
 const UNBOND_STORAGE_KEY: &str = "unbond";
+const DELEGATOR_VALIDATORS_STORAGE_KEY: &str = "delegator_validators";
+const DELEGATOR_VALIDATORS_REMOVALS_STORAGE_KEY: &str =
+    "delegator_validators_removals";
+const DELEGATOR_VALIDATORS_MIGRATED_STORAGE_KEY: &str =
+    "delegator_validators_migrated";
 const VALIDATOR_SETS_STORAGE_PREFIX: &str = "validator_sets";
 const CONSENSUS_VALIDATOR_SET_STORAGE_KEY: &str = "consensus";
 const NUM_CONSENSUS_VALIDATORS_STORAGE_KEY: &str = "num_consensus";
@@ -32,6 +40,16 @@ const BELOW_CAPACITY_VALIDATOR_SET_STORAGE_KEY: &str = "below_capacity";
 const TOTAL_DELTAS_STORAGE_KEY: &str = "total_deltas";
 const VALIDATOR_SET_POSITIONS_KEY: &str = "validator_set_positions";
 const CONSENSUS_KEYS: &str = "consensus_keys";
+const TOMBSTONED_VALIDATORS_KEY: &str = "tombstoned_validators";
+const PENDING_MULTISIG_BONDS_KEY: &str = "pending_multisig_bonds";
+const MULTISIG_BOND_COUNTER_KEY: &str = "multisig_bond_counter";
+const BONDS_SUMMARY_KEY: &str = "bonds_summary";
+const EPOCH_VOTES_KEY: &str = "epoch_votes";
+const TOTAL_BURNED_KEY: &str = "total_burned";
+const BEACON_SNAPSHOTS_KEY: &str = "beacon_snapshots";
+const TOTAL_UNBONDING_KEY: &str = "total_unbonding";
+const REWARDS_ACCUMULATOR_SNAPSHOTS_STORAGE_KEY: &str =
+    "validator_rewards_accumulator_snapshots";
 const LAST_BLOCK_PROPOSER_STORAGE_KEY: &str = "last_block_proposer";
 const CONSENSUS_VALIDATOR_SET_ACCUMULATOR_STORAGE_KEY: &str =
     "validator_rewards_accumulator";
@@ -140,6 +158,27 @@ pub fn is_validator_commission_rate_key(key: &Key) -> Option<&Address> {
     }
 }
 
+/// Is storage key for validator's epoched commission rate data, i.e. any key
+/// under [`validator_commission_rate_key`]?
+pub fn is_validator_commission_rate_sub_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(validator),
+            DbKeySeg::StringSeg(key),
+            _,
+            ..,
+        ] if addr == &ADDRESS
+            && prefix == VALIDATOR_STORAGE_PREFIX
+            && key == VALIDATOR_COMMISSION_RATE_STORAGE_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
 /// Storage key for validator's maximum commission rate change per epoch.
 pub fn validator_max_commission_rate_change_key(validator: &Address) -> Key {
     validator_prefix(validator)
@@ -167,6 +206,33 @@ pub fn is_validator_max_commission_rate_change_key(
     }
 }
 
+/// Storage key for validator's announced commission rate change.
+pub fn validator_commission_rate_announcement_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_COMMISSION_RATE_ANNOUNCEMENT_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for validator's announced commission rate change?
+pub fn is_validator_commission_rate_announcement_key(
+    key: &Key,
+) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(validator),
+            DbKeySeg::StringSeg(key),
+        ] if addr == &ADDRESS
+            && prefix == VALIDATOR_STORAGE_PREFIX
+            && key == VALIDATOR_COMMISSION_RATE_ANNOUNCEMENT_STORAGE_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
 /// Storage key for validator's self rewards products.
 pub fn validator_self_rewards_product_key(validator: &Address) -> Key {
     validator_prefix(validator)
@@ -315,6 +381,13 @@ pub fn validator_slashes_key(validator: &Address) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for validator's stake at the infraction epochs of its slashes.
+pub fn validator_slashes_stake_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_SLASHES_STAKE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// NEW: Is storage key for validator's slashes
 pub fn is_validator_slashes_key(key: &Key) -> Option<Address> {
     if key.segments.len() >= 5 {
@@ -450,6 +523,63 @@ pub fn is_unbond_key(key: &Key) -> Option<(BondId, Epoch, Epoch)> {
     }
 }
 
+/// Storage key prefix for the index of validators to which the given source
+/// address has bonds.
+pub fn delegator_validator_index_prefix(source: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DELEGATOR_VALIDATORS_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&source.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the index entry of a bond from the given source address
+/// to the given validator. The entry is present until the start of the epoch
+/// in which a full unbond of the bond takes effect.
+pub fn delegator_validator_index_key(
+    source: &Address,
+    validator: &Address,
+) -> Key {
+    delegator_validator_index_prefix(source)
+        .push(&validator.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a delegator validator index entry? Returns the bond ID
+/// if so.
+pub fn is_delegator_validator_index_key(key: &Key) -> Option<BondId> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(source),
+            DbKeySeg::AddressSeg(validator),
+        ] if addr == &ADDRESS && prefix == DELEGATOR_VALIDATORS_STORAGE_KEY => {
+            Some(BondId {
+                source: source.clone(),
+                validator: validator.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the delegator validator index entries that are due to be
+/// removed, keyed by the epoch from which they can be removed.
+pub fn delegator_validator_index_removals_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DELEGATOR_VALIDATORS_REMOVALS_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the marker that is present once the bonds that precede
+/// the delegator validator index have been indexed.
+pub fn delegator_validator_index_migrated_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DELEGATOR_VALIDATORS_MIGRATED_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Storage prefix for validator sets.
 pub fn validator_sets_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -534,6 +664,13 @@ pub fn consensus_validator_rewards_accumulator_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage prefix for the snapshots of the rewards accumulator.
+pub fn rewards_accumulator_snapshots_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&REWARDS_ACCUMULATOR_SNAPSHOTS_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Is storage key for the consensus validator set?
 pub fn is_consensus_validator_set_accumulator_key(key: &Key) -> bool {
     matches!(&key.segments[..], [
@@ -543,6 +680,27 @@ pub fn is_consensus_validator_set_accumulator_key(key: &Key) -> bool {
             && key == CONSENSUS_VALIDATOR_SET_ACCUMULATOR_STORAGE_KEY)
 }
 
+/// Is storage key for a validator's entry in the consensus validator set
+/// rewards accumulator? Returns the validator's address if so.
+pub fn is_consensus_validator_rewards_accumulator_key(
+    key: &Key,
+) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(key),
+            DbKeySeg::StringSeg(data),
+            DbKeySeg::AddressSeg(validator),
+        ] if addr == &ADDRESS
+            && key == CONSENSUS_VALIDATOR_SET_ACCUMULATOR_STORAGE_KEY
+            && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
 /// Get validator address from bond key
 pub fn get_validator_address_from_bond(key: &Key) -> Option<Address> {
     match key.get_at(3) {
@@ -572,3 +730,99 @@ pub fn consensus_keys_key() -> Key {
 pub fn is_consensus_keys_key(key: &Key) -> bool {
     matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == CONSENSUS_KEYS)
 }
+
+/// Storage key for the set of tombstoned validators.
+pub fn tombstoned_validators_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&TOMBSTONED_VALIDATORS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the set of tombstoned validators?
+pub fn is_tombstoned_validators_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key), ..] if addr == &ADDRESS && key == TOMBSTONED_VALIDATORS_KEY)
+}
+
+/// Storage key for the multisig bonds that are waiting for approvals.
+pub fn pending_multisig_bonds_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&PENDING_MULTISIG_BONDS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the multisig bonds that are waiting for approvals?
+pub fn is_pending_multisig_bonds_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key), ..] if addr == &ADDRESS && key == PENDING_MULTISIG_BONDS_KEY)
+}
+
+/// Is storage key for a multisig bond that is waiting for approvals? Returns
+/// the ID of the bond.
+pub fn is_pending_multisig_bond_key(key: &Key) -> Option<u64> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(data),
+            DbKeySeg::StringSeg(id),
+        ] if addr == &ADDRESS
+            && prefix == PENDING_MULTISIG_BONDS_KEY
+            && data == lazy_map::DATA_SUBKEY =>
+        {
+            u64::parse(id.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the counter used to assign IDs to multisig bonds.
+pub fn multisig_bond_counter_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&MULTISIG_BOND_COUNTER_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the counter used to assign IDs to multisig bonds?
+pub fn is_multisig_bond_counter_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == MULTISIG_BOND_COUNTER_KEY)
+}
+
+/// Storage key for the summary of the bonds in the PoS system.
+pub fn bonds_summary_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&BONDS_SUMMARY_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the votes included in the blocks of every epoch.
+pub fn epoch_votes_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&EPOCH_VOTES_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the total amount of tokens burned from the slash pool.
+pub fn total_burned_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&TOTAL_BURNED_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the total amount of tokens burned from the slash pool?
+pub fn is_total_burned_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == TOTAL_BURNED_KEY)
+}
+
+/// Storage key for the snapshots of the PoS system taken at beacon epochs.
+pub fn beacon_snapshots_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&BEACON_SNAPSHOTS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the total amounts of unbonded tokens that become
+/// withdrawable in an epoch.
+pub fn total_unbonding_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&TOTAL_UNBONDING_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
@@ -3,12 +3,16 @@
 mod state_machine;
 
 use std::cmp::min;
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
 
+use namada_core::ledger::parameters::storage::get_epochs_per_year_key;
 use namada_core::ledger::storage::testing::TestWlStorage;
 use namada_core::ledger::storage_api::collections::lazy_map;
-use namada_core::ledger::storage_api::token::credit_tokens;
-use namada_core::ledger::storage_api::StorageRead;
+use namada_core::ledger::storage_api::token::{
+    credit_tokens, read_balance, read_total_supply,
+};
+use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
 use namada_core::types::address::testing::{
     address_from_simple_seed, arb_established_address,
 };
@@ -29,24 +33,55 @@ use test_log::test;
 
 use crate::parameters::testing::arb_pos_params;
 use crate::parameters::PosParams;
+use crate::storage::{
+    delegator_validator_index_key, delegator_validator_index_migrated_key,
+};
 use crate::types::{
-    into_tm_voting_power, BondDetails, BondId, BondsAndUnbondsDetails,
-    ConsensusValidator, GenesisValidator, Position, ReverseOrdTokenAmount,
-    UnbondDetails, ValidatorSetUpdate, ValidatorState, WeightedValidator,
+    into_tm_voting_power, BeaconSnapshot, BondDetails, BondId,
+    BondsAndUnbondsDetails, BondsSummary, ConsensusValidator, GenesisValidator,
+    MultisigBond, Position, ReverseOrdTokenAmount, Slash, SlashPoolDestination,
+    SlashType, UnbondDetails, ValidatorSetUpdate, ValidatorState, VoteInfo,
+    WeightedValidator,
 };
 use crate::{
+    announce_validator_commission_rate_change, approve_multisig_bond,
     become_validator, below_capacity_validator_set_handle, bond_handle,
-    bond_tokens, bonds_and_unbonds, consensus_validator_set_handle,
-    copy_validator_sets_and_positions, find_validator_by_raw_hash,
-    init_genesis, insert_validator_into_validator_set,
-    read_below_capacity_validator_set_addresses_with_stake,
-    read_consensus_validator_set_addresses_with_stake,
-    read_num_consensus_validators, read_total_stake,
-    read_validator_delta_value, read_validator_stake, staking_token_address,
-    total_deltas_handle, unbond_handle, unbond_tokens, update_validator_deltas,
-    update_validator_set, validator_consensus_key_handle,
-    validator_set_update_tendermint, validator_state_handle, withdraw_tokens,
-    write_validator_address_raw_hash,
+    bond_tokens, bonds_and_unbonds, burn_address, burn_slash_pool,
+    change_validator_commission_rate, compute_annual_staking_yield,
+    compute_compounded_rewards_product, compute_total_unbonding_at_epoch,
+    consensus_validator_set_handle, copy_validator_sets_and_positions,
+    count_total_active_bonds, count_unique_delegators, delegator_reward_share,
+    delegator_rewards_products_handle, epoched_rewards_snapshot_handle,
+    find_bonds, find_bonds_above, find_bonds_in_epoch_range, find_delegations,
+    find_epochs_for_consensus_key, find_first_position, find_last_position,
+    find_validator_by_raw_hash, find_validator_consensus_keys,
+    get_max_below_capacity_validator_amount,
+    get_max_consensus_validator_amount, init_genesis,
+    insert_validator_into_set, insert_validator_into_validator_set,
+    is_beacon_epoch, is_delegator, migrate_delegator_validator_index,
+    next_beacon_epoch, pending_multisig_bonds_handle,
+    preview_next_epoch_consensus_set, prune_delegator_validator_index,
+    prune_epoch_votes, read_beacon_snapshot,
+    read_below_capacity_validator_set_addresses_with_stake, read_block_votes,
+    read_bonds_summary, read_consensus_validator_set_addresses_with_stake,
+    read_num_consensus_validators, read_total_burned, read_total_stake,
+    read_total_unbonding_at_epoch, read_validator_commission_rate_announcement,
+    read_validator_consensus_key, read_validator_delta_value,
+    read_validator_stake, record_block_votes, redistribute_slash_pool,
+    remove_expired_multisig_bonds, remove_validator_address_raw_hash,
+    reset_rewards_accumulator, rewards_accumulator_handle,
+    save_rewards_accumulator_snapshot, simulate_epoch_transition, slash,
+    slash_pool_address, staking_token_address, submit_multisig_bond,
+    tombstone_validator, total_deltas_handle, unbond_handle, unbond_tokens,
+    update_bonds_summary, update_total_unbonding, update_validator_deltas,
+    update_validator_set_for_stake_change,
+    update_validator_set_for_state_change, validator_commission_earned,
+    validator_commission_rate_handle, validator_consensus_key_handle,
+    validator_deltas_handle, validator_rewards_products_handle,
+    validator_set_positions_handle, validator_set_update_tendermint,
+    validator_slashes_handle, validator_slashes_stake_handle,
+    validator_state_handle, withdraw_tokens, write_beacon_snapshot,
+    write_validator_address_raw_hash, write_validator_state,
 };
 
 proptest! {
@@ -131,10 +166,7 @@ fn test_init_genesis_aux(
 
         let addr = &validator.address;
         let self_bonds = bond_details
-            .remove(&BondId {
-                source: addr.clone(),
-                validator: addr.clone(),
-            })
+            .remove(&BondId::self_bond(addr.clone()))
             .unwrap();
         assert_eq!(self_bonds.bonds.len(), 1);
         assert_eq!(
@@ -268,10 +300,7 @@ fn test_bonds_aux(params: PosParams, validators: Vec<GenesisValidator>) {
     assert_eq!(total_stake_before + amount_self_bond, total_stake_after);
 
     // Check bond details after self-bond
-    let self_bond_id = BondId {
-        source: validator.address.clone(),
-        validator: validator.address.clone(),
-    };
+    let self_bond_id = BondId::self_bond(validator.address.clone());
     let check_bond_details = |ix, bond_details: BondsAndUnbondsDetails| {
         println!("Check index {ix}");
         assert_eq!(bond_details.len(), 1);
@@ -850,157 +879,1736 @@ fn test_validator_raw_hash() {
     let found =
         find_validator_by_raw_hash(&storage, &expected_raw_hash).unwrap();
     assert_eq!(found, Some(address));
+
+    remove_validator_address_raw_hash(&mut storage, &consensus_pk).unwrap();
+    let found =
+        find_validator_by_raw_hash(&storage, &expected_raw_hash).unwrap();
+    assert_eq!(found, None);
 }
 
 #[test]
-fn test_validator_sets() {
+fn test_redistribute_slash_pool() {
     let mut s = TestWlStorage::default();
-    // Only 3 consensus validator slots
-    let params = PosParams {
-        max_validator_slots: 3,
-        ..Default::default()
-    };
-    let addr_seed = "seed";
-    let mut address_gen = EstablishedAddressGen::new(addr_seed);
-    let mut sk_seed = 0;
-    let mut gen_validator = || {
-        let res = (
-            address_gen.generate_address(addr_seed),
-            key::testing::common_sk_from_simple_seed(sk_seed).to_public(),
-        );
-        // bump the sk seed
-        sk_seed += 1;
-        res
-    };
+    let current_epoch = s.storage.block.epoch;
+    let staking_token = staking_token_address(&s);
+    let treasury = address::testing::established_address_1();
+    let pool_amount = token::Amount::from(1_000);
+    credit_tokens(&mut s, &staking_token, &slash_pool_address(), pool_amount)
+        .unwrap();
 
-    // A helper to insert a non-genesis validator
-    let insert_validator = |s: &mut TestWlStorage,
-                            addr,
-                            pk: &PublicKey,
-                            stake: token::Amount,
-                            epoch: Epoch| {
-        insert_validator_into_validator_set(
+    // Redistribute a part of the pool to the treasury
+    let treasury_amount = token::Amount::from(400);
+    redistribute_slash_pool(
+        &mut s,
+        current_epoch,
+        SlashPoolDestination::Treasury(treasury.clone()),
+        Some(treasury_amount),
+    )
+    .unwrap();
+    let treasury_balance = read_balance(&s, &staking_token, &treasury).unwrap();
+    assert_eq!(treasury_balance, treasury_amount);
+
+    // Cannot redistribute more than what's in the pool
+    let res = redistribute_slash_pool(
+        &mut s,
+        current_epoch,
+        SlashPoolDestination::Burn,
+        Some(pool_amount),
+    );
+    assert!(res.is_err());
+
+    // Burn the rest of the pool
+    redistribute_slash_pool(
+        &mut s,
+        current_epoch,
+        SlashPoolDestination::Burn,
+        None,
+    )
+    .unwrap();
+    let pool_balance =
+        read_balance(&s, &staking_token, &slash_pool_address()).unwrap();
+    assert!(pool_balance.is_zero());
+    let burned = read_balance(&s, &staking_token, &burn_address()).unwrap();
+    assert_eq!(burned, pool_amount - treasury_amount);
+}
+
+#[test]
+fn test_burn_slash_pool() {
+    let mut s = TestWlStorage::default();
+    let current_epoch = s.storage.block.epoch;
+    let staking_token = staking_token_address(&s);
+    let pool_amount = token::Amount::from(1_000);
+    credit_tokens(&mut s, &staking_token, &slash_pool_address(), pool_amount)
+        .unwrap();
+    assert!(read_total_burned(&s).unwrap().is_zero());
+
+    // Burn a part of the pool
+    let supply_pre = read_total_supply(&s, &staking_token).unwrap();
+    let burn_amount = token::Amount::from(300);
+    burn_slash_pool(&mut s, burn_amount).unwrap();
+    let pool_balance =
+        read_balance(&s, &staking_token, &slash_pool_address()).unwrap();
+    assert_eq!(pool_balance, pool_amount - burn_amount);
+    let burned = read_balance(&s, &staking_token, &burn_address()).unwrap();
+    assert_eq!(burned, burn_amount);
+    // The burned tokens stay in the total supply, held by the burn address
+    let supply_post = read_total_supply(&s, &staking_token).unwrap();
+    assert_eq!(supply_pre, supply_post);
+    assert_eq!(read_total_burned(&s).unwrap(), burn_amount);
+
+    // Cannot burn more than what's in the pool
+    let res = burn_slash_pool(&mut s, pool_amount);
+    assert!(res.is_err());
+
+    // Burning the rest via redistribution is also counted
+    redistribute_slash_pool(
+        &mut s,
+        current_epoch,
+        SlashPoolDestination::Burn,
+        None,
+    )
+    .unwrap();
+    assert_eq!(read_total_burned(&s).unwrap(), pool_amount);
+}
+
+#[test]
+fn test_write_validator_state() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let current_epoch = s.storage.block.epoch;
+    let validator = address::testing::established_address_1();
+
+    let write = |s: &mut TestWlStorage, state| {
+        write_validator_state(
             s,
             &params,
-            addr,
-            stake,
-            epoch,
+            &validator,
+            state,
+            current_epoch,
             params.pipeline_len,
         )
+    };
+    write(&mut s, ValidatorState::BelowCapacity).unwrap();
+    write(&mut s, ValidatorState::Consensus).unwrap();
+    write(&mut s, ValidatorState::Inactive).unwrap();
+    // An inactive validator cannot be re-activated
+    assert!(write(&mut s, ValidatorState::Consensus).is_err());
+    assert!(write(&mut s, ValidatorState::BelowCapacity).is_err());
+
+    let state = validator_state_handle(&validator)
+        .get(&s, current_epoch + params.pipeline_len, &params)
         .unwrap();
+    assert_eq!(state, Some(ValidatorState::Inactive));
+}
 
-        update_validator_deltas(s, &params, addr, stake.change(), epoch)
-            .unwrap();
+#[test]
+fn test_find_epochs_for_consensus_key() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let old_key = key::testing::keypair_1().to_public();
+    let new_key = key::testing::keypair_2().to_public();
+    let handle = validator_consensus_key_handle(&validator);
+
+    handle
+        .init_at_genesis(&mut s, old_key.clone(), Epoch(0))
+        .unwrap();
+    // Rotate the key in epoch 1, it becomes active at the pipeline offset
+    handle
+        .set(&mut s, new_key.clone(), Epoch(1), params.pipeline_len)
+        .unwrap();
+    let rotation_epoch = Epoch(1) + params.pipeline_len;
 
-        // Set their consensus key (needed for
-        // `validator_set_update_tendermint` fn)
-        validator_consensus_key_handle(addr)
-            .set(s, pk.clone(), epoch, params.pipeline_len)
-            .unwrap();
+    let old_key_epochs = find_epochs_for_consensus_key(
+        &s,
+        &params,
+        &validator,
+        &old_key,
+        Epoch(0),
+        rotation_epoch,
+    )
+    .unwrap();
+    assert_eq!(
+        old_key_epochs,
+        Epoch(0).iter_range(rotation_epoch.0).collect::<Vec<_>>()
+    );
+    let new_key_epochs = find_epochs_for_consensus_key(
+        &s,
+        &params,
+        &validator,
+        &new_key,
+        Epoch(0),
+        rotation_epoch,
+    )
+    .unwrap();
+    assert_eq!(new_key_epochs, vec![rotation_epoch]);
+
+    // Look-up the keys active at, before and after the rotation
+    assert_eq!(
+        read_validator_consensus_key(&s, &params, &validator, Epoch(0))
+            .unwrap(),
+        Some(old_key.clone())
+    );
+    assert_eq!(
+        read_validator_consensus_key(&s, &params, &validator, rotation_epoch)
+            .unwrap(),
+        Some(new_key.clone())
+    );
+    let keys = find_validator_consensus_keys(
+        &s,
+        &params,
+        &validator,
+        Epoch(0),
+        rotation_epoch,
+    )
+    .unwrap();
+    assert_eq!(keys, vec![old_key.clone(), new_key]);
+    let keys = find_validator_consensus_keys(
+        &s,
+        &params,
+        &validator,
+        Epoch(0),
+        rotation_epoch.prev(),
+    )
+    .unwrap();
+    assert_eq!(keys, vec![old_key]);
+}
+
+/// Initialize PoS genesis in the given storage with the given validators and
+/// their stakes. The validators' consensus keys are the testing keypairs in
+/// order and their commission rate and max commission rate change are both
+/// 0.1. Returns the genesis epoch.
+fn init_test_pos(
+    s: &mut TestWlStorage,
+    params: &PosParams,
+    validators: &[(Address, token::Amount)],
+) -> Epoch {
+    let keys = [key::testing::keypair_1(), key::testing::keypair_2()];
+    assert!(
+        validators.len() <= keys.len(),
+        "There are only {} testing keypairs",
+        keys.len()
+    );
+    let genesis_validators =
+        validators
+            .iter()
+            .zip(keys)
+            .map(|((address, tokens), consensus_sk)| GenesisValidator {
+                address: address.clone(),
+                tokens: *tokens,
+                consensus_key: consensus_sk.to_public(),
+                commission_rate: Decimal::new(1, 1),
+                max_commission_rate_change: Decimal::new(1, 1),
+            });
+    let current_epoch = s.storage.block.epoch;
+    init_genesis(s, params, genesis_validators, current_epoch).unwrap();
+    current_epoch
+}
+
+#[test]
+fn test_update_validator_set_for_state_change() {
+    let mut s = TestWlStorage::default();
+    // Only 1 consensus validator slot
+    let params = PosParams {
+        max_validator_slots: 1,
+        ..Default::default()
     };
+    let val1 = address::testing::established_address_1();
+    let val2 = address::testing::established_address_2();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[
+            (val1.clone(), token::Amount::whole(10)),
+            (val2.clone(), token::Amount::whole(1)),
+        ],
+    );
+    s.commit_block().unwrap();
 
-    // Start with two genesis validators with 1 NAM stake
-    let epoch = Epoch::default();
-    let ((val1, pk1), stake1) = (gen_validator(), token::Amount::whole(1));
-    let ((val2, pk2), stake2) = (gen_validator(), token::Amount::whole(1));
-    let ((val3, pk3), stake3) = (gen_validator(), token::Amount::whole(10));
-    let ((val4, pk4), stake4) = (gen_validator(), token::Amount::whole(1));
-    let ((val5, pk5), stake5) = (gen_validator(), token::Amount::whole(100));
-    let ((val6, pk6), stake6) = (gen_validator(), token::Amount::whole(1));
-    let ((val7, pk7), stake7) = (gen_validator(), token::Amount::whole(1));
-    println!("val1: {val1}, {pk1}, {stake1}");
-    println!("val2: {val2}, {pk2}, {stake2}");
-    println!("val3: {val3}, {pk3}, {stake3}");
-    println!("val4: {val4}, {pk4}, {stake4}");
-    println!("val5: {val5}, {pk5}, {stake5}");
-    println!("val6: {val6}, {pk6}, {stake6}");
-    println!("val7: {val7}, {pk7}, {stake7}");
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let state = |s: &TestWlStorage, validator: &Address| {
+        validator_state_handle(validator)
+            .get(s, pipeline_epoch, &params)
+            .unwrap()
+    };
+    let consensus_addresses = |s: &TestWlStorage| {
+        read_consensus_validator_set_addresses_with_stake(s, pipeline_epoch)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.address)
+            .collect::<Vec<_>>()
+    };
 
-    init_genesis(
+    // Deactivating the consensus validator promotes the below-capacity one
+    update_validator_set_for_state_change(
         &mut s,
         &params,
-        [
-            GenesisValidator {
-                address: val1.clone(),
-                tokens: stake1,
-                consensus_key: pk1.clone(),
-                commission_rate: Decimal::new(1, 1),
-                max_commission_rate_change: Decimal::new(1, 1),
-            },
-            GenesisValidator {
-                address: val2.clone(),
-                tokens: stake2,
-                consensus_key: pk2.clone(),
-                commission_rate: Decimal::new(1, 1),
-                max_commission_rate_change: Decimal::new(1, 1),
-            },
-        ]
-        .into_iter(),
-        epoch,
+        &val1,
+        ValidatorState::Inactive,
+        current_epoch,
     )
     .unwrap();
+    assert_eq!(state(&s, &val1), Some(ValidatorState::Inactive));
+    assert_eq!(state(&s, &val2), Some(ValidatorState::Consensus));
+    assert_eq!(consensus_addresses(&s), vec![val2.clone()]);
+    assert_eq!(
+        read_validator_stake(&s, &params, &val1, pipeline_epoch).unwrap(),
+        Some(token::Amount::whole(10))
+    );
+    let position = validator_set_positions_handle()
+        .at(&pipeline_epoch)
+        .get(&s, &val1)
+        .unwrap();
+    assert_eq!(position, None);
 
-    // Advance to EPOCH 1
-    //
-    // We cannot call `get_tendermint_set_updates` for the genesis state as
-    // `validator_set_update_tendermint` is only called 2 blocks before the
-    // start of an epoch and so we need to give it a predecessor epoch (see
-    // `get_tendermint_set_updates`), which we cannot have on the first
-    // epoch. In any way, the initial validator set is given to Tendermint
-    // from InitChain, so `validator_set_update_tendermint` is
-    // not being used for it.
-    let epoch = advance_epoch(&mut s, &params);
-    let pipeline_epoch = epoch + params.pipeline_len;
-
-    // Insert another validator with the greater stake 10 NAM
-    insert_validator(&mut s, &val3, &pk3, stake3, epoch);
-    // Insert validator with stake 1 NAM
-    insert_validator(&mut s, &val4, &pk4, stake4, epoch);
+    // Re-activating it puts it back according to its stake
+    update_validator_set_for_state_change(
+        &mut s,
+        &params,
+        &val1,
+        ValidatorState::Consensus,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(state(&s, &val1), Some(ValidatorState::Consensus));
+    assert_eq!(state(&s, &val2), Some(ValidatorState::BelowCapacity));
+    assert_eq!(consensus_addresses(&s), vec![val1]);
+}
 
-    // Validator `val3` and `val4` will be added at pipeline offset (2) - epoch
-    // 3
-    let val3_and_4_epoch = pipeline_epoch;
+#[test]
+fn test_tombstone_validator() {
+    let mut s = TestWlStorage::default();
+    // Only 1 consensus validator slot
+    let params = PosParams {
+        max_validator_slots: 1,
+        ..Default::default()
+    };
+    let val1 = address::testing::established_address_1();
+    let pk1 = key::testing::keypair_1().to_public();
+    let val2 = address::testing::established_address_2();
+    let mut current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[
+            (val1.clone(), token::Amount::whole(10)),
+            (val2.clone(), token::Amount::whole(1)),
+        ],
+    );
+    s.commit_block().unwrap();
 
-    let consensus_vals: Vec<_> = consensus_validator_set_handle()
-        .at(&pipeline_epoch)
-        .iter(&s)
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let max_consensus_amount = |s: &TestWlStorage| {
+        get_max_consensus_validator_amount(
+            &consensus_validator_set_handle().at(&pipeline_epoch),
+            s,
+        )
         .unwrap()
-        .map(Result::unwrap)
-        .collect();
+    };
+    assert_eq!(max_consensus_amount(&s), token::Amount::whole(10));
 
-    assert_eq!(consensus_vals.len(), 3);
-    assert!(matches!(
-        &consensus_vals[0],
-        (lazy_map::NestedSubKey::Data {
-                key: stake,
-                nested_sub_key: lazy_map::SubKey::Data(position),
-        }, address)
-        if address == &val1 && stake == &stake1 && *position == Position(0)
-    ));
-    assert!(matches!(
-        &consensus_vals[1],
-        (lazy_map::NestedSubKey::Data {
-                key: stake,
-                nested_sub_key: lazy_map::SubKey::Data(position),
-        }, address)
-        if address == &val2 && stake == &stake2 && *position == Position(1)
-    ));
-    assert!(matches!(
-        &consensus_vals[2],
-        (lazy_map::NestedSubKey::Data {
-                key: stake,
-                nested_sub_key: lazy_map::SubKey::Data(position),
-        }, address)
-        if address == &val3 && stake == &stake3 && *position == Position(0)
-    ));
+    // Tombstone the consensus validator
+    tombstone_validator(&mut s, &params, &val1, current_epoch).unwrap();
 
-    // Check tendermint validator set updates - there should be none
-    let tm_updates = get_tendermint_set_updates(&s, &params, epoch);
-    assert!(tm_updates.is_empty());
+    // The below-capacity validator gets promoted in its place
+    let consensus_vals =
+        read_consensus_validator_set_addresses_with_stake(&s, pipeline_epoch)
+            .unwrap();
+    assert_eq!(consensus_vals.len(), 1);
+    assert!(consensus_vals.iter().all(|v| v.address == val2));
+    assert_eq!(max_consensus_amount(&s), token::Amount::whole(1));
+    let below_capacity_vals =
+        read_below_capacity_validator_set_addresses_with_stake(
+            &s,
+            pipeline_epoch,
+        )
+        .unwrap();
+    assert!(below_capacity_vals.is_empty());
+    let state = |s: &TestWlStorage, validator: &Address| {
+        validator_state_handle(validator)
+            .get(s, pipeline_epoch, &params)
+            .unwrap()
+    };
+    assert_eq!(state(&s, &val1), Some(ValidatorState::Tombstoned));
+    assert_eq!(state(&s, &val2), Some(ValidatorState::Consensus));
 
-    // Advance to EPOCH 2
-    let epoch = advance_epoch(&mut s, &params);
-    let pipeline_epoch = epoch + params.pipeline_len;
+    // No stale validator set position is kept for the tombstoned validator
+    let positions = validator_set_positions_handle().at(&pipeline_epoch);
+    assert!(positions.get(&s, &val1).unwrap().is_none());
+    assert!(positions.get(&s, &val2).unwrap().is_some());
+
+    // A tombstoned validator cannot receive new bonds
+    let staking_token = staking_token_address(&s);
+    let delegator = address::testing::established_address_3();
+    let amount = token::Amount::whole(1);
+    credit_tokens(&mut s, &staking_token, &delegator, amount).unwrap();
+    let res =
+        bond_tokens(&mut s, Some(&delegator), &val1, amount, current_epoch);
+    assert!(res.is_err());
+
+    // A tombstoned validator cannot become a validator again
+    let res = become_validator(
+        &mut s,
+        &params,
+        &val1,
+        &key::testing::common_sk_from_simple_seed(0).to_public(),
+        current_epoch,
+        Decimal::new(5, 2),
+        Decimal::new(5, 2),
+    );
+    assert!(res.is_err());
+
+    // Tendermint gets a deactivation of the tombstoned validator when its
+    // removal from the consensus set takes effect
+    for _ in 0..params.pipeline_len - 1 {
+        current_epoch = advance_epoch(&mut s, &params);
+    }
+    let tendermint_updates =
+        validator_set_update_tendermint(&s, &params, current_epoch, |update| {
+            update
+        })
+        .unwrap();
+    let deactivation = ValidatorSetUpdate::Deactivated(pk1);
+    assert!(tendermint_updates.contains(&deactivation));
+}
+
+#[test]
+fn test_deactivate_validator_without_voting_power() {
+    let mut s = TestWlStorage::default();
+    // 1 voting power per whole token
+    let params = PosParams {
+        tm_votes_per_token: dec!(0.000001),
+        ..Default::default()
+    };
+    let val1 = address::testing::established_address_1();
+    let val2 = address::testing::established_address_2();
+    let pk2 = key::testing::keypair_2().to_public();
+    let mut current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[
+            (val1, token::Amount::whole(10)),
+            (val2.clone(), token::Amount::whole(2)),
+        ],
+    );
+    s.commit_block().unwrap();
+
+    // Leave the validator with less than a whole token, which maps to no
+    // voting power, while it stays in the consensus set
+    let amount = token::Amount::from(1_500_000);
+    unbond_tokens(&mut s, None, &val2, amount, current_epoch).unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let consensus_vals =
+        read_consensus_validator_set_addresses_with_stake(&s, pipeline_epoch)
+            .unwrap();
+    assert!(consensus_vals.iter().any(|v| v.address == val2));
+
+    // Tendermint gets a deactivation of the validator when its stake update
+    // takes effect
+    for _ in 0..params.pipeline_len {
+        current_epoch = advance_epoch(&mut s, &params);
+    }
+    let tm_updates = get_tendermint_set_updates(&s, &params, current_epoch);
+    assert_eq!(tm_updates, vec![ValidatorSetUpdate::Deactivated(pk2)]);
+
+    // There's no further update while the validator has no voting power
+    let tm_updates =
+        get_tendermint_set_updates(&s, &params, current_epoch.next());
+    assert!(tm_updates.is_empty());
+}
+
+#[test]
+fn test_save_rewards_accumulator_snapshot() {
+    let mut s = TestWlStorage::default();
+    let epoch = Epoch(3);
+    let val1 = address::testing::established_address_1();
+    let val2 = address::testing::established_address_2();
+    let accumulator = rewards_accumulator_handle();
+    accumulator
+        .insert(&mut s, val1.clone(), dec!(0.25))
+        .unwrap();
+    accumulator
+        .insert(&mut s, val2.clone(), dec!(0.75))
+        .unwrap();
+
+    save_rewards_accumulator_snapshot(&mut s, epoch).unwrap();
+    reset_rewards_accumulator(&mut s).unwrap();
+    assert!(accumulator.is_empty(&s).unwrap());
+
+    // The snapshot keeps the values of the given epoch
+    let snapshot = epoched_rewards_snapshot_handle().at(&epoch);
+    assert_eq!(snapshot.get(&s, &val1).unwrap(), Some(dec!(0.25)));
+    assert_eq!(snapshot.get(&s, &val2).unwrap(), Some(dec!(0.75)));
+    let other_snapshot = epoched_rewards_snapshot_handle().at(&epoch.next());
+    assert!(other_snapshot.is_empty(&s).unwrap());
+}
+
+#[test]
+fn test_record_block_votes() {
+    let mut s = TestWlStorage::default();
+    let epoch = Epoch(3);
+    let height = 42_u64;
+    let votes = vec![
+        VoteInfo {
+            validator_address: address::testing::established_address_1(),
+            validator_vp: 10,
+        },
+        VoteInfo {
+            validator_address: address::testing::established_address_2(),
+            validator_vp: 5,
+        },
+    ];
+
+    record_block_votes(&mut s, epoch, height, &votes).unwrap();
+    assert_eq!(read_block_votes(&s, epoch, height).unwrap(), Some(votes));
+
+    // The votes are only recorded for the given block
+    assert_eq!(read_block_votes(&s, epoch, height + 1).unwrap(), None);
+    assert_eq!(read_block_votes(&s, epoch.next(), height).unwrap(), None);
+}
+
+#[test]
+fn test_prune_epoch_votes() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let votes = vec![VoteInfo {
+        validator_address: address::testing::established_address_1(),
+        validator_vp: 10,
+    }];
+    let current_epoch = Epoch(params.unbonding_len + 2);
+    let keep_from_epoch = Epoch(2);
+    for epoch in 0..=current_epoch.0 {
+        record_block_votes(&mut s, Epoch(epoch), epoch * 10, &votes).unwrap();
+    }
+
+    prune_epoch_votes(&mut s, &params, current_epoch).unwrap();
+
+    // Only the votes within the unbonding length are kept
+    for epoch in 0..=current_epoch.0 {
+        let epoch_votes =
+            read_block_votes(&s, Epoch(epoch), epoch * 10).unwrap();
+        if Epoch(epoch) < keep_from_epoch {
+            assert_eq!(epoch_votes, None);
+        } else {
+            assert_eq!(epoch_votes, Some(votes.clone()));
+        }
+    }
+}
+
+#[test]
+fn test_slash_records_stake_at_infraction() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let tokens = token::Amount::whole(10);
+    let current_epoch =
+        init_test_pos(&mut s, &params, &[(validator.clone(), tokens)]);
+
+    for _ in 0..2 {
+        slash(
+            &mut s,
+            &params,
+            current_epoch,
+            current_epoch,
+            0_u64,
+            SlashType::DuplicateVote,
+            &validator,
+        )
+        .unwrap();
+    }
+
+    // The stake is recorded under the index of each slash
+    let stakes = validator_slashes_stake_handle(&validator);
+    assert_eq!(validator_slashes_handle(&validator).len(&s).unwrap(), 2);
+    assert_eq!(stakes.get(&s, &0).unwrap(), Some(tokens));
+    assert_eq!(stakes.get(&s, &1).unwrap(), Some(tokens));
+}
+
+#[test]
+fn test_slash_below_capacity_validator() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams {
+        max_validator_slots: 1,
+        ..Default::default()
+    };
+    let consensus_validator = address::testing::established_address_1();
+    let below_capacity_validator = address::testing::established_address_2();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[
+            (consensus_validator, token::Amount::whole(20)),
+            (below_capacity_validator.clone(), token::Amount::whole(10)),
+        ],
+    );
+
+    // A validator that was not in the consensus set cannot be slashed
+    let res = slash(
+        &mut s,
+        &params,
+        current_epoch,
+        current_epoch,
+        0_u64,
+        SlashType::DuplicateVote,
+        &below_capacity_validator,
+    );
+    assert!(res.is_err());
+    let slashes = validator_slashes_handle(&below_capacity_validator);
+    assert!(slashes.is_empty(&s).unwrap());
+}
+
+#[test]
+fn test_commission_rate_change_announcement() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams {
+        commission_change_notice_epochs: 2,
+        ..Default::default()
+    };
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    let new_rate = Decimal::new(15, 2);
+
+    // A rate larger than 1 cannot be announced
+    let res = announce_validator_commission_rate_change(
+        &mut s,
+        &validator,
+        Decimal::new(11, 1),
+        current_epoch,
+    );
+    assert!(res.is_err());
+
+    // The change must be announced first
+    let res = change_validator_commission_rate(
+        &mut s,
+        &validator,
+        new_rate,
+        current_epoch,
+    );
+    assert!(res.is_err());
+    announce_validator_commission_rate_change(
+        &mut s,
+        &validator,
+        new_rate,
+        current_epoch,
+    )
+    .unwrap();
+
+    // The change is not allowed before the notice period has passed
+    let early_epoch =
+        current_epoch + params.commission_change_notice_epochs - 1;
+    let res = change_validator_commission_rate(
+        &mut s,
+        &validator,
+        new_rate,
+        early_epoch,
+    );
+    assert!(res.is_err());
+
+    // Only the announced rate can be set
+    let allowed_epoch = current_epoch + params.commission_change_notice_epochs;
+    let res = change_validator_commission_rate(
+        &mut s,
+        &validator,
+        Decimal::new(2, 1),
+        allowed_epoch,
+    );
+    assert!(res.is_err());
+    change_validator_commission_rate(
+        &mut s,
+        &validator,
+        new_rate,
+        allowed_epoch,
+    )
+    .unwrap();
+    let pipeline_epoch = allowed_epoch + params.pipeline_len;
+    let rate = validator_commission_rate_handle(&validator)
+        .get(&s, pipeline_epoch, &params)
+        .unwrap();
+    assert_eq!(rate, Some(new_rate));
+
+    // The announcement is used up by the change
+    assert_eq!(
+        read_validator_commission_rate_announcement(&s, &validator).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_multisig_bond() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    s.commit_block().unwrap();
+
+    let staking_token = staking_token_address(&s);
+    let owner = address::testing::established_address_2();
+    let co_signer = address::testing::established_address_3();
+    let amount = token::Amount::whole(1);
+    credit_tokens(&mut s, &staking_token, &owner, amount * 2).unwrap();
+    let bond = MultisigBond {
+        sources: vec![owner.clone(), co_signer.clone()],
+        threshold: 2,
+        validator: validator.clone(),
+        amount,
+    };
+
+    // The threshold cannot be higher than the number of sources
+    let invalid_bond = MultisigBond {
+        threshold: 3,
+        ..bond.clone()
+    };
+    assert!(submit_multisig_bond(&mut s, invalid_bond, current_epoch).is_err());
+
+    // The tokens are held by PoS until the bond is committed
+    let id = submit_multisig_bond(&mut s, bond.clone(), current_epoch).unwrap();
+    assert_eq!(read_balance(&s, &staking_token, &owner).unwrap(), amount);
+    let bonds = bond_handle(&owner, &validator);
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(bonds.get_sum(&s, pipeline_epoch, &params).unwrap(), None);
+
+    // Only the sources may approve the bond, and only once. The submission
+    // is the owner's approval.
+    assert!(
+        approve_multisig_bond(&mut s, id, &validator, current_epoch).is_err()
+    );
+    assert!(approve_multisig_bond(&mut s, id, &owner, current_epoch).is_err());
+
+    assert!(
+        approve_multisig_bond(&mut s, id, &co_signer, current_epoch).unwrap()
+    );
+    assert_eq!(
+        bonds.get_sum(&s, pipeline_epoch, &params).unwrap(),
+        Some(amount.change())
+    );
+    assert!(pending_multisig_bonds_handle().is_empty(&s).unwrap());
+
+    // A bond that isn't approved in time gets refunded
+    let id = submit_multisig_bond(&mut s, bond.clone(), current_epoch).unwrap();
+    let expired_epoch = current_epoch + params.multisig_timeout_epochs + 1;
+    assert!(
+        approve_multisig_bond(&mut s, id, &co_signer, expired_epoch).is_err()
+    );
+    remove_expired_multisig_bonds(&mut s, current_epoch).unwrap();
+    assert_eq!(
+        read_balance(&s, &staking_token, &owner).unwrap(),
+        token::Amount::default()
+    );
+    remove_expired_multisig_bonds(&mut s, expired_epoch).unwrap();
+    assert_eq!(read_balance(&s, &staking_token, &owner).unwrap(), amount);
+    assert!(pending_multisig_bonds_handle().is_empty(&s).unwrap());
+
+    // A bond with a threshold of 1 is committed right away
+    let single_bond = MultisigBond {
+        threshold: 1,
+        ..bond
+    };
+    submit_multisig_bond(&mut s, single_bond, current_epoch).unwrap();
+    assert_eq!(
+        bonds.get_sum(&s, pipeline_epoch, &params).unwrap(),
+        Some((amount * 2).change())
+    );
+    assert!(pending_multisig_bonds_handle().is_empty(&s).unwrap());
+}
+
+#[test]
+fn test_below_capacity_validator_set_order() {
+    let mut s = TestWlStorage::default();
+    let epoch = Epoch::default();
+    let below_capacity = below_capacity_validator_set_handle().at(&epoch);
+    let val1 = address::testing::established_address_1();
+    let val2 = address::testing::established_address_2();
+    let val3 = address::testing::established_address_3();
+    let low_stake = token::Amount::whole(1);
+    let high_stake = token::Amount::whole(2);
+
+    // An empty set has no maximum amount
+    assert_eq!(
+        get_max_below_capacity_validator_amount(&below_capacity, &s).unwrap(),
+        token::Amount::default()
+    );
+
+    // Exactly one below-capacity validator
+    let low_tier = below_capacity.at(&low_stake.into());
+    insert_validator_into_set(&low_tier, &mut s, &epoch, &val1).unwrap();
+    assert_eq!(
+        get_max_below_capacity_validator_amount(&below_capacity, &s).unwrap(),
+        low_stake
+    );
+    assert_eq!(
+        find_first_position(&low_tier, &s).unwrap(),
+        Some(Position(0))
+    );
+    assert_eq!(
+        find_last_position(&low_tier, &s).unwrap(),
+        Some(Position(0))
+    );
+
+    // The highest stake comes first, regardless of the insertion order
+    let high_tier = below_capacity.at(&high_stake.into());
+    insert_validator_into_set(&high_tier, &mut s, &epoch, &val2).unwrap();
+    insert_validator_into_set(&low_tier, &mut s, &epoch, &val3).unwrap();
+    assert_eq!(
+        get_max_below_capacity_validator_amount(&below_capacity, &s).unwrap(),
+        high_stake
+    );
+    assert_eq!(
+        find_first_position(&low_tier, &s).unwrap(),
+        Some(Position(0))
+    );
+    assert_eq!(
+        find_last_position(&low_tier, &s).unwrap(),
+        Some(Position(1))
+    );
+    let first_low = low_tier.get(&s, &Position(0)).unwrap();
+    assert_eq!(first_low, Some(val1));
+}
+
+#[test]
+fn test_preview_next_epoch_consensus_set() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams {
+        max_validator_slots: 1,
+        ..Default::default()
+    };
+    let current_epoch = Epoch::default();
+    let next_epoch = current_epoch.next();
+    let val1 = address::testing::established_address_1();
+    let val2 = address::testing::established_address_2();
+    let stake = token::Amount::whole(1);
+
+    // `val1` is in the consensus set and `val2` is below capacity in the
+    // current epoch, but `val2` has a pending bond for the next epoch
+    let consensus = consensus_validator_set_handle().at(&current_epoch);
+    insert_validator_into_set(
+        &consensus.at(&stake),
+        &mut s,
+        &current_epoch,
+        &val1,
+    )
+    .unwrap();
+    let below_capacity =
+        below_capacity_validator_set_handle().at(&current_epoch);
+    insert_validator_into_set(
+        &below_capacity.at(&stake.into()),
+        &mut s,
+        &current_epoch,
+        &val2,
+    )
+    .unwrap();
+    validator_deltas_handle(&val1)
+        .set(&mut s, stake.change(), current_epoch, 0)
+        .unwrap();
+    validator_deltas_handle(&val2)
+        .set(&mut s, stake.change(), current_epoch, 0)
+        .unwrap();
+    validator_deltas_handle(&val2)
+        .set(&mut s, stake.change(), current_epoch, 1)
+        .unwrap();
+
+    // The next epoch's set is not populated, so it's computed from the stake
+    let preview =
+        preview_next_epoch_consensus_set(&s, &params, current_epoch).unwrap();
+    let expected = HashSet::from([WeightedValidator {
+        bonded_stake: token::Amount::whole(2),
+        address: val2.clone(),
+    }]);
+    assert_eq!(preview, expected);
+
+    // Once the next epoch's set is populated, it's read from storage
+    let next_consensus = consensus_validator_set_handle().at(&next_epoch);
+    insert_validator_into_set(
+        &next_consensus.at(&stake),
+        &mut s,
+        &next_epoch,
+        &val1,
+    )
+    .unwrap();
+    let preview =
+        preview_next_epoch_consensus_set(&s, &params, current_epoch).unwrap();
+    let expected = HashSet::from([WeightedValidator {
+        bonded_stake: stake,
+        address: val1,
+    }]);
+    assert_eq!(preview, expected);
+}
+
+#[test]
+fn test_get_sum_after_slashing() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams {
+        duplicate_vote_min_slash_rate: dec!(0.1),
+        ..Default::default()
+    };
+    let pipeline_epoch = Epoch(params.pipeline_len);
+    let deltas = total_deltas_handle();
+    deltas.set(&mut s, 100, Epoch(0), 0).unwrap();
+    deltas
+        .set(&mut s, 50, Epoch(0), params.pipeline_len)
+        .unwrap();
+    let slashes = vec![Slash {
+        epoch: Epoch(1),
+        block_height: 0,
+        r#type: SlashType::DuplicateVote,
+    }];
+
+    // The slash doesn't affect the sum before its epoch
+    let sum = deltas
+        .get_sum_after_slashing(&s, Epoch(0), &params, &slashes)
+        .unwrap();
+    assert_eq!(sum, 100);
+    // Only the stake bonded at or before the slash epoch is slashed
+    let sum = deltas
+        .get_sum_after_slashing(&s, pipeline_epoch, &params, &slashes)
+        .unwrap();
+    assert_eq!(sum, 100 - 10 + 50);
+    // Without slashes, the result matches the nominal sum
+    let sum = deltas
+        .get_sum_after_slashing(&s, pipeline_epoch, &params, &[])
+        .unwrap();
+    let nominal = deltas.get_sum(&s, pipeline_epoch, &params).unwrap();
+    assert_eq!(Some(sum), nominal);
+}
+
+#[test]
+fn test_prune_before_epoch() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let deltas = total_deltas_handle();
+    deltas.set(&mut s, 100, Epoch(0), 0).unwrap();
+    deltas.set(&mut s, 20, Epoch(0), 1).unwrap();
+    deltas.set(&mut s, -30, Epoch(0), 2).unwrap();
+    deltas.set(&mut s, 5, Epoch(0), 3).unwrap();
+    let sums_before = (0..4)
+        .map(|ep| deltas.get_sum(&s, Epoch(ep), &params).unwrap())
+        .collect::<Vec<_>>();
+
+    deltas.prune_before_epoch(&mut s, Epoch(2)).unwrap();
+
+    // The pruned deltas are folded into the value at the kept epoch
+    let data = deltas.to_hashmap(&s).unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data.get(&Epoch(2)), Some(&90));
+    assert_eq!(data.get(&Epoch(3)), Some(&5));
+    for ep in 2..4 {
+        assert_eq!(
+            deltas.get_sum(&s, Epoch(ep), &params).unwrap(),
+            sums_before[ep as usize]
+        );
+    }
+}
+
+#[test]
+fn test_find_bonds_above() {
+    let mut s = TestWlStorage::default();
+    let source = address::testing::established_address_1();
+    let validator = address::testing::established_address_2();
+    let bonds = bond_handle(&source, &validator);
+    bonds.set(&mut s, 1_000, Epoch(0), 0).unwrap();
+    bonds.set(&mut s, 2, Epoch(0), 1).unwrap();
+    bonds.set(&mut s, -500, Epoch(0), 2).unwrap();
+    bonds.set(&mut s, -1, Epoch(0), 3).unwrap();
+
+    let all = find_bonds(&s, &source, &validator).unwrap();
+    assert_eq!(all.len(), 4);
+    let above = find_bonds_above(&s, &source, &validator, 10).unwrap();
+    assert_eq!(
+        above.into_iter().collect::<Vec<_>>(),
+        vec![(Epoch(0), 1_000), (Epoch(2), -500)]
+    );
+}
+
+#[test]
+fn test_find_delegations() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    s.commit_block().unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+
+    // The genesis self-bond is indexed
+    let delegations = find_delegations(&s, &validator, &current_epoch).unwrap();
+    assert_eq!(
+        delegations,
+        HashMap::from([(validator.clone(), token::Amount::whole(10))])
+    );
+
+    // A new delegation is indexed
+    let staking_token = staking_token_address(&s);
+    let delegator = address::testing::established_address_2();
+    let amount = token::Amount::whole(1);
+    credit_tokens(&mut s, &staking_token, &delegator, amount).unwrap();
+    bond_tokens(&mut s, Some(&delegator), &validator, amount, current_epoch)
+        .unwrap();
+    let delegations =
+        find_delegations(&s, &delegator, &pipeline_epoch).unwrap();
+    assert_eq!(delegations, HashMap::from([(validator.clone(), amount)]));
+
+    // A partially unbonded delegation stays indexed
+    let unbond_amount = token::Amount::from(100_000);
+    unbond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        unbond_amount,
+        current_epoch,
+    )
+    .unwrap();
+    let delegations =
+        find_delegations(&s, &delegator, &pipeline_epoch).unwrap();
+    assert_eq!(
+        delegations,
+        HashMap::from([(validator.clone(), amount - unbond_amount)])
+    );
+
+    // A fully unbonded delegation stays indexed until the pipeline epoch
+    unbond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        amount - unbond_amount,
+        current_epoch,
+    )
+    .unwrap();
+    let delegations = find_delegations(&s, &delegator, &current_epoch).unwrap();
+    assert!(delegations.contains_key(&validator));
+    prune_delegator_validator_index(&mut s, current_epoch).unwrap();
+    let delegations = find_delegations(&s, &delegator, &current_epoch).unwrap();
+    assert!(delegations.contains_key(&validator));
+
+    // It's removed from the index at the start of the pipeline epoch
+    prune_delegator_validator_index(&mut s, pipeline_epoch).unwrap();
+    let delegations =
+        find_delegations(&s, &delegator, &pipeline_epoch).unwrap();
+    assert!(delegations.is_empty());
+    let index_key = delegator_validator_index_key(&delegator, &validator);
+    assert!(!s.has_key(&index_key).unwrap());
+}
+
+#[test]
+fn test_migrate_delegator_validator_index() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    s.commit_block().unwrap();
+
+    // Drop the index to simulate a chain that started without it
+    let index_key = delegator_validator_index_key(&validator, &validator);
+    let migrated_key = delegator_validator_index_migrated_key();
+    s.delete(&index_key).unwrap();
+    s.delete(&migrated_key).unwrap();
+
+    // The bonds are found from their keys until they're migrated
+    let delegations = find_delegations(&s, &validator, &current_epoch).unwrap();
+    assert_eq!(
+        delegations,
+        HashMap::from([(validator.clone(), token::Amount::whole(10))])
+    );
+
+    migrate_delegator_validator_index(&mut s).unwrap();
+    assert!(s.has_key(&index_key).unwrap());
+    assert!(s.has_key(&migrated_key).unwrap());
+    let delegations = find_delegations(&s, &validator, &current_epoch).unwrap();
+    assert_eq!(
+        delegations,
+        HashMap::from([(validator, token::Amount::whole(10))])
+    );
+}
+
+#[test]
+fn test_compute_annual_staking_yield() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    s.write(&get_epochs_per_year_key(), 100_u64).unwrap();
+
+    // The self-bond receives 1% of rewards in each of the first two epochs
+    let products = validator_rewards_products_handle(&validator);
+    products.insert(&mut s, Epoch(0), dec!(1.01)).unwrap();
+    products.insert(&mut s, Epoch(1), dec!(1.01)).unwrap();
+
+    let apy = compute_annual_staking_yield(
+        &s,
+        &params,
+        &validator,
+        &validator,
+        Epoch(2),
+        2,
+    )
+    .unwrap();
+    assert_eq!(apy, dec!(1));
+
+    // The delegations' growth factors compound over the epoch range
+    let delegator_products = delegator_rewards_products_handle(&validator);
+    delegator_products
+        .insert(&mut s, Epoch(0), dec!(1.1))
+        .unwrap();
+    delegator_products
+        .insert(&mut s, Epoch(2), dec!(1.2))
+        .unwrap();
+    let compounded =
+        compute_compounded_rewards_product(&s, &validator, Epoch(0), Epoch(2))
+            .unwrap();
+    assert_eq!(compounded, dec!(1.32));
+    let compounded =
+        compute_compounded_rewards_product(&s, &validator, Epoch(1), Epoch(1))
+            .unwrap();
+    assert_eq!(compounded, Decimal::ONE);
+
+    // An empty window has no yield
+    let apy = compute_annual_staking_yield(
+        &s,
+        &params,
+        &validator,
+        &validator,
+        Epoch(2),
+        0,
+    )
+    .unwrap();
+    assert_eq!(apy, Decimal::ZERO);
+
+    // A source without any bond has no yield
+    let delegator = address::testing::established_address_2();
+    let apy = compute_annual_staking_yield(
+        &s,
+        &params,
+        &delegator,
+        &validator,
+        Epoch(2),
+        2,
+    )
+    .unwrap();
+    assert_eq!(apy, Decimal::ZERO);
+}
+
+#[test]
+fn test_is_delegator() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    s.commit_block().unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+
+    // A self-bond doesn't make the validator a delegator
+    assert!(!is_delegator(&s, &validator, Some(pipeline_epoch)).unwrap());
+
+    // A delegation only counts from the epoch at which it takes effect
+    let staking_token = staking_token_address(&s);
+    let delegator = address::testing::established_address_2();
+    let amount = token::Amount::whole(1);
+    credit_tokens(&mut s, &staking_token, &delegator, amount).unwrap();
+    bond_tokens(&mut s, Some(&delegator), &validator, amount, current_epoch)
+        .unwrap();
+    let is_delegator_before =
+        is_delegator(&s, &delegator, Some(pipeline_epoch.prev())).unwrap();
+    assert!(!is_delegator_before);
+    assert!(is_delegator(&s, &delegator, Some(pipeline_epoch)).unwrap());
+    assert!(is_delegator(&s, &delegator, Some(pipeline_epoch.next())).unwrap());
+    let bonds = bond_handle(&delegator, &validator);
+    assert_eq!(bonds.count(&s).unwrap(), 1);
+    let bonds_in_range = find_bonds_in_epoch_range(
+        &s,
+        &validator,
+        current_epoch.next(),
+        pipeline_epoch,
+    )
+    .unwrap();
+    assert_eq!(
+        bonds_in_range,
+        vec![(delegator.clone(), pipeline_epoch, amount.change())]
+    );
+
+    // A partial unbond leaves the delegator with a non-zero bond
+    let unbond_amount = token::Amount::from(100_000);
+    unbond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        unbond_amount,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(is_delegator(&s, &delegator, Some(pipeline_epoch)).unwrap());
+    let unbonds = unbond_handle(&delegator, &validator);
+    assert_eq!(unbonds.count(&s).unwrap(), 1);
+
+    // After unbonding the rest, the delegator has no bonded tokens as of the
+    // pipeline epoch
+    unbond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        amount - unbond_amount,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(!is_delegator(&s, &delegator, Some(pipeline_epoch)).unwrap());
+}
+
+#[test]
+fn test_bonds_summary() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    s.commit_block().unwrap();
+
+    // Only the genesis self-bond is active
+    assert_eq!(count_total_active_bonds(&s).unwrap(), 1);
+    assert_eq!(count_unique_delegators(&s).unwrap(), 0);
+
+    // Two delegations from one delegator and one from another
+    let staking_token = staking_token_address(&s);
+    let delegator_1 = address::testing::established_address_2();
+    let delegator_2 = address::testing::established_address_3();
+    let amount = token::Amount::whole(1);
+    credit_tokens(&mut s, &staking_token, &delegator_1, amount * 2).unwrap();
+    credit_tokens(&mut s, &staking_token, &delegator_2, amount).unwrap();
+    bond_tokens(
+        &mut s,
+        Some(&delegator_1),
+        &validator,
+        amount,
+        current_epoch,
+    )
+    .unwrap();
+    bond_tokens(
+        &mut s,
+        Some(&delegator_1),
+        &validator,
+        amount,
+        current_epoch.next(),
+    )
+    .unwrap();
+    bond_tokens(
+        &mut s,
+        Some(&delegator_2),
+        &validator,
+        amount,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(count_total_active_bonds(&s).unwrap(), 3);
+    assert_eq!(count_unique_delegators(&s).unwrap(), 2);
+
+    // A fully unbonded delegation is no longer counted
+    unbond_tokens(
+        &mut s,
+        Some(&delegator_2),
+        &validator,
+        amount,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(count_total_active_bonds(&s).unwrap(), 2);
+    assert_eq!(count_unique_delegators(&s).unwrap(), 1);
+
+    // The summary is only written when it's updated
+    assert_eq!(read_bonds_summary(&s).unwrap(), None);
+    update_bonds_summary(&mut s, current_epoch).unwrap();
+    assert_eq!(
+        read_bonds_summary(&s).unwrap(),
+        Some(BondsSummary {
+            epoch: current_epoch,
+            active_bonds: 2,
+            unique_delegators: 1,
+        })
+    );
+}
+
+#[test]
+fn test_beacon_epochs() {
+    assert!(is_beacon_epoch(Epoch(0), 10));
+    assert!(is_beacon_epoch(Epoch(20), 10));
+    assert!(!is_beacon_epoch(Epoch(5), 10));
+    assert!(!is_beacon_epoch(Epoch(0), 0));
+
+    assert_eq!(next_beacon_epoch(Epoch(0), 10), Epoch(10));
+    assert_eq!(next_beacon_epoch(Epoch(5), 10), Epoch(10));
+    assert_eq!(next_beacon_epoch(Epoch(10), 10), Epoch(20));
+    assert_eq!(next_beacon_epoch(Epoch(7), 1), Epoch(8));
+}
+
+#[test]
+fn test_write_beacon_snapshot() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams {
+        beacon_epoch_interval: 2,
+        ..Default::default()
+    };
+    let val1 = address::testing::established_address_1();
+    let val2 = address::testing::established_address_2();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[
+            (val1, token::Amount::whole(10)),
+            (val2.clone(), token::Amount::whole(20)),
+        ],
+    );
+    // A different commission rate for the second validator
+    validator_commission_rate_handle(&val2)
+        .set(&mut s, Decimal::new(3, 1), current_epoch, 0)
+        .unwrap();
+
+    // The genesis epoch is a beacon epoch
+    write_beacon_snapshot(&mut s, &params, current_epoch).unwrap();
+    assert_eq!(
+        read_beacon_snapshot(&s, current_epoch).unwrap(),
+        Some(BeaconSnapshot {
+            total_stake: token::Amount::whole(30),
+            num_validators: 2,
+            avg_commission_rate: Decimal::new(2, 1),
+        })
+    );
+
+    // No snapshot is taken in other epochs
+    let epoch = current_epoch.next();
+    write_beacon_snapshot(&mut s, &params, epoch).unwrap();
+    assert_eq!(read_beacon_snapshot(&s, epoch).unwrap(), None);
+
+    // Until the next beacon epoch
+    let epoch = next_beacon_epoch(epoch, params.beacon_epoch_interval);
+    write_beacon_snapshot(&mut s, &params, epoch).unwrap();
+    assert!(read_beacon_snapshot(&s, epoch).unwrap().is_some());
+}
+
+#[test]
+fn test_total_unbonding_at_epoch() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(10))],
+    );
+    s.commit_block().unwrap();
+
+    // Unbond from a self-bond and from a delegation in the same epoch
+    let staking_token = staking_token_address(&s);
+    let delegator = address::testing::established_address_2();
+    let amount = token::Amount::whole(3);
+    credit_tokens(&mut s, &staking_token, &delegator, amount).unwrap();
+    bond_tokens(&mut s, Some(&delegator), &validator, amount, current_epoch)
+        .unwrap();
+    unbond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        token::Amount::whole(1),
+        current_epoch,
+    )
+    .unwrap();
+    unbond_tokens(
+        &mut s,
+        None,
+        &validator,
+        token::Amount::whole(2),
+        current_epoch,
+    )
+    .unwrap();
+
+    // And from the self-bond again in the next epoch
+    let next_epoch = current_epoch.next();
+    unbond_tokens(
+        &mut s,
+        None,
+        &validator,
+        token::Amount::whole(1),
+        next_epoch,
+    )
+    .unwrap();
+
+    let withdrawable_epoch =
+        current_epoch + params.pipeline_len + params.unbonding_len;
+    assert_eq!(
+        compute_total_unbonding_at_epoch(&s, withdrawable_epoch).unwrap(),
+        token::Amount::whole(3)
+    );
+    assert_eq!(
+        compute_total_unbonding_at_epoch(&s, withdrawable_epoch.next())
+            .unwrap(),
+        token::Amount::whole(1)
+    );
+    let total = compute_total_unbonding_at_epoch(&s, withdrawable_epoch.prev())
+        .unwrap();
+    assert!(total.is_zero());
+
+    // At the start of the next epoch, the total of the epoch that can no
+    // longer receive new unbonds is cached
+    assert_eq!(
+        read_total_unbonding_at_epoch(&s, withdrawable_epoch).unwrap(),
+        None
+    );
+    update_total_unbonding(&mut s, &params, next_epoch).unwrap();
+    assert_eq!(
+        read_total_unbonding_at_epoch(&s, withdrawable_epoch).unwrap(),
+        Some(token::Amount::whole(3))
+    );
+}
+
+#[test]
+fn test_delegator_reward_share() {
+    let mut s = TestWlStorage::default();
+    let params = PosParams::default();
+    let validator = address::testing::established_address_1();
+    let current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[(validator.clone(), token::Amount::whole(9))],
+    );
+    let commission_rate = validator_commission_rate_handle(&validator)
+        .get(&s, current_epoch, &params)
+        .unwrap()
+        .unwrap();
+    s.commit_block().unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+
+    let staking_token = staking_token_address(&s);
+    let delegator = address::testing::established_address_2();
+    let amount = token::Amount::whole(1);
+    credit_tokens(&mut s, &staking_token, &delegator, amount).unwrap();
+    bond_tokens(&mut s, Some(&delegator), &validator, amount, current_epoch)
+        .unwrap();
+
+    // Before the delegation takes effect, there's no share and commission
+    let share = delegator_reward_share(
+        &s,
+        &params,
+        &delegator,
+        &validator,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(share, Decimal::ZERO);
+    let commission =
+        validator_commission_earned(&s, &params, &validator, current_epoch)
+            .unwrap();
+    assert_eq!(commission, Decimal::ZERO);
+
+    // The delegation holds a tenth of the validator's stake
+    let share = delegator_reward_share(
+        &s,
+        &params,
+        &delegator,
+        &validator,
+        pipeline_epoch,
+    )
+    .unwrap();
+    assert_eq!(share, dec!(0.1) * (Decimal::ONE - commission_rate));
+    let commission =
+        validator_commission_earned(&s, &params, &validator, pipeline_epoch)
+            .unwrap();
+    assert_eq!(commission, dec!(0.1) * commission_rate);
+
+    // The validator's self-bond is not charged the commission
+    let self_share = delegator_reward_share(
+        &s,
+        &params,
+        &validator,
+        &validator,
+        pipeline_epoch,
+    )
+    .unwrap();
+    assert_eq!(self_share, dec!(0.9));
+
+    // After a slash, only the self-bond is slashed, since the delegation was
+    // bonded after the infraction epoch
+    slash(
+        &mut s,
+        &params,
+        current_epoch,
+        current_epoch,
+        0_u64,
+        SlashType::DuplicateVote,
+        &validator,
+    )
+    .unwrap();
+    let total_stake = dec!(9.991);
+    let self_share = delegator_reward_share(
+        &s,
+        &params,
+        &validator,
+        &validator,
+        pipeline_epoch,
+    )
+    .unwrap();
+    assert_eq!(self_share, dec!(8.991) / total_stake);
+    let share = delegator_reward_share(
+        &s,
+        &params,
+        &delegator,
+        &validator,
+        pipeline_epoch,
+    )
+    .unwrap();
+    assert_eq!(
+        share,
+        Decimal::ONE / total_stake * (Decimal::ONE - commission_rate)
+    );
+    assert!(self_share + share <= Decimal::ONE);
+}
+
+#[test]
+fn test_simulate_epoch_transition() {
+    let mut s = TestWlStorage::default();
+    // Only 1 consensus validator slot
+    let params = PosParams {
+        max_validator_slots: 1,
+        ..Default::default()
+    };
+    let val1 = address::testing::established_address_1();
+    let val2 = address::testing::established_address_2();
+    let mut current_epoch = init_test_pos(
+        &mut s,
+        &params,
+        &[
+            (val1.clone(), token::Amount::whole(10)),
+            (val2.clone(), token::Amount::whole(1)),
+        ],
+    );
+    s.commit_block().unwrap();
+
+    // A delegation makes the below-capacity validator overtake the consensus
+    // validator at the pipeline offset
+    let staking_token = staking_token_address(&s);
+    let delegator = address::testing::established_address_3();
+    let amount = token::Amount::whole(20);
+    credit_tokens(&mut s, &staking_token, &delegator, amount).unwrap();
+    bond_tokens(&mut s, Some(&delegator), &val2, amount, current_epoch)
+        .unwrap();
+
+    // Nothing changes in the next epoch yet
+    let result = simulate_epoch_transition(&s, current_epoch).unwrap();
+    assert!(result.promotions.is_empty());
+    assert!(result.demotions.is_empty());
+    let new_consensus_set: Vec<_> =
+        result.new_consensus_set.into_iter().collect();
+    assert_eq!(
+        new_consensus_set,
+        vec![WeightedValidator {
+            bonded_stake: token::Amount::whole(10),
+            address: val1.clone(),
+        }]
+    );
+
+    // The validators swap with the transition to the pipeline epoch
+    for _ in 0..params.pipeline_len - 1 {
+        current_epoch = advance_epoch(&mut s, &params);
+    }
+    let result = simulate_epoch_transition(&s, current_epoch).unwrap();
+    assert_eq!(
+        result.promotions.into_iter().collect::<Vec<_>>(),
+        vec![val2.clone()]
+    );
+    assert_eq!(result.demotions.into_iter().collect::<Vec<_>>(), vec![val1]);
+    let new_consensus_set: Vec<_> =
+        result.new_consensus_set.into_iter().collect();
+    assert_eq!(
+        new_consensus_set,
+        vec![WeightedValidator {
+            bonded_stake: token::Amount::whole(21),
+            address: val2,
+        }]
+    );
+}
+
+/// Test that the validator sets cannot be copied into epoch 0, which has no
+/// previous epoch to copy them from
+#[test]
+fn test_copy_validator_sets_into_epoch_0() {
+    let mut s = TestWlStorage::default();
+    let result = copy_validator_sets_and_positions(
+        &mut s,
+        Epoch(0),
+        Epoch(0),
+        &consensus_validator_set_handle(),
+        &below_capacity_validator_set_handle(),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_validator_sets() {
+    let mut s = TestWlStorage::default();
+    // Only 3 consensus validator slots
+    let params = PosParams {
+        max_validator_slots: 3,
+        ..Default::default()
+    };
+    let addr_seed = "seed";
+    let mut address_gen = EstablishedAddressGen::new(addr_seed);
+    let mut sk_seed = 0;
+    let mut gen_validator = || {
+        let res = (
+            address_gen.generate_address(addr_seed),
+            key::testing::common_sk_from_simple_seed(sk_seed).to_public(),
+        );
+        // bump the sk seed
+        sk_seed += 1;
+        res
+    };
+
+    // A helper to insert a non-genesis validator
+    let insert_validator = |s: &mut TestWlStorage,
+                            addr,
+                            pk: &PublicKey,
+                            stake: token::Amount,
+                            epoch: Epoch| {
+        insert_validator_into_validator_set(
+            s,
+            &params,
+            addr,
+            stake,
+            epoch,
+            params.pipeline_len,
+        )
+        .unwrap();
+
+        update_validator_deltas(s, &params, addr, stake.change(), epoch)
+            .unwrap();
+
+        // Set their consensus key (needed for
+        // `validator_set_update_tendermint` fn)
+        validator_consensus_key_handle(addr)
+            .set(s, pk.clone(), epoch, params.pipeline_len)
+            .unwrap();
+    };
+
+    // Start with two genesis validators with 1 NAM stake
+    let epoch = Epoch::default();
+    let ((val1, pk1), stake1) = (gen_validator(), token::Amount::whole(1));
+    let ((val2, pk2), stake2) = (gen_validator(), token::Amount::whole(1));
+    let ((val3, pk3), stake3) = (gen_validator(), token::Amount::whole(10));
+    let ((val4, pk4), stake4) = (gen_validator(), token::Amount::whole(1));
+    let ((val5, pk5), stake5) = (gen_validator(), token::Amount::whole(100));
+    let ((val6, pk6), stake6) = (gen_validator(), token::Amount::whole(1));
+    let ((val7, pk7), stake7) = (gen_validator(), token::Amount::whole(1));
+    println!("val1: {val1}, {pk1}, {stake1}");
+    println!("val2: {val2}, {pk2}, {stake2}");
+    println!("val3: {val3}, {pk3}, {stake3}");
+    println!("val4: {val4}, {pk4}, {stake4}");
+    println!("val5: {val5}, {pk5}, {stake5}");
+    println!("val6: {val6}, {pk6}, {stake6}");
+    println!("val7: {val7}, {pk7}, {stake7}");
+
+    init_genesis(
+        &mut s,
+        &params,
+        [
+            GenesisValidator {
+                address: val1.clone(),
+                tokens: stake1,
+                consensus_key: pk1.clone(),
+                commission_rate: Decimal::new(1, 1),
+                max_commission_rate_change: Decimal::new(1, 1),
+            },
+            GenesisValidator {
+                address: val2.clone(),
+                tokens: stake2,
+                consensus_key: pk2.clone(),
+                commission_rate: Decimal::new(1, 1),
+                max_commission_rate_change: Decimal::new(1, 1),
+            },
+        ]
+        .into_iter(),
+        epoch,
+    )
+    .unwrap();
+
+    // Advance to EPOCH 1
+    //
+    // We cannot call `get_tendermint_set_updates` for the genesis state as
+    // `validator_set_update_tendermint` is only called 2 blocks before the
+    // start of an epoch and so we need to give it a predecessor epoch (see
+    // `get_tendermint_set_updates`), which we cannot have on the first
+    // epoch. In any way, the initial validator set is given to Tendermint
+    // from InitChain, so `validator_set_update_tendermint` is
+    // not being used for it.
+    let epoch = advance_epoch(&mut s, &params);
+    let pipeline_epoch = epoch + params.pipeline_len;
+
+    // Insert another validator with the greater stake 10 NAM
+    insert_validator(&mut s, &val3, &pk3, stake3, epoch);
+    // Insert validator with stake 1 NAM
+    insert_validator(&mut s, &val4, &pk4, stake4, epoch);
+
+    // Validator `val3` and `val4` will be added at pipeline offset (2) - epoch
+    // 3
+    let val3_and_4_epoch = pipeline_epoch;
+
+    let consensus_vals: Vec<_> = consensus_validator_set_handle()
+        .at(&pipeline_epoch)
+        .iter(&s)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(consensus_vals.len(), 3);
+    assert!(matches!(
+        &consensus_vals[0],
+        (lazy_map::NestedSubKey::Data {
+                key: stake,
+                nested_sub_key: lazy_map::SubKey::Data(position),
+        }, address)
+        if address == &val1 && stake == &stake1 && *position == Position(0)
+    ));
+    assert!(matches!(
+        &consensus_vals[1],
+        (lazy_map::NestedSubKey::Data {
+                key: stake,
+                nested_sub_key: lazy_map::SubKey::Data(position),
+        }, address)
+        if address == &val2 && stake == &stake2 && *position == Position(1)
+    ));
+    assert!(matches!(
+        &consensus_vals[2],
+        (lazy_map::NestedSubKey::Data {
+                key: stake,
+                nested_sub_key: lazy_map::SubKey::Data(position),
+        }, address)
+        if address == &val3 && stake == &stake3 && *position == Position(0)
+    ));
+
+    // Check tendermint validator set updates - there should be none
+    let tm_updates = get_tendermint_set_updates(&s, &params, epoch);
+    assert!(tm_updates.is_empty());
+
+    // Advance to EPOCH 2
+    let epoch = advance_epoch(&mut s, &params);
+    let pipeline_epoch = epoch + params.pipeline_len;
 
     // Insert another validator with a greater stake still 1000 NAM. It should
     // replace 2nd consensus validator with stake 1, which should become
@@ -1084,6 +2692,7 @@ fn test_validator_sets() {
         ValidatorSetUpdate::Consensus(ConsensusValidator {
             consensus_key: pk3,
             bonded_stake: stake3.into(),
+            weight: into_tm_voting_power(params.tm_votes_per_token, stake3),
         })
     );
 
@@ -1139,6 +2748,7 @@ fn test_validator_sets() {
         ValidatorSetUpdate::Consensus(ConsensusValidator {
             consensus_key: pk5,
             bonded_stake: stake5.into(),
+            weight: into_tm_voting_power(params.tm_votes_per_token, stake5),
         })
     );
     assert_eq!(tm_updates[1], ValidatorSetUpdate::Deactivated(pk2));
@@ -1148,11 +2758,17 @@ fn test_validator_sets() {
     let unbond = token::Amount::from(500_000);
     let stake1 = stake1 - unbond;
     println!("val1 {val1} new stake {stake1}");
-    // Because `update_validator_set` and `update_validator_deltas` are
-    // effective from pipeline offset, we use pipeline epoch for the rest of the
-    // checks
-    update_validator_set(&mut s, &params, &val1, -unbond.change(), epoch)
-        .unwrap();
+    // Because `update_validator_set_for_stake_change` and
+    // `update_validator_deltas` are effective from pipeline offset, we use
+    // pipeline epoch for the rest of the checks
+    update_validator_set_for_stake_change(
+        &mut s,
+        &params,
+        &val1,
+        -unbond.change(),
+        epoch,
+    )
+    .unwrap();
     update_validator_deltas(&mut s, &params, &val1, -unbond.change(), epoch)
         .unwrap();
     // Epoch 6
@@ -1335,6 +2951,7 @@ fn test_validator_sets() {
         ValidatorSetUpdate::Consensus(ConsensusValidator {
             consensus_key: pk4.clone(),
             bonded_stake: stake4.into(),
+            weight: into_tm_voting_power(params.tm_votes_per_token, stake4),
         })
     );
     assert_eq!(tm_updates[1], ValidatorSetUpdate::Deactivated(pk1));
@@ -1344,7 +2961,14 @@ fn test_validator_sets() {
     let bond = token::Amount::from(500_000);
     let stake6 = stake6 + bond;
     println!("val6 {val6} new stake {stake6}");
-    update_validator_set(&mut s, &params, &val6, bond.change(), epoch).unwrap();
+    update_validator_set_for_stake_change(
+        &mut s,
+        &params,
+        &val6,
+        bond.change(),
+        epoch,
+    )
+    .unwrap();
     update_validator_deltas(&mut s, &params, &val6, bond.change(), epoch)
         .unwrap();
     let val6_bond_epoch = pipeline_epoch;
@@ -1451,6 +3075,7 @@ fn test_validator_sets() {
         ValidatorSetUpdate::Consensus(ConsensusValidator {
             consensus_key: pk6,
             bonded_stake: stake6.into(),
+            weight: into_tm_voting_power(params.tm_votes_per_token, stake6),
         })
     );
     assert_eq!(tm_updates[1], ValidatorSetUpdate::Deactivated(pk4));
@@ -1567,13 +3192,25 @@ fn test_validator_sets_swap() {
     assert_eq!(into_tm_voting_power(params.tm_votes_per_token, stake2), 0);
     assert_eq!(into_tm_voting_power(params.tm_votes_per_token, stake3), 0);
 
-    update_validator_set(&mut s, &params, &val2, bond2.change(), epoch)
-        .unwrap();
+    update_validator_set_for_stake_change(
+        &mut s,
+        &params,
+        &val2,
+        bond2.change(),
+        epoch,
+    )
+    .unwrap();
     update_validator_deltas(&mut s, &params, &val2, bond2.change(), epoch)
         .unwrap();
 
-    update_validator_set(&mut s, &params, &val3, bond3.change(), epoch)
-        .unwrap();
+    update_validator_set_for_stake_change(
+        &mut s,
+        &params,
+        &val3,
+        bond3.change(),
+        epoch,
+    )
+    .unwrap();
     update_validator_deltas(&mut s, &params, &val3, bond3.change(), epoch)
         .unwrap();
 
@@ -1592,13 +3229,25 @@ fn test_validator_sets_swap() {
         into_tm_voting_power(params.tm_votes_per_token, stake3)
     );
 
-    update_validator_set(&mut s, &params, &val2, bonds.change(), epoch)
-        .unwrap();
+    update_validator_set_for_stake_change(
+        &mut s,
+        &params,
+        &val2,
+        bonds.change(),
+        epoch,
+    )
+    .unwrap();
     update_validator_deltas(&mut s, &params, &val2, bonds.change(), epoch)
         .unwrap();
 
-    update_validator_set(&mut s, &params, &val3, bonds.change(), epoch)
-        .unwrap();
+    update_validator_set_for_stake_change(
+        &mut s,
+        &params,
+        &val3,
+        bonds.change(),
+        epoch,
+    )
+    .unwrap();
     update_validator_deltas(&mut s, &params, &val3, bonds.change(), epoch)
         .unwrap();
 
@@ -1628,6 +3277,7 @@ fn test_validator_sets_swap() {
         ValidatorSetUpdate::Consensus(ConsensusValidator {
             consensus_key: pk3,
             bonded_stake: stake3.into(),
+            weight: into_tm_voting_power(params.tm_votes_per_token, stake3),
         })
     );
 }
@@ -662,10 +662,7 @@ impl AbstractStateMachine for AbstractPosState {
                 {
                     let bonds = state.bonds.entry(epoch).or_default();
                     bonds.insert(
-                        BondId {
-                            source: address.clone(),
-                            validator: address.clone(),
-                        },
+                        BondId::self_bond(address.clone()),
                         token::Change::from(tokens),
                     );
 
@@ -890,7 +887,7 @@ impl AbstractStateMachine for AbstractPosState {
                 let pipeline = state.epoch + state.params.pipeline_len;
                 // The validator must be known
                 state.is_validator(&id.validator, pipeline)
-                    && (id.validator == id.source
+                    && (id.is_self_bond()
                         // If it's not a self-bond, the source must not be a validator
                         || !state.is_validator(&id.source, pipeline))
             }
@@ -1084,7 +1081,7 @@ impl AbstractPosState {
                     .or_default()
                     .push_back(validator.clone());
             }
-            ValidatorState::Inactive => {
+            ValidatorState::Inactive | ValidatorState::Tombstoned => {
                 panic!("unexpected state")
             }
         }
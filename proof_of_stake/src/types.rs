@@ -3,7 +3,7 @@
 mod rev_order;
 
 use core::fmt::Debug;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::convert::TryFrom;
 use std::fmt::Display;
 use std::hash::Hash;
@@ -21,6 +21,7 @@ use namada_core::types::storage::{Epoch, KeySeg};
 use namada_core::types::token;
 pub use rev_order::ReverseOrdTokenAmount;
 use rust_decimal::prelude::{Decimal, ToPrimitive};
+use serde::{Deserialize, Serialize};
 
 use crate::parameters::PosParams;
 
@@ -137,6 +138,10 @@ pub type Unbonds = NestedMap<Epoch, LazyMap<Epoch, token::Amount>>;
 /// Consensus keys set, used to ensure uniqueness
 pub type ConsensusKeys = LazySet<common::PublicKey>;
 
+/// Set of tombstoned validators, which are permanently banned from the
+/// validator sets
+pub type TombstonedValidators = LazySet<Address>;
+
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
 /// Commission rate and max commission rate change per epoch for a validator
 pub struct CommissionPair {
@@ -153,6 +158,28 @@ pub type RewardsProducts = LazyMap<Epoch, Decimal>;
 /// rewards owed over the course of an epoch)
 pub type RewardsAccumulator = LazyMap<Address, Decimal>;
 
+/// Snapshots of the consensus validator rewards accumulator, taken at the end
+/// of each epoch
+pub type RewardsAccumulatorSnapshots = NestedMap<Epoch, RewardsAccumulator>;
+
+/// The votes of the validators whose signature was included in a block, keyed
+/// by the epoch and the height of the signed block
+pub type EpochedVotes = NestedMap<Epoch, LazyMap<u64, Vec<VoteInfo>>>;
+
+/// Total amounts of unbonded tokens that become withdrawable in an epoch,
+/// keyed by that epoch
+pub type TotalUnbonding = LazyMap<Epoch, token::Amount>;
+
+/// Snapshots of the PoS system taken at the start of every beacon epoch
+pub type BeaconSnapshots = LazyMap<Epoch, BeaconSnapshot>;
+
+/// Multisig bonds that are waiting for approvals, keyed by their ID
+pub type PendingMultisigBonds = LazyMap<u64, PendingMultisigBond>;
+
+/// Delegator validator index entries of fully unbonded bonds, keyed by the
+/// epoch from which they can be removed
+pub type DelegatorValidatorIndexRemovals = LazyMap<Epoch, Vec<BondId>>;
+
 // --------------------------------------------------------------------------------------------
 
 /// A genesis validator definition.
@@ -190,13 +217,28 @@ pub enum ValidatorSetUpdate {
     Deactivated(common::PublicKey),
 }
 
-/// Consensus validator's consensus key and its bonded stake.
+/// Consensus validator's consensus key, its bonded stake and its voting power.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ConsensusValidator {
     /// A public key used for signing validator's consensus actions
     pub consensus_key: common::PublicKey,
     /// Total bonded stake of the validator
     pub bonded_stake: u64,
+    /// The validator's Tendermint voting power, computed from the bonded
+    /// stake with [`into_tm_voting_power`]
+    pub weight: i64,
+}
+
+/// The changes to the consensus validator set that take effect in the next
+/// epoch, as previewed by [`crate::simulate_epoch_transition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedEpochResult {
+    /// The consensus validator set in the next epoch
+    pub new_consensus_set: HashSet<WeightedValidator>,
+    /// Validators that join the consensus set in the next epoch
+    pub promotions: HashSet<Address>,
+    /// Validators that leave the consensus set in the next epoch
+    pub demotions: HashSet<Address>,
 }
 
 /// ID of a bond and/or an unbond.
@@ -211,6 +253,8 @@ pub struct ConsensusValidator {
     BorshDeserialize,
     BorshSerialize,
     BorshSchema,
+    Serialize,
+    Deserialize,
 )]
 pub struct BondId {
     /// (Un)bond's source address is the owner of the bonded tokens.
@@ -219,6 +263,110 @@ pub struct BondId {
     pub validator: Address,
 }
 
+/// Statistics of the bonds in the whole PoS system, refreshed at the start of
+/// every epoch.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct BondsSummary {
+    /// The epoch in which the statistics were computed
+    pub epoch: Epoch,
+    /// The number of bonds with a non-zero amount, including self-bonds
+    pub active_bonds: u64,
+    /// The number of distinct sources of delegations with a non-zero amount
+    pub unique_delegators: u64,
+}
+
+/// A snapshot of the PoS system taken at the start of a beacon epoch, i.e. an
+/// epoch that is a multiple of the `beacon_epoch_interval` PoS parameter.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct BeaconSnapshot {
+    /// The total bonded stake
+    pub total_stake: token::Amount,
+    /// The number of validators in the consensus and below-capacity sets
+    pub num_validators: u64,
+    /// The average commission rate of these validators
+    pub avg_commission_rate: Decimal,
+}
+
+/// An announcement of a validator's commission rate change. The change may
+/// only be applied once the `commission_change_notice_epochs` PoS parameter
+/// have passed since the `announced_epoch`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct CommissionRateAnnouncement {
+    /// The epoch in which the change was announced
+    pub announced_epoch: Epoch,
+    /// The announced commission rate
+    pub new_rate: Decimal,
+}
+
+/// A bond that is only committed once at least `threshold` of its `sources`
+/// have approved it.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct MultisigBond {
+    /// Addresses that may approve the bond. The first source is the owner of
+    /// the bonded tokens.
+    pub sources: Vec<Address>,
+    /// The number of sources that must approve the bond
+    pub threshold: u32,
+    /// Validator address
+    pub validator: Address,
+    /// The amount of tokens
+    pub amount: token::Amount,
+}
+
+/// A multisig bond waiting for approvals. Its tokens are held by the PoS
+/// account until it is either committed or it expires.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct PendingMultisigBond {
+    /// The bond
+    pub bond: MultisigBond,
+    /// The sources that have approved the bond so far
+    pub approvals: BTreeSet<Address>,
+    /// Epoch in which the bond was submitted
+    pub submission_epoch: Epoch,
+}
+
 /// Validator's address with its voting power.
 #[derive(
     Debug,
@@ -315,6 +463,8 @@ impl Position {
     BorshSchema,
     PartialEq,
     Eq,
+    Serialize,
+    Deserialize,
 )]
 pub enum ValidatorState {
     /// A validator who may participate in the consensus
@@ -325,6 +475,9 @@ pub enum ValidatorState {
     /// A validator who is deactivated via a tx when a validator no longer
     /// wants to be one (not implemented yet)
     Inactive,
+    /// A validator who has been permanently removed from the validator sets
+    /// for a severe infraction and can never rejoin them
+    Tombstoned,
 }
 
 /// A slash applied to validator, to punish byzantine behavior by removing
@@ -340,6 +493,8 @@ pub enum ValidatorState {
     PartialOrd,
     Ord,
     Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct Slash {
     /// Epoch at which the slashable event occurred.
@@ -354,6 +509,10 @@ pub struct Slash {
 /// their staked tokens at and before the epoch of the slash.
 pub type Slashes = LazyVec<Slash>;
 
+/// A validator's stake in the epoch of the slashable event of each of its
+/// slashes, keyed by the slash's index in [`Slashes`]
+pub type SlashesStakeAtInfraction = LazyMap<u64, token::Amount>;
+
 /// A type of slashsable event.
 #[derive(
     Debug,
@@ -366,6 +525,8 @@ pub type Slashes = LazyVec<Slash>;
     PartialOrd,
     Ord,
     Hash,
+    Serialize,
+    Deserialize,
 )]
 pub enum SlashType {
     /// Duplicate block vote.
@@ -374,9 +535,26 @@ pub enum SlashType {
     LightClientAttack,
 }
 
+/// Destination of the tokens redistributed from the PoS slash pool by
+/// governance.
+#[derive(
+    Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Eq,
+)]
+pub enum SlashPoolDestination {
+    /// Burn the tokens, reducing the total supply.
+    Burn,
+    /// Transfer the tokens to a treasury account.
+    Treasury(Address),
+    /// Distribute the tokens to the consensus validators, pro-rata to their
+    /// stake.
+    ValidatorSet,
+}
+
 /// VoteInfo inspired from tendermint for validators whose signature was
 /// included in the last block
-#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema,
+)]
 pub struct VoteInfo {
     /// Validator address
     pub validator_address: Address,
@@ -386,10 +564,18 @@ pub struct VoteInfo {
 
 /// Bonds and unbonds with all details (slashes and rewards, if any)
 /// grouped by their bond IDs.
-pub type BondsAndUnbondsDetails = HashMap<BondId, BondsAndUnbondsDetail>;
+pub type BondsAndUnbondsDetails = BTreeMap<BondId, BondsAndUnbondsDetail>;
 
 /// Bonds and unbonds with all details (slashes and rewards, if any)
-#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema)]
+#[derive(
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
 pub struct BondsAndUnbondsDetail {
     /// Bonds
     pub bonds: Vec<BondDetails>,
@@ -397,11 +583,20 @@ pub struct BondsAndUnbondsDetail {
     pub unbonds: Vec<UnbondDetails>,
     /// Slashes applied to any of the bonds and/or unbonds
     pub slashes: HashSet<Slash>,
+    /// The state of the bond's validator in the current epoch
+    pub validator_state: Option<ValidatorState>,
 }
 
 /// Bond with all its details
 #[derive(
-    Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq,
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Serialize,
+    Deserialize,
 )]
 pub struct BondDetails {
     /// The first epoch in which this bond contributed to a stake
@@ -414,7 +609,14 @@ pub struct BondDetails {
 
 /// Unbond with all its details
 #[derive(
-    Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq,
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Serialize,
+    Deserialize,
 )]
 pub struct UnbondDetails {
     /// The first epoch in which the source bond of this unbond contributed to
@@ -430,6 +632,43 @@ pub struct UnbondDetails {
     pub slashed_amount: Option<token::Amount>,
 }
 
+/// Convert bonds and unbonds details into a JSON value for RPC responses. The
+/// details are given as an array of entries sorted by their bond IDs and the
+/// slashes of each entry are sorted too, so that the output is stable.
+pub fn to_json_response(details: &BondsAndUnbondsDetails) -> serde_json::Value {
+    let entries: Vec<serde_json::Value> = details
+        .iter()
+        .map(|(bond_id, detail)| {
+            let mut slashes: Vec<&Slash> = detail.slashes.iter().collect();
+            slashes.sort();
+            serde_json::json!({
+                "source": bond_id.source,
+                "validator": bond_id.validator,
+                "bonds": detail.bonds,
+                "unbonds": detail.unbonds,
+                "slashes": slashes,
+                "validator_state": detail.validator_state,
+            })
+        })
+        .collect();
+    serde_json::Value::Array(entries)
+}
+
+impl BondId {
+    /// The ID of a validator's self-bond
+    pub fn self_bond(validator: Address) -> Self {
+        Self {
+            source: validator.clone(),
+            validator,
+        }
+    }
+
+    /// Is this the ID of a self-bond, i.e. is the source the validator itself?
+    pub fn is_self_bond(&self) -> bool {
+        self.source == self.validator
+    }
+}
+
 impl Display for BondId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -440,6 +679,56 @@ impl Display for BondId {
     }
 }
 
+impl MultisigBond {
+    /// The owner of the bonded tokens
+    pub fn owner(&self) -> &Address {
+        &self.sources[0]
+    }
+}
+
+impl PendingMultisigBond {
+    /// Find the sources that approve a pending multisig bond with its change
+    /// from `pre` to `post`. A new bond is approved by its owner. When a bond
+    /// is committed and removed, the approval may have come from any source
+    /// that hasn't approved it before.
+    pub fn new_approvers<'a>(
+        pre: Option<&'a Self>,
+        post: Option<&'a Self>,
+    ) -> BTreeSet<&'a Address> {
+        match (pre, post) {
+            (None, Some(post)) => post.approvals.iter().collect(),
+            (Some(pre), Some(post)) => {
+                post.approvals.difference(&pre.approvals).collect()
+            }
+            (Some(pre), None) => pre
+                .bond
+                .sources
+                .iter()
+                .filter(|source| !pre.approvals.contains(source))
+                .collect(),
+            (None, None) => BTreeSet::new(),
+        }
+    }
+}
+
+impl ValidatorState {
+    /// Check if a validator may transition from this state to the given
+    /// state. A validator can move between the consensus and below-capacity
+    /// sets and can be deactivated, but an inactive validator cannot be
+    /// re-activated. Any validator can be tombstoned, after which its state
+    /// can no longer change.
+    pub fn can_transition_to(&self, next: ValidatorState) -> bool {
+        match self {
+            ValidatorState::Consensus | ValidatorState::BelowCapacity => true,
+            ValidatorState::Inactive => matches!(
+                next,
+                ValidatorState::Inactive | ValidatorState::Tombstoned
+            ),
+            ValidatorState::Tombstoned => next == ValidatorState::Tombstoned,
+        }
+    }
+}
+
 impl SlashType {
     /// Get the slash rate applicable to the given slash type from the PoS
     /// parameters.
@@ -456,10 +745,33 @@ impl SlashType {
 impl Display for SlashType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SlashType::DuplicateVote => write!(f, "Duplicate vote"),
-            SlashType::LightClientAttack => write!(f, "Light client attack"),
+            SlashType::DuplicateVote => write!(f, "duplicate_vote"),
+            SlashType::LightClientAttack => write!(f, "light_client_attack"),
+        }
+    }
+}
+
+impl KeySeg for SlashType {
+    fn parse(string: String) -> namada_core::types::storage::Result<Self>
+    where
+        Self: Sized,
+    {
+        match string.as_str() {
+            "duplicate_vote" => Ok(SlashType::DuplicateVote),
+            "light_client_attack" => Ok(SlashType::LightClientAttack),
+            _ => Err(namada_core::types::storage::Error::ParseKeySeg(format!(
+                "Unknown slash type: {string}"
+            ))),
         }
     }
+
+    fn raw(&self) -> String {
+        self.to_string()
+    }
+
+    fn to_db_key(&self) -> namada_core::types::storage::DbKeySeg {
+        namada_core::types::storage::DbKeySeg::StringSeg(self.raw())
+    }
 }
 
 /// Multiply a value of type Decimal with one of type u64 and then return the
@@ -512,6 +824,7 @@ pub mod tests {
 
     use std::ops::Range;
 
+    use namada_core::types::address;
     use proptest::prelude::*;
 
     use super::*;
@@ -520,4 +833,47 @@ pub mod tests {
     pub fn arb_epoch(range: Range<u64>) -> impl Strategy<Value = Epoch> {
         range.prop_map(Epoch)
     }
+
+    /// Test the display of the slash types
+    #[test]
+    fn test_slash_type_display() {
+        assert_eq!(SlashType::DuplicateVote.to_string(), "duplicate_vote");
+        assert_eq!(
+            SlashType::LightClientAttack.to_string(),
+            "light_client_attack"
+        );
+    }
+
+    /// Test that the slash type key segments round-trip and preserve the
+    /// ordering of the slash types
+    #[test]
+    fn test_slash_type_key_seg() {
+        for slash_type in
+            [SlashType::DuplicateVote, SlashType::LightClientAttack]
+        {
+            let parsed = SlashType::parse(slash_type.raw()).unwrap();
+            assert_eq!(parsed, slash_type);
+        }
+        assert!(
+            SlashType::DuplicateVote.to_db_key()
+                < SlashType::LightClientAttack.to_db_key()
+        );
+        assert!(SlashType::parse("unknown".to_owned()).is_err());
+    }
+
+    /// Test the detection of self-bonds
+    #[test]
+    fn test_bond_id_is_self_bond() {
+        let validator = address::testing::established_address_1();
+        let delegator = address::testing::established_address_2();
+        let self_bond = BondId::self_bond(validator.clone());
+        assert_eq!(self_bond.source, validator);
+        assert_eq!(self_bond.validator, validator);
+        assert!(self_bond.is_self_bond());
+        let delegation = BondId {
+            source: delegator,
+            validator,
+        };
+        assert!(!delegation.is_self_bond());
+    }
 }
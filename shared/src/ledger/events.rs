@@ -10,6 +10,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use thiserror::Error;
 
 use crate::ledger::native_vp::governance::utils::ProposalEvent;
+use crate::ledger::pos::PosEvent;
 use crate::tendermint_proto::abci::EventAttribute;
 use crate::types::ibc::IbcEvent;
 #[cfg(feature = "ferveo-tpke")]
@@ -49,6 +50,10 @@ pub enum EventType {
     Ibc(String),
     /// The proposal that has been executed
     Proposal,
+    /// Evidence of an unknown type of misbehavior that could not be slashed
+    UnknownEvidence,
+    /// A PoS operation that has been applied
+    Pos,
 }
 
 impl Display for EventType {
@@ -58,6 +63,8 @@ impl Display for EventType {
             EventType::Applied => write!(f, "applied"),
             EventType::Ibc(t) => write!(f, "{}", t),
             EventType::Proposal => write!(f, "proposal"),
+            EventType::UnknownEvidence => write!(f, "unknown_evidence"),
+            EventType::Pos => write!(f, "pos"),
         }?;
         Ok(())
     }
@@ -161,6 +168,71 @@ impl From<ProposalEvent> for Event {
     }
 }
 
+impl From<PosEvent> for Event {
+    fn from(pos_event: PosEvent) -> Self {
+        let kind = pos_event.kind().to_string();
+        let (level, attributes) = match pos_event {
+            PosEvent::Bond {
+                source,
+                validator,
+                amount,
+                epoch,
+            } => (
+                EventLevel::Tx,
+                HashMap::from([
+                    ("kind".to_string(), kind),
+                    ("source".to_string(), source.to_string()),
+                    ("validator".to_string(), validator.to_string()),
+                    ("amount".to_string(), amount.to_string()),
+                    ("epoch".to_string(), epoch.to_string()),
+                ]),
+            ),
+            PosEvent::Unbond {
+                source,
+                validator,
+                amount,
+                withdraw_epoch,
+            } => (
+                EventLevel::Tx,
+                HashMap::from([
+                    ("kind".to_string(), kind),
+                    ("source".to_string(), source.to_string()),
+                    ("validator".to_string(), validator.to_string()),
+                    ("amount".to_string(), amount.to_string()),
+                    ("withdraw_epoch".to_string(), withdraw_epoch.to_string()),
+                ]),
+            ),
+            PosEvent::Slash {
+                validator,
+                slash_type,
+                rate,
+                epoch,
+            } => (
+                EventLevel::Block,
+                HashMap::from([
+                    ("kind".to_string(), kind),
+                    ("validator".to_string(), validator.to_string()),
+                    ("slash_type".to_string(), slash_type),
+                    ("rate".to_string(), rate.to_string()),
+                    ("epoch".to_string(), epoch.to_string()),
+                ]),
+            ),
+            PosEvent::Burn { amount } => (
+                EventLevel::Tx,
+                HashMap::from([
+                    ("kind".to_string(), kind),
+                    ("amount".to_string(), amount.to_string()),
+                ]),
+            ),
+        };
+        Self {
+            event_type: EventType::Pos,
+            level,
+            attributes,
+        }
+    }
+}
+
 /// Convert our custom event into the necessary tendermint proto type
 impl From<Event> for crate::tendermint_proto::abci::Event {
     fn from(event: Event) -> Self {
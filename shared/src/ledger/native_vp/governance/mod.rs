@@ -121,10 +121,9 @@ where
 {
     fn is_valid_key_set(&self, keys: &BTreeSet<Key>) -> Result<(bool, u64)> {
         let counter_key = gov_storage::get_counter_key();
-        let pre_counter: u64 =
-            self.ctx.pre().read(&counter_key)?.unwrap_or_default();
+        let pre_counter: u64 = self.ctx.pre().read_or_default(&counter_key)?;
         let post_counter: u64 =
-            self.ctx.post().read(&counter_key)?.unwrap_or_default();
+            self.ctx.post().read_or_default(&counter_key)?;
 
         if post_counter < pre_counter {
             return Ok((false, 0));
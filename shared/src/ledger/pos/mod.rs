@@ -7,12 +7,17 @@ use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
 use namada_core::types::address;
 pub use namada_core::types::key::common;
 pub use namada_core::types::token;
+pub use namada_core::types::transaction::pos::{
+    decode_pos_tx_data, PosEvent, PosTxData,
+};
 pub use namada_proof_of_stake;
 pub use namada_proof_of_stake::parameters::PosParams;
 pub use namada_proof_of_stake::storage::*;
-pub use namada_proof_of_stake::{staking_token_address, types};
+pub use namada_proof_of_stake::{
+    burn_address, slash_pool_address, staking_token_address, types,
+};
 use rust_decimal::Decimal;
-pub use vp::PosVP;
+pub use vp::{PosBurnVp, PosVP, SlashPoolVp};
 
 use crate::types::address::{Address, InternalAddress};
 use crate::types::storage::Epoch;
@@ -6,13 +6,26 @@ use std::panic::{RefUnwindSafe, UnwindSafe};
 // use borsh::BorshDeserialize;
 pub use namada_proof_of_stake;
 pub use namada_proof_of_stake::parameters::PosParams;
-// use namada_proof_of_stake::validation::validate;
-use namada_proof_of_stake::read_pos_params;
 pub use namada_proof_of_stake::types;
-// use rust_decimal::Decimal;
+// use namada_proof_of_stake::validation::validate;
+use namada_proof_of_stake::{
+    burn_address, read_pos_params, read_validator_commission_rate_announcement,
+    read_validator_max_commission_rate_change, slash_pool_address,
+    validator_commission_rate_handle,
+};
+use rust_decimal::Decimal;
 use thiserror::Error;
 
-use super::is_params_key;
+use super::{
+    decode_pos_tx_data, is_bond_key,
+    is_consensus_validator_rewards_accumulator_key,
+    is_multisig_bond_counter_key, is_params_key, is_pending_multisig_bond_key,
+    is_pending_multisig_bonds_key, is_total_burned_key, is_unbond_key,
+    is_validator_commission_rate_announcement_key,
+    is_validator_commission_rate_sub_key, multisig_bond_counter_key,
+    total_burned_key, PendingMultisigBond, PosEvent, PosTxData,
+};
+use crate::ledger::governance::storage as gov_storage;
 use crate::ledger::native_vp::{self, governance, Ctx, NativeVp};
 // use crate::ledger::pos::{
 //     is_validator_address_raw_hash_key,
@@ -21,7 +34,10 @@ use crate::ledger::native_vp::{self, governance, Ctx, NativeVp};
 use crate::ledger::storage::{self as ledger_storage, StorageHasher};
 use crate::ledger::storage_api::StorageRead;
 use crate::types::address::{Address, InternalAddress};
-use crate::types::storage::{Key, KeySeg};
+use crate::types::hash::{Hash, HASH_LENGTH};
+use crate::types::storage::{Epoch, Key, KeySeg, SafeEpochArithmetic};
+use crate::types::token;
+use crate::types::transaction::pos::{DecodeError, POS_TX_WASMS};
 use crate::vm::WasmCacheAccess;
 
 #[allow(missing_docs)]
@@ -29,6 +45,8 @@ use crate::vm::WasmCacheAccess;
 pub enum Error {
     #[error("Native VP error: {0}")]
     NativeVpError(native_vp::Error),
+    #[error("PoS tx data decoding error: {0}")]
+    TxDataDecoding(DecodeError),
 }
 
 /// PoS functions result
@@ -55,6 +73,475 @@ where
     pub fn new(ctx: Ctx<'a, DB, H, CA>) -> Self {
         Self { ctx }
     }
+
+    /// Check that the PoS parameters written under the given key are valid
+    /// and that none of their values changes by more than the maximum rate
+    /// allowed by governance in a single proposal.
+    fn is_valid_params_change(&self, key: &Key) -> Result<bool> {
+        let new_params = match self.ctx.post().read::<PosParams>(key)? {
+            Some(params) => params,
+            None => {
+                tracing::info!("PoS parameters must not be deleted");
+                return Ok(false);
+            }
+        };
+        let errors = new_params.validate();
+        if !errors.is_empty() {
+            tracing::info!("Invalid PoS parameters: {:?}", errors);
+            return Ok(false);
+        }
+
+        let max_change_rate_key =
+            gov_storage::get_max_pos_params_change_rate_key();
+        let max_change_rate =
+            match self.ctx.pre().read::<Decimal>(&max_change_rate_key)? {
+                Some(rate) => rate,
+                None => return Ok(false),
+            };
+        let params = read_pos_params(&self.ctx.pre())?;
+        let errors = params.validate_change(&new_params, max_change_rate);
+        if !errors.is_empty() {
+            tracing::info!("Invalid PoS parameters change: {:?}", errors);
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Check that the commission rate of the given validator at the pipeline
+    /// offset is between 0 and 1 and that it doesn't differ from the rate in
+    /// the epoch before it by more than the validator's maximum commission
+    /// rate change. When the `commission_change_notice_epochs` parameter is
+    /// non-zero, a new rate must also have been announced at least that many
+    /// epochs before.
+    fn is_valid_commission_rate_change(
+        &self,
+        validator: &Address,
+        params: &PosParams,
+        current_epoch: Epoch,
+    ) -> Result<bool> {
+        let commission_handle = validator_commission_rate_handle(validator);
+        let pipeline_epoch = current_epoch.saturating_add(params.pipeline_len);
+        let new_rate = match commission_handle.get(
+            &self.ctx.post(),
+            pipeline_epoch,
+            params,
+        )? {
+            Some(rate) => rate,
+            None => {
+                tracing::info!(
+                    "PoS commission rate of validator {} must not be deleted",
+                    validator
+                );
+                return Ok(false);
+            }
+        };
+        if new_rate.is_sign_negative() || new_rate > Decimal::ONE {
+            tracing::info!(
+                "PoS commission rate of validator {} must be between 0 and 1, \
+                 got {}",
+                validator,
+                new_rate
+            );
+            return Ok(false);
+        }
+
+        // A new validator has no previous rate to compare against
+        let max_change = match read_validator_max_commission_rate_change(
+            &self.ctx.pre(),
+            validator,
+        )? {
+            Some(max_change) => max_change,
+            None => return Ok(true),
+        };
+        if params.commission_change_notice_epochs > 0 {
+            let pre_rate = commission_handle.get(
+                &self.ctx.pre(),
+                pipeline_epoch,
+                params,
+            )?;
+            if pre_rate != Some(new_rate)
+                && !self.is_announced_commission_rate(
+                    validator,
+                    new_rate,
+                    params,
+                    current_epoch,
+                )?
+            {
+                return Ok(false);
+            }
+        }
+        // At epoch 0 there's no previous rate to compare against
+        let prev_epoch = match pipeline_epoch.checked_sub_epochs(1) {
+            Some(prev_epoch) => prev_epoch,
+            None => return Ok(true),
+        };
+        let prev_rate =
+            commission_handle.get(&self.ctx.post(), prev_epoch, params)?;
+        if let Some(prev_rate) = prev_rate {
+            let change = (new_rate - prev_rate).abs();
+            if change > max_change {
+                tracing::info!(
+                    "PoS commission rate change of validator {} from {} to {} \
+                     is larger than the maximum change {}",
+                    validator,
+                    prev_rate,
+                    new_rate,
+                    max_change
+                );
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Decode the data of the tx when its code is one of the PoS txs, which
+    /// are identified by the code hashes stored for their wasm file names.
+    fn pos_tx_data(&self, tx_data: &[u8]) -> Result<Option<PosTxData>> {
+        let code = &self.ctx.tx.code_or_hash;
+        let code_hash = if code.len() == HASH_LENGTH {
+            match Hash::try_from(&code[..]) {
+                Ok(hash) => hash,
+                Err(_) => return Ok(None),
+            }
+        } else {
+            Hash::sha256(code)
+        };
+        for tx_code_path in POS_TX_WASMS {
+            let hash =
+                self.ctx.pre().read_bytes(&Key::wasm_hash(tx_code_path))?;
+            if hash.as_deref() == Some(code_hash.as_ref()) {
+                return decode_pos_tx_data(tx_code_path, tx_data)
+                    .map_err(Error::TxDataDecoding);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Check that the PoS events emitted by the tx match the changes that it
+    /// applied. Slash events may only be emitted by the protocol.
+    fn is_valid_pos_events(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool> {
+        let mut burned = token::Amount::default();
+        for event in self.ctx.write_log.get_pos_events() {
+            let is_valid = match event {
+                PosEvent::Bond {
+                    source,
+                    validator,
+                    amount,
+                    epoch,
+                } => {
+                    let bond_keys = keys_changed.iter().filter(|key| {
+                        matches!(is_bond_key(key), Some((id, bond_epoch))
+                            if &id.source == source
+                                && &id.validator == validator
+                                && &bond_epoch == epoch)
+                    });
+                    let mut bonded: token::Change = 0;
+                    for key in bond_keys {
+                        let pre: token::Change =
+                            self.ctx.pre().read_or_default(key)?;
+                        let post: token::Change =
+                            self.ctx.post().read_or_default(key)?;
+                        bonded += post - pre;
+                    }
+                    bonded == amount.change()
+                }
+                PosEvent::Unbond {
+                    source,
+                    validator,
+                    amount,
+                    withdraw_epoch,
+                } => {
+                    let unbond_keys = keys_changed.iter().filter(|key| {
+                        matches!(is_unbond_key(key), Some((id, _start, withdraw))
+                            if &id.source == source
+                                && &id.validator == validator
+                                && &withdraw == withdraw_epoch)
+                    });
+                    let mut unbonded: token::Change = 0;
+                    for key in unbond_keys {
+                        let pre: token::Amount =
+                            self.ctx.pre().read_or_default(key)?;
+                        let post: token::Amount =
+                            self.ctx.post().read_or_default(key)?;
+                        unbonded += post.change() - pre.change();
+                    }
+                    unbonded == amount.change()
+                }
+                PosEvent::Slash { .. } => false,
+                PosEvent::Burn { amount } => {
+                    burned = match burned.checked_add(*amount) {
+                        Some(burned) => burned,
+                        None => return Ok(false),
+                    };
+                    true
+                }
+            };
+            if !is_valid {
+                tracing::info!(
+                    "PoS event {:?} doesn't match the changes of the tx",
+                    event
+                );
+                return Ok(false);
+            }
+        }
+        if !burned.is_zero() {
+            let key = total_burned_key();
+            let pre: token::Amount = self.ctx.pre().read_or_default(&key)?;
+            let post: token::Amount = self.ctx.post().read_or_default(&key)?;
+            if post.checked_sub(pre) != Some(burned) {
+                tracing::info!(
+                    "PoS burn events of {} don't match the total burned \
+                     amount change from {} to {}",
+                    burned,
+                    pre,
+                    post
+                );
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check that the total burned amount only increases by the amount that
+    /// is transferred from the slash pool to the burn address in the same tx.
+    fn is_valid_total_burned_change(&self, key: &Key) -> Result<bool> {
+        let pre: token::Amount = self.ctx.pre().read_or_default(key)?;
+        let post: token::Amount = self.ctx.post().read_or_default(key)?;
+        if post == pre {
+            return Ok(true);
+        }
+        let burned = match post.checked_sub(pre) {
+            Some(burned) => burned,
+            None => {
+                tracing::info!(
+                    "PoS total burned amount must not decrease, from {} to {}",
+                    pre,
+                    post
+                );
+                return Ok(false);
+            }
+        };
+
+        let native_token = self.ctx.pre().get_native_token()?;
+        let pool_key = token::balance_key(&native_token, &slash_pool_address());
+        let pre_pool: token::Amount =
+            self.ctx.pre().read_or_default(&pool_key)?;
+        let post_pool: token::Amount =
+            self.ctx.post().read_or_default(&pool_key)?;
+        let burn_key = token::balance_key(&native_token, &burn_address());
+        let pre_burn: token::Amount =
+            self.ctx.pre().read_or_default(&burn_key)?;
+        let post_burn: token::Amount =
+            self.ctx.post().read_or_default(&burn_key)?;
+        if pre_pool.checked_sub(post_pool) != Some(burned)
+            || post_burn.checked_sub(pre_burn) != Some(burned)
+        {
+            tracing::info!(
+                "PoS total burned amount increased by {}, which must be \
+                 debited from the slash pool (from {} to {}) and credited to \
+                 the burn address (from {} to {})",
+                burned,
+                pre_pool,
+                post_pool,
+                pre_burn,
+                post_burn
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Check that the given commission rate of the validator has been
+    /// announced and that the announcement's notice period has passed.
+    fn is_announced_commission_rate(
+        &self,
+        validator: &Address,
+        new_rate: Decimal,
+        params: &PosParams,
+        current_epoch: Epoch,
+    ) -> Result<bool> {
+        let announcement = match read_validator_commission_rate_announcement(
+            &self.ctx.pre(),
+            validator,
+        )? {
+            Some(announcement) => announcement,
+            None => {
+                tracing::info!(
+                    "PoS commission rate change of validator {} to {} was not \
+                     announced",
+                    validator,
+                    new_rate
+                );
+                return Ok(false);
+            }
+        };
+        if announcement.new_rate != new_rate {
+            tracing::info!(
+                "PoS commission rate change of validator {} to {} differs \
+                 from the announced rate {}",
+                validator,
+                new_rate,
+                announcement.new_rate
+            );
+            return Ok(false);
+        }
+        let allowed_epoch = announcement
+            .announced_epoch
+            .saturating_add(params.commission_change_notice_epochs);
+        if current_epoch < allowed_epoch {
+            tracing::info!(
+                "PoS commission rate of validator {} cannot be changed before \
+                 epoch {}",
+                validator,
+                allowed_epoch
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Check that an announced commission rate change of the given validator
+    /// is for a rate between 0 and 1 and that it's announced in the current
+    /// epoch, so that its notice period cannot be shortened. An announcement
+    /// may be removed once it's used up.
+    fn is_valid_commission_rate_announcement(
+        &self,
+        validator: &Address,
+        current_epoch: Epoch,
+    ) -> Result<bool> {
+        let announcement = match read_validator_commission_rate_announcement(
+            &self.ctx.post(),
+            validator,
+        )? {
+            Some(announcement) => announcement,
+            None => return Ok(true),
+        };
+        if announcement.new_rate.is_sign_negative()
+            || announcement.new_rate > Decimal::ONE
+        {
+            tracing::info!(
+                "PoS announced commission rate of validator {} must be \
+                 between 0 and 1, got {}",
+                validator,
+                announcement.new_rate
+            );
+            return Ok(false);
+        }
+        if announcement.announced_epoch != current_epoch {
+            tracing::info!(
+                "PoS commission rate change of validator {} must be announced \
+                 in the current epoch {}, got {}",
+                validator,
+                current_epoch,
+                announcement.announced_epoch
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Check a change of the pending multisig bond with the given ID. A new
+    /// bond must be submitted under the next ID and approved only by its
+    /// owner. Every other change must add the approval of a single source
+    /// that is a verifier of the tx, so that its VP checks the signature. A
+    /// bond may only be removed by the approval that reaches its threshold,
+    /// because the expired bonds are removed by the protocol.
+    fn is_valid_multisig_bond_change(
+        &self,
+        key: &Key,
+        id: u64,
+        verifiers: &BTreeSet<Address>,
+        current_epoch: Epoch,
+    ) -> Result<bool> {
+        let pre: Option<PendingMultisigBond> = self.ctx.pre().read(key)?;
+        let post: Option<PendingMultisigBond> = self.ctx.post().read(key)?;
+        let new_approvers =
+            PendingMultisigBond::new_approvers(pre.as_ref(), post.as_ref());
+        let is_approved_by_verifier = new_approvers
+            .iter()
+            .any(|approver| verifiers.contains(*approver));
+        let params = read_pos_params(&self.ctx.pre())?;
+        let is_expired = |pending: &PendingMultisigBond| {
+            current_epoch
+                > pending
+                    .submission_epoch
+                    .saturating_add(params.multisig_timeout_epochs)
+        };
+        let valid = match (&pre, &post) {
+            (None, Some(post)) => {
+                let next_id: u64 = self
+                    .ctx
+                    .pre()
+                    .read_or_default(&multisig_bond_counter_key())?;
+                let sources: BTreeSet<&Address> =
+                    post.bond.sources.iter().collect();
+                let threshold = post.bond.threshold as usize;
+                // The owner is the first source
+                let is_approved_by_owner = match post.bond.sources.first() {
+                    Some(owner) => {
+                        post.approvals.len() == 1
+                            && post.approvals.contains(owner)
+                    }
+                    None => false,
+                };
+                id == next_id
+                    && post.submission_epoch == current_epoch
+                    && sources.len() == post.bond.sources.len()
+                    && threshold > 1
+                    && threshold <= sources.len()
+                    && is_approved_by_owner
+                    && is_approved_by_verifier
+            }
+            (Some(pre), Some(post)) => {
+                post.bond == pre.bond
+                    && post.submission_epoch == pre.submission_epoch
+                    && post.approvals.is_superset(&pre.approvals)
+                    && new_approvers.len() == 1
+                    && new_approvers
+                        .iter()
+                        .all(|approver| post.bond.sources.contains(*approver))
+                    && post.approvals.len() < post.bond.threshold as usize
+                    && is_approved_by_verifier
+                    && !is_expired(pre)
+            }
+            (Some(pre), None) => {
+                pre.approvals.len() + 1 >= pre.bond.threshold as usize
+                    && is_approved_by_verifier
+                    && !is_expired(pre)
+            }
+            (None, None) => true,
+        };
+        if !valid {
+            tracing::info!(
+                "PoS invalid change of the pending multisig bond with ID {} \
+                 from {:?} to {:?}",
+                id,
+                pre,
+                post
+            );
+        }
+        Ok(valid)
+    }
+
+    /// Check that the multisig bond counter is only incremented by one, when
+    /// a new multisig bond is submitted.
+    fn is_valid_multisig_bond_counter_change(&self, key: &Key) -> Result<bool> {
+        let pre: u64 = self.ctx.pre().read_or_default(key)?;
+        let post: u64 = self.ctx.post().read_or_default(key)?;
+        if pre.checked_add(1) != Some(post) {
+            tracing::info!(
+                "PoS multisig bond counter must only be incremented by one, \
+                 from {} to {}",
+                pre,
+                post
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
 }
 
 // TODO this is temporarily to run PoS native VP in a new thread to avoid
@@ -95,7 +582,7 @@ where
         &self,
         tx_data: &[u8],
         keys_changed: &BTreeSet<Key>,
-        _verifiers: &BTreeSet<Address>,
+        verifiers: &BTreeSet<Address>,
     ) -> Result<bool> {
         // use validation::Data;
         // use validation::DataUpdate::{self, *};
@@ -103,7 +590,11 @@ where
 
         let addr = Address::Internal(Self::ADDR);
         // let mut changes: Vec<DataUpdate> = vec![];
-        let _current_epoch = self.ctx.pre().get_block_epoch()?;
+        let current_epoch = self.ctx.pre().get_block_epoch()?;
+        // The sum of the increments of the rewards accumulator
+        let mut rewards_increment = Decimal::ZERO;
+        // Validators whose commission rate has changed
+        let mut commission_changes: BTreeSet<&Address> = BTreeSet::new();
 
         println!("\nVALIDATING TX\n");
 
@@ -118,6 +609,72 @@ where
                 {
                     return Ok(false);
                 }
+                if !self.is_valid_params_change(key)? {
+                    return Ok(false);
+                }
+            } else if let Some(validator) =
+                is_consensus_validator_rewards_accumulator_key(key)
+            {
+                let pre: Decimal = self.ctx.pre().read_or_default(key)?;
+                // The accumulator is cleared at the end of every epoch
+                if let Some(post) = self.ctx.post().read::<Decimal>(key)? {
+                    if post.is_sign_negative() {
+                        tracing::info!(
+                            "PoS rewards accumulator of validator {} must not \
+                             be negative, got {}",
+                            validator,
+                            post
+                        );
+                        return Ok(false);
+                    }
+                    if post < pre {
+                        tracing::info!(
+                            "PoS rewards accumulator of validator {} must not \
+                             decrease within an epoch, from {} to {}",
+                            validator,
+                            pre,
+                            post
+                        );
+                        return Ok(false);
+                    }
+                    rewards_increment += post - pre;
+                }
+            } else if let Some(validator) =
+                is_validator_commission_rate_sub_key(key)
+            {
+                commission_changes.insert(validator);
+            } else if let Some(validator) =
+                is_validator_commission_rate_announcement_key(key)
+            {
+                if !self.is_valid_commission_rate_announcement(
+                    validator,
+                    current_epoch,
+                )? {
+                    return Ok(false);
+                }
+            } else if is_total_burned_key(key) {
+                if !self.is_valid_total_burned_change(key)? {
+                    return Ok(false);
+                }
+            } else if let Some(id) = is_pending_multisig_bond_key(key) {
+                if !self.is_valid_multisig_bond_change(
+                    key,
+                    id,
+                    verifiers,
+                    current_epoch,
+                )? {
+                    return Ok(false);
+                }
+            } else if is_multisig_bond_counter_key(key) {
+                if !self.is_valid_multisig_bond_counter_change(key)? {
+                    return Ok(false);
+                }
+            } else if is_pending_multisig_bonds_key(key) {
+                tracing::info!(
+                    "PoS multisig bonds key {} must not be changed by a tx",
+                    key
+                );
+                return Ok(false);
             } else if key.segments.get(0) == Some(&addr.to_db_key()) {
                 // Unknown changes to this address space are disallowed
                 // tracing::info!("PoS unrecognized key change {} rejected",
@@ -134,7 +691,50 @@ where
             }
         }
 
-        let _params = read_pos_params(&self.ctx.pre())?;
+        if !self.is_valid_pos_events(keys_changed)? {
+            return Ok(false);
+        }
+
+        // The fractions of a block's rewards of all the consensus validators
+        // must not sum up to more than the whole rewards
+        if rewards_increment > Decimal::ONE {
+            tracing::info!(
+                "PoS rewards accumulator increased by {} in total, which is \
+                 more than the whole block rewards",
+                rewards_increment
+            );
+            return Ok(false);
+        }
+
+        // A commission change tx may only change the commission rate of the
+        // validator in its data
+        if let Some(PosTxData::ChangeCommission(change)) =
+            self.pos_tx_data(tx_data)?
+        {
+            if let Some(validator) = commission_changes
+                .iter()
+                .find(|validator| **validator != &change.validator)
+            {
+                tracing::info!(
+                    "PoS commission change tx for validator {} must not \
+                     change the commission rate of validator {}",
+                    change.validator,
+                    validator
+                );
+                return Ok(false);
+            }
+        }
+
+        let params = read_pos_params(&self.ctx.pre())?;
+        for validator in commission_changes {
+            if !self.is_valid_commission_rate_change(
+                validator,
+                &params,
+                current_epoch,
+            )? {
+                return Ok(false);
+            }
+        }
         // let errors = validate(&params, changes, current_epoch);
         // Ok(if errors.is_empty() {
         //     true
@@ -149,6 +749,152 @@ where
     }
 }
 
+/// Proof-of-Stake slash pool validity predicate. The slash pool is closed
+/// to txs, except for a debit of its native token balance executed from an
+/// accepted governance proposal.
+pub struct SlashPoolVp<'a, DB, H, CA>
+where
+    DB: ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    /// Context to interact with the host structures.
+    pub ctx: Ctx<'a, DB, H, CA>,
+}
+
+impl<'a, DB, H, CA> SlashPoolVp<'a, DB, H, CA>
+where
+    DB: 'static + ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    /// Instantiate a `SlashPoolVp`.
+    pub fn new(ctx: Ctx<'a, DB, H, CA>) -> Self {
+        Self { ctx }
+    }
+}
+
+impl<'a, DB, H, CA> NativeVp for SlashPoolVp<'a, DB, H, CA>
+where
+    DB: 'static + ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    type Error = Error;
+
+    const ADDR: InternalAddress = InternalAddress::PosSlashPool;
+
+    fn validate_tx(
+        &self,
+        tx_data: &[u8],
+        keys_changed: &BTreeSet<Key>,
+        _verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let slash_pool = slash_pool_address();
+        let native_token = self.ctx.pre().get_native_token()?;
+
+        for key in keys_changed {
+            if token::is_balance_key(&native_token, key) == Some(&slash_pool) {
+                let pre: token::Amount = self.ctx.pre().read_or_default(key)?;
+                let post: token::Amount =
+                    self.ctx.post().read_or_default(key)?;
+                if post > pre {
+                    tracing::info!(
+                        "PoS slash pool balance may only be debited by a tx, \
+                         got a change from {} to {}",
+                        pre,
+                        post
+                    );
+                    return Ok(false);
+                }
+                // The slashed tokens may only be redistributed by an accepted
+                // governance proposal
+                if !governance::utils::is_proposal_accepted(
+                    &self.ctx.pre(),
+                    tx_data,
+                )
+                .map_err(Error::NativeVpError)?
+                {
+                    tracing::info!(
+                        "PoS slash pool balance change rejected, it must be \
+                         executed from an accepted governance proposal"
+                    );
+                    return Ok(false);
+                }
+            } else if key.find_addresses().contains(&slash_pool) {
+                tracing::info!("PoS slash pool key change {} rejected", key);
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Proof-of-Stake burn address validity predicate. The burned tokens can
+/// never leave the burn address, so only a credit of its balances is allowed.
+pub struct PosBurnVp<'a, DB, H, CA>
+where
+    DB: ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    /// Context to interact with the host structures.
+    pub ctx: Ctx<'a, DB, H, CA>,
+}
+
+impl<'a, DB, H, CA> PosBurnVp<'a, DB, H, CA>
+where
+    DB: 'static + ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    /// Instantiate a `PosBurnVp`.
+    pub fn new(ctx: Ctx<'a, DB, H, CA>) -> Self {
+        Self { ctx }
+    }
+}
+
+impl<'a, DB, H, CA> NativeVp for PosBurnVp<'a, DB, H, CA>
+where
+    DB: 'static + ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    type Error = Error;
+
+    const ADDR: InternalAddress = InternalAddress::PosBurn;
+
+    fn validate_tx(
+        &self,
+        _tx_data: &[u8],
+        keys_changed: &BTreeSet<Key>,
+        _verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let burn = burn_address();
+
+        for key in keys_changed {
+            if token::is_any_token_balance_key(key) == Some(&burn) {
+                let pre: token::Amount = self.ctx.pre().read_or_default(key)?;
+                let post: token::Amount =
+                    self.ctx.post().read_or_default(key)?;
+                if post < pre {
+                    tracing::info!(
+                        "PoS burn address balance must not be debited, got a \
+                         change from {} to {}",
+                        pre,
+                        post
+                    );
+                    return Ok(false);
+                }
+            } else if key.find_addresses().contains(&burn) {
+                tracing::info!("PoS burn address key change {} rejected", key);
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
 impl From<native_vp::Error> for Error {
     fn from(err: native_vp::Error) -> Self {
         Self::NativeVpError(err)
@@ -1,6 +1,7 @@
 //! The ledger's protocol
 use std::collections::BTreeSet;
 use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use thiserror::Error;
@@ -13,7 +14,7 @@ use crate::ledger::native_vp::parameters::{self, ParametersVp};
 use crate::ledger::native_vp::replay_protection::ReplayProtectionVp;
 use crate::ledger::native_vp::slash_fund::SlashFundVp;
 use crate::ledger::native_vp::{self, NativeVp};
-use crate::ledger::pos::{self, PosVP};
+use crate::ledger::pos::{self, PosBurnVp, PosVP, SlashPoolVp};
 use crate::ledger::storage::write_log::WriteLog;
 use crate::ledger::storage::{DBIter, Storage, StorageHasher, DB};
 use crate::proto::{self, Tx};
@@ -76,6 +77,9 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// If the given tx is a successfully decrypted payload apply the necessary
 /// vps. Otherwise, we include the tx on chain with the gas charge added
 /// but no further validations.
+///
+/// With `fail_fast`, the vps stop running once any of them rejects the tx
+/// (see [`check_vps`]).
 #[allow(clippy::too_many_arguments)]
 pub fn apply_tx<D, H, CA>(
     tx: TxType,
@@ -86,6 +90,7 @@ pub fn apply_tx<D, H, CA>(
     storage: &Storage<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
     tx_wasm_cache: &mut TxCache<CA>,
+    fail_fast: bool,
 ) -> Result<TxResult>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -121,6 +126,7 @@ where
                 write_log,
                 &verifiers,
                 vp_wasm_cache,
+                fail_fast,
                 #[cfg(not(feature = "mainnet"))]
                 has_valid_pow,
             )?;
@@ -131,6 +137,7 @@ where
             let initialized_accounts = write_log.get_initialized_accounts();
             let changed_keys = write_log.get_keys();
             let ibc_event = write_log.take_ibc_event();
+            let pos_events = write_log.take_pos_events();
 
             Ok(TxResult {
                 gas_used,
@@ -138,6 +145,7 @@ where
                 vps_result,
                 initialized_accounts,
                 ibc_event,
+                pos_events,
             })
         }
         _ => {
@@ -182,7 +190,10 @@ where
     .map_err(Error::TxRunnerError)
 }
 
-/// Check the acceptance of a transaction by validity predicates
+/// Check the acceptance of a transaction by validity predicates. With
+/// `fail_fast`, the VPs that haven't started yet are skipped once any VP
+/// rejects. The gas cost then depends on the order in which the VPs ran, so
+/// this must not be used when the gas cost must be deterministic.
 #[allow(clippy::too_many_arguments)]
 fn check_vps<D, H, CA>(
     tx: &Tx,
@@ -192,6 +203,7 @@ fn check_vps<D, H, CA>(
     write_log: &WriteLog,
     verifiers_from_tx: &BTreeSet<Address>,
     vp_wasm_cache: &mut VpCache<CA>,
+    fail_fast: bool,
     #[cfg(not(feature = "mainnet"))]
     // This is true when the wrapper of this tx contained a valid
     // `testnet_pow::Solution`
@@ -216,6 +228,7 @@ where
         write_log,
         initial_gas,
         vp_wasm_cache,
+        fail_fast,
         #[cfg(not(feature = "mainnet"))]
         has_valid_pow,
     )?;
@@ -228,7 +241,8 @@ where
     Ok(vps_result)
 }
 
-/// Execute verifiers' validity predicates
+/// Execute verifiers' validity predicates. With `fail_fast`, stop starting
+/// new VPs as soon as one of them rejects.
 #[allow(clippy::too_many_arguments)]
 fn execute_vps<D, H, CA>(
     verifiers: BTreeSet<Address>,
@@ -239,6 +253,7 @@ fn execute_vps<D, H, CA>(
     write_log: &WriteLog,
     initial_gas: u64,
     vp_wasm_cache: &mut VpCache<CA>,
+    fail_fast: bool,
     #[cfg(not(feature = "mainnet"))]
     // This is true when the wrapper of this tx contained a valid
     // `testnet_pow::Solution`
@@ -249,9 +264,14 @@ where
     H: 'static + StorageHasher + Sync,
     CA: 'static + WasmCacheAccess + Sync,
 {
+    // Set once any VP rejects, to skip the remaining VPs with `fail_fast`
+    let has_rejection = AtomicBool::new(false);
     verifiers
         .par_iter()
         .try_fold(VpsResult::default, |mut result, addr| {
+            if fail_fast && has_rejection.load(Ordering::Relaxed) {
+                return Ok(result);
+            }
             let mut gas_meter = VpGasMeter::new(initial_gas);
             let accept = match &addr {
                 Address::Implicit(_) | Address::Established(_) => {
@@ -351,11 +371,25 @@ where
                             result
                         }
                         InternalAddress::PosSlashPool => {
+                            // The slash pool only accepts governance
+                            // redistributions of the slashed tokens
+                            let slash_pool = SlashPoolVp { ctx };
+                            let result = slash_pool
+                                .validate_tx(tx_data, &keys_changed, &verifiers)
+                                .map_err(Error::PosNativeVpError);
+                            // Take the gas meter back out of the context
+                            gas_meter = slash_pool.ctx.gas_meter.into_inner();
+                            result
+                        }
+                        InternalAddress::PosBurn => {
+                            // The burned tokens can never be spent
+                            let burn = PosBurnVp { ctx };
+                            let result = burn
+                                .validate_tx(tx_data, &keys_changed, &verifiers)
+                                .map_err(Error::PosNativeVpError);
                             // Take the gas meter back out of the context
-                            gas_meter = ctx.gas_meter.into_inner();
-                            Err(Error::AccessForbidden(
-                                (*internal_addr).clone(),
-                            ))
+                            gas_meter = burn.ctx.gas_meter.into_inner();
+                            result
                         }
                         InternalAddress::Governance => {
                             let governance = GovernanceVp { ctx };
@@ -416,6 +450,7 @@ where
             match accept {
                 Ok(accepted) => {
                     if !accepted {
+                        has_rejection.store(true, Ordering::Relaxed);
                         result.rejected_vps.insert(addr.clone());
                     } else {
                         result.accepted_vps.insert(addr.clone());
@@ -425,6 +460,7 @@ where
                 Err(err) => match err {
                     Error::GasError(_) => Err(err),
                     _ => {
+                        has_rejection.store(true, Ordering::Relaxed);
                         result.rejected_vps.insert(addr.clone());
                         result.errors.push((addr.clone(), err.to_string()));
                         Ok(result)
@@ -466,3 +502,134 @@ fn merge_vp_results(
         errors,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::gas::VpsGas;
+    use crate::ledger::storage::testing::TestStorage;
+    use crate::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+
+    /// Make a VPs result with the given gas used by a single VP
+    fn vps_result_with_gas(gas: u64) -> VpsResult {
+        let mut vp_gas_meter = VpGasMeter::new(0);
+        vp_gas_meter.current_gas = gas;
+        let mut gas_used = VpsGas::default();
+        gas_used.set(&vp_gas_meter).unwrap();
+        VpsResult {
+            gas_used,
+            ..Default::default()
+        }
+    }
+
+    /// Get the gas that would be charged to the tx for the given VPs result
+    fn charged_gas(result: &VpsResult) -> u64 {
+        let mut block_gas_meter = BlockGasMeter::default();
+        block_gas_meter.add_vps_gas(&result.gas_used).unwrap();
+        block_gas_meter.get_current_transaction_gas()
+    }
+
+    #[test]
+    fn test_merge_vp_results_gas() {
+        let a = vps_result_with_gas(100);
+        let b = vps_result_with_gas(500);
+
+        // The maximum is charged fully and the rest is divided by the
+        // parallel gas divider
+        let merged = merge_vp_results(a.clone(), b.clone(), 0).unwrap();
+        assert_eq!(charged_gas(&merged), 500 + 100 / 10);
+
+        // The result must not depend on the order of the merge
+        let merged = merge_vp_results(b, a, 0).unwrap();
+        assert_eq!(charged_gas(&merged), 500 + 100 / 10);
+    }
+
+    #[test]
+    fn test_merge_vp_results_gas_error() {
+        let a = vps_result_with_gas(9_000_000_000);
+        let b = vps_result_with_gas(9_000_000_000);
+
+        // Fits in the tx gas limit without any initial gas
+        merge_vp_results(a.clone(), b.clone(), 0).unwrap();
+
+        // Exceeds the tx gas limit with the initial gas added
+        let err = merge_vp_results(a, b, 1_000_000_000)
+            .expect_err("Merging VP results should fail");
+        assert_matches!(err, Error::GasError(gas::Error::GasOverflow));
+    }
+
+    #[test]
+    fn test_merge_vp_results_rejected() {
+        let addr_1 = established_address_1();
+        let addr_2 = established_address_2();
+
+        let mut a = vps_result_with_gas(1);
+        a.rejected_vps.insert(addr_1.clone());
+        a.errors.push((addr_1.clone(), "error 1".to_string()));
+        let mut b = vps_result_with_gas(2);
+        b.rejected_vps.insert(addr_2.clone());
+        b.errors.push((addr_2.clone(), "error 2".to_string()));
+
+        let merged = merge_vp_results(a, b, 0).unwrap();
+        assert_eq!(
+            merged.rejected_vps,
+            BTreeSet::from([addr_1.clone(), addr_2.clone()])
+        );
+        assert!(merged.accepted_vps.is_empty());
+        assert_eq!(
+            merged.errors,
+            vec![
+                (addr_1, "error 1".to_string()),
+                (addr_2, "error 2".to_string())
+            ]
+        );
+    }
+
+    /// Test that with `fail_fast`, the remaining VPs are skipped once one of
+    /// them rejects the tx
+    #[test]
+    fn test_execute_vps_fail_fast() {
+        let storage = TestStorage::default();
+        let write_log = WriteLog::default();
+        let tx = Tx::new(vec![], None, storage.chain_id.clone(), None);
+        let tx_index = TxIndex::default();
+        let (mut vp_wasm_cache, _vp_cache_dir) =
+            wasm::compilation_cache::common::testing::cache();
+        // Established addresses without a VP in storage are rejected
+        let verifiers =
+            BTreeSet::from([established_address_1(), established_address_2()]);
+        // Run the VPs on a single thread, so that the second VP only starts
+        // after the first one has rejected
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut run_vps = |fail_fast| {
+            pool.install(|| {
+                execute_vps(
+                    verifiers.clone(),
+                    BTreeSet::new(),
+                    &tx,
+                    &tx_index,
+                    &storage,
+                    &write_log,
+                    0,
+                    &mut vp_wasm_cache,
+                    fail_fast,
+                    #[cfg(not(feature = "mainnet"))]
+                    false,
+                )
+            })
+            .unwrap()
+        };
+
+        let result = run_vps(false);
+        assert_eq!(result.rejected_vps, verifiers);
+
+        let result = run_vps(true);
+        assert_eq!(result.rejected_vps.len(), 1);
+        assert!(result.accepted_vps.is_empty());
+    }
+}
@@ -13,9 +13,10 @@ use vp::VP;
 // Re-export to show in rustdoc!
 pub use vp::{Pos, Vp};
 
+use super::gas::MIN_STORAGE_GAS;
 use super::storage::{DBIter, StorageHasher, DB};
-use super::storage_api;
-use crate::types::storage::BlockHeight;
+use super::storage_api::{self, StorageRead};
+use crate::types::storage::{BlockHeight, Key};
 
 #[macro_use]
 mod router;
@@ -91,6 +92,42 @@ pub fn require_no_data(request: &RequestQuery) -> storage_api::Result<()> {
     Ok(())
 }
 
+/// For queries that iterate over storage, check that the estimated gas is
+/// within the configured query gas limit, otherwise return an error.
+pub fn require_gas_within_limit<D, H>(
+    ctx: &RequestCtx<'_, D, H>,
+    gas: u64,
+) -> storage_api::Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    match ctx.query_gas_limit {
+        Some(limit) if gas > limit => Err(storage_api::Error::new(format!(
+            "The estimated gas of the query {} exceeds the query gas limit {}",
+            gas, limit
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Estimate the gas needed to iterate over all the storage keys under the
+/// given prefix. Every key is charged with the minimum storage access gas.
+pub fn estimate_prefix_iter_gas<S>(
+    storage: &S,
+    prefix: &Key,
+) -> storage_api::Result<u64>
+where
+    S: StorageRead,
+{
+    let mut num_keys: u64 = 0;
+    for result in storage_api::iter_prefix_bytes(storage, prefix)? {
+        let _ = result?;
+        num_keys += 1;
+    }
+    Ok(num_keys * MIN_STORAGE_GAS)
+}
+
 #[cfg(any(feature = "tendermint-rpc", feature = "tendermint-rpc-abcipp",))]
 /// Provides [`Client`] implementation for Tendermint RPC client
 pub mod tm {
@@ -241,6 +278,7 @@ mod testing {
                 vp_wasm_cache: self.vp_wasm_cache.clone(),
                 tx_wasm_cache: self.tx_wasm_cache.clone(),
                 storage_read_past_height_limit: None,
+                query_gas_limit: None,
             };
             let response = self.rpc.handle(ctx, &request).unwrap();
             Ok(response)
@@ -17,6 +17,7 @@ use crate::tendermint::merkle::proof::Proof;
 use crate::types::storage::{self, Epoch, PrefixValue};
 #[cfg(any(test, feature = "async-client"))]
 use crate::types::transaction::TxResult;
+use crate::vm::WasmCacheStats;
 
 type Conversion = (
     Address,
@@ -56,6 +57,9 @@ router! {SHELL,
     // was the transaction applied?
     ( "applied" / [tx_hash: Hash] ) -> Option<Event> = applied,
 
+    // Usage statistics of the wasm compilation caches
+    ( "wasm_cache_stats" ) -> WasmCacheStats = wasm_cache_stats,
+
 }
 
 // Handlers:
@@ -93,6 +97,7 @@ where
         &ctx.wl_storage.storage,
         &mut ctx.vp_wasm_cache,
         &mut ctx.tx_wasm_cache,
+        false,
     )
     .into_storage_result()?;
     let data = data.try_to_vec().into_storage_result()?;
@@ -172,6 +177,28 @@ where
     unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
 }
 
+#[cfg(feature = "wasm-runtime")]
+fn wasm_cache_stats<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+) -> storage_api::Result<WasmCacheStats>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(WasmCacheStats::new(&ctx.vp_wasm_cache, &ctx.tx_wasm_cache))
+}
+
+#[cfg(not(feature = "wasm-runtime"))]
+fn wasm_cache_stats<D, H>(
+    _ctx: RequestCtx<'_, D, H>,
+) -> storage_api::Result<WasmCacheStats>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!("Wasm cache statistics require \"wasm-runtime\" feature.")
+}
+
 fn epoch<D, H>(ctx: RequestCtx<'_, D, H>) -> storage_api::Result<Epoch>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
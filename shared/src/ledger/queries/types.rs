@@ -32,6 +32,9 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `query_gas_limit`. When set, will limit the
+    /// estimated gas that a single query may use.
+    pub query_gas_limit: Option<u64>,
 }
 
 /// A `Router` handles parsing read-only query requests and dispatching them to
@@ -2,19 +2,30 @@ use std::collections::{HashMap, HashSet};
 
 use namada_core::ledger::storage_api::collections::lazy_map;
 use namada_core::ledger::storage_api::OptionExt;
+use namada_proof_of_stake::storage::{
+    bonds_for_source_prefix, bonds_prefix, unbonds_for_source_prefix,
+    unbonds_prefix,
+};
 use namada_proof_of_stake::types::{
-    BondId, BondsAndUnbondsDetails, CommissionPair, Slash, WeightedValidator,
+    BondId, BondsAndUnbondsDetails, CommissionPair, Slash, ValidatorState,
+    WeightedValidator,
 };
 use namada_proof_of_stake::{
     self, below_capacity_validator_set_handle, bond_amount, bond_handle,
-    consensus_validator_set_handle, find_all_slashes,
-    find_delegation_validators, find_delegations, read_all_validator_addresses,
+    compute_annual_staking_yield, consensus_validator_set_handle,
+    find_all_slashes, find_delegation_validators, find_delegations,
+    read_all_validator_addresses, read_consensus_validator_set_voting_powers,
     read_pos_params, read_total_stake,
     read_validator_max_commission_rate_change, read_validator_stake,
     unbond_handle, validator_commission_rate_handle, validator_slashes_handle,
+    validator_state_handle,
 };
+use rust_decimal::Decimal;
 
 use crate::ledger::queries::types::RequestCtx;
+use crate::ledger::queries::{
+    estimate_prefix_iter_gas, require_gas_within_limit,
+};
 use crate::ledger::storage::{DBIter, StorageHasher, DB};
 use crate::ledger::storage_api;
 use crate::types::address::Address;
@@ -22,6 +33,7 @@ use crate::types::storage::Epoch;
 use crate::types::token;
 
 type AmountPair = (token::Amount, token::Amount);
+type VotingPowers = Vec<(Address, u64)>;
 
 // PoS validity predicate queries
 router! {POS,
@@ -39,6 +51,12 @@ router! {POS,
 
         ( "commission" / [validator: Address] / [epoch: opt Epoch] )
             -> Option<CommissionPair> = validator_commission,
+
+        ( "state" / [validator: Address] / [epoch: opt Epoch] )
+            -> Option<ValidatorState> = validator_state,
+
+        ( "commission_earned" / [validator: Address] / [epoch: opt Epoch] )
+            -> Decimal = validator_commission_earned,
     },
 
     ( "validator_set" ) = {
@@ -48,6 +66,9 @@ router! {POS,
         ( "below_capacity" / [epoch: opt Epoch] )
             -> HashSet<WeightedValidator> = below_capacity_validator_set,
 
+        ( "consensus_voting_powers" / [epoch: opt Epoch] )
+            -> VotingPowers = consensus_voting_powers,
+
         // TODO: add "below_threshold"
     },
 
@@ -78,6 +99,12 @@ router! {POS,
     ( "bonds_and_unbonds" / [source: opt Address] / [validator: opt Address] )
         -> BondsAndUnbondsDetails = bonds_and_unbonds,
 
+    ( "annual_staking_yield" / [source: Address] / [validator: Address] / [window_epochs: u64] / [epoch: opt Epoch] )
+        -> Decimal = annual_staking_yield,
+
+    ( "delegator_reward_share" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
+        -> Decimal = delegator_reward_share,
+
     ( "all_slashes" ) -> HashMap<Address, Vec<Slash>> = slashes,
 
     ( "is_delegator" / [addr: Address ] / [epoch: opt Epoch] ) -> bool = is_delegator,
@@ -181,6 +208,43 @@ where
     read_validator_stake(ctx.wl_storage, &params, &validator, epoch)
 }
 
+/// Get the fraction of the validator's rewards earned as a commission at the
+/// given epoch. If no epoch is given, defaults to last committed epoch.
+fn validator_commission_earned<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    validator: Address,
+    epoch: Option<Epoch>,
+) -> storage_api::Result<Decimal>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = epoch.unwrap_or(ctx.wl_storage.storage.last_epoch);
+    let params = read_pos_params(ctx.wl_storage)?;
+    namada_proof_of_stake::validator_commission_earned(
+        ctx.wl_storage,
+        &params,
+        &validator,
+        epoch,
+    )
+}
+
+/// Get the validator state at the given epoch, if any. If no epoch is given,
+/// defaults to last committed epoch.
+fn validator_state<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    validator: Address,
+    epoch: Option<Epoch>,
+) -> storage_api::Result<Option<ValidatorState>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = epoch.unwrap_or(ctx.wl_storage.storage.last_epoch);
+    let params = read_pos_params(ctx.wl_storage)?;
+    validator_state_handle(&validator).get(ctx.wl_storage, epoch, &params)
+}
+
 /// Get all the validator in the consensus set with their bonded stake.
 fn consensus_validator_set<D, H>(
     ctx: RequestCtx<'_, D, H>,
@@ -245,6 +309,21 @@ where
         .collect()
 }
 
+/// Get all the validators in the consensus set with their Tendermint voting
+/// power.
+fn consensus_voting_powers<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    epoch: Option<Epoch>,
+) -> storage_api::Result<VotingPowers>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = epoch.unwrap_or(ctx.wl_storage.storage.last_epoch);
+    let params = read_pos_params(ctx.wl_storage)?;
+    read_consensus_validator_set_voting_powers(ctx.wl_storage, &params, epoch)
+}
+
 /// Get the total stake in PoS system at the given epoch or current when `None`.
 fn total_stake<D, H>(
     ctx: RequestCtx<'_, D, H>,
@@ -294,6 +373,55 @@ where
         .ok_or_err_msg("Cannot find bond")
 }
 
+/// Estimate the annual staking yield of a bond from the rewards it received
+/// in the given number of epochs preceding the given epoch (defaults to the
+/// current epoch).
+fn annual_staking_yield<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    source: Address,
+    validator: Address,
+    window_epochs: u64,
+    epoch: Option<Epoch>,
+) -> storage_api::Result<Decimal>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let params = read_pos_params(ctx.wl_storage)?;
+    let epoch = epoch.unwrap_or(ctx.wl_storage.storage.last_epoch);
+    compute_annual_staking_yield(
+        ctx.wl_storage,
+        &params,
+        &source,
+        &validator,
+        epoch,
+        window_epochs,
+    )
+}
+
+/// Get the fraction of the validator's rewards received by the bond at the
+/// given epoch. If no epoch is given, defaults to last committed epoch.
+fn delegator_reward_share<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    source: Address,
+    validator: Address,
+    epoch: Option<Epoch>,
+) -> storage_api::Result<Decimal>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = epoch.unwrap_or(ctx.wl_storage.storage.last_epoch);
+    let params = read_pos_params(ctx.wl_storage)?;
+    namada_proof_of_stake::delegator_reward_share(
+        ctx.wl_storage,
+        &params,
+        &source,
+        &validator,
+        epoch,
+    )
+}
+
 fn bond_with_slashing<D, H>(
     ctx: RequestCtx<'_, D, H>,
     source: Address,
@@ -404,6 +532,22 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
+    if ctx.query_gas_limit.is_some()
+        && (source.is_none() || validator.is_none())
+    {
+        // Without a full bond ID, all the bonds and unbonds of the source, or
+        // of all the sources if it's not given, have to be iterated
+        let (bonds_iter_prefix, unbonds_iter_prefix) = match source.as_ref() {
+            Some(source) => (
+                bonds_for_source_prefix(source),
+                unbonds_for_source_prefix(source),
+            ),
+            None => (bonds_prefix(), unbonds_prefix()),
+        };
+        let gas = estimate_prefix_iter_gas(ctx.wl_storage, &bonds_iter_prefix)?
+            + estimate_prefix_iter_gas(ctx.wl_storage, &unbonds_iter_prefix)?;
+        require_gas_within_limit(&ctx, gas)?;
+    }
     namada_proof_of_stake::bonds_and_unbonds(ctx.wl_storage, source, validator)
 }
 
@@ -24,6 +24,7 @@ use crate::types::ibc::IbcEvent;
 use crate::types::internal::HostEnvResult;
 use crate::types::key::*;
 use crate::types::storage::{Key, TxIndex};
+use crate::types::transaction::pos::PosEvent;
 use crate::vm::memory::VmMemory;
 use crate::vm::prefix_iter::{PrefixIteratorId, PrefixIterators};
 use crate::vm::{HostRef, MutHostRef};
@@ -59,6 +60,8 @@ pub enum TxRuntimeError {
     NumConversionError(TryFromIntError),
     #[error("Memory error: {0}")]
     MemoryError(Box<dyn std::error::Error + Sync + Send + 'static>),
+    #[error("PoS event type {0} doesn't match the event {1:?}")]
+    PosEventTypeMismatch(String, PosEvent),
 }
 
 type TxResult<T> = std::result::Result<T, TxRuntimeError>;
@@ -989,6 +992,46 @@ where
     tx_add_gas(env, gas)
 }
 
+/// Emitting a PoS event function exposed to the wasm VM Tx environment.
+/// The given PoS event, whose kind must match the given event type, will be
+/// added to the write log. The PoS address is inserted into the verifiers, so
+/// that the PoS VP checks the event against the changes applied by the tx.
+pub fn tx_emit_pos_event<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    event_type_ptr: u64,
+    event_type_len: u64,
+    event_data_ptr: u64,
+    event_data_len: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (event_type, gas) = env
+        .memory
+        .read_string(event_type_ptr, event_type_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_add_gas(env, gas)?;
+    let (event_data, gas) = env
+        .memory
+        .read_bytes(event_data_ptr, event_data_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_add_gas(env, gas)?;
+    let event: PosEvent = BorshDeserialize::try_from_slice(&event_data)
+        .map_err(TxRuntimeError::EncodingError)?;
+    if event.kind() != event_type {
+        return Err(TxRuntimeError::PosEventTypeMismatch(event_type, event));
+    }
+
+    let verifiers = unsafe { env.ctx.verifiers.get() };
+    verifiers.insert(address::POS);
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let gas = write_log.set_pos_event(event);
+    tx_add_gas(env, gas)
+}
+
 /// Storage read prior state (before tx execution) function exposed to the wasm
 /// VM VP environment. It will try to read from the storage.
 ///
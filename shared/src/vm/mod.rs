@@ -4,6 +4,7 @@ use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::slice;
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use wasmparser::{Validator, WasmFeatures};
 
 pub mod host_env;
@@ -43,6 +44,43 @@ pub enum WasmValidationError {
     ForbiddenWasmFeatures(wasmparser::BinaryReaderError),
 }
 
+/// Usage statistics of the VP and transaction WASM compilation caches
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct WasmCacheStats {
+    /// Number of VP modules found in the in-memory cache
+    pub vp_hit_count: u64,
+    /// Number of VP modules not found in the in-memory cache
+    pub vp_miss_count: u64,
+    /// Number of VP modules evicted from the in-memory cache
+    pub vp_eviction_count: u64,
+    /// Number of transaction modules found in the in-memory cache
+    pub tx_hit_count: u64,
+    /// Number of transaction modules not found in the in-memory cache
+    pub tx_miss_count: u64,
+    /// Number of transaction modules evicted from the in-memory cache
+    pub tx_eviction_count: u64,
+}
+
+impl WasmCacheStats {
+    /// Read the usage statistics of the given caches
+    #[cfg(feature = "wasm-runtime")]
+    pub fn new<A: WasmCacheAccess>(
+        vp_cache: &wasm::VpCache<A>,
+        tx_cache: &wasm::TxCache<A>,
+    ) -> Self {
+        Self {
+            vp_hit_count: vp_cache.get_hit_count(),
+            vp_miss_count: vp_cache.get_miss_count(),
+            vp_eviction_count: vp_cache.get_eviction_count(),
+            tx_hit_count: tx_cache.get_hit_count(),
+            tx_miss_count: tx_cache.get_miss_count(),
+            tx_eviction_count: tx_cache.get_eviction_count(),
+        }
+    }
+}
+
 /// WASM Cache access level, used to limit dry-ran transactions to read-only
 /// cache access.
 pub trait WasmCacheAccess: Clone + std::fmt::Debug + Default {
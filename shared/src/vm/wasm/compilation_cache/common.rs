@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::thread::sleep;
 use std::time::Duration;
@@ -32,6 +33,8 @@ pub struct Cache<N: CacheName, A: WasmCacheAccess> {
     progress: Arc<RwLock<HashMap<Hash, Compilation>>>,
     /// In-memory LRU cache of compiled modules
     in_memory: Arc<RwLock<MemoryCache>>,
+    /// Usage statistics of the in-memory cache
+    stats: Arc<CacheStats>,
     /// The cache's name
     name: PhantomData<N>,
     /// Cache access level
@@ -47,6 +50,17 @@ pub trait CacheName: Clone + std::fmt::Debug {
 /// In-memory LRU cache of compiled modules
 type MemoryCache = CLruCache<Hash, Module, RandomState, ModuleCacheScale>;
 
+/// Usage statistics of the in-memory cache
+#[derive(Debug, Default)]
+struct CacheStats {
+    /// Number of modules found in the in-memory cache
+    hits: AtomicU64,
+    /// Number of modules not found in the in-memory cache
+    misses: AtomicU64,
+    /// Number of modules evicted from the in-memory cache
+    evictions: AtomicU64,
+}
+
 /// Compilation progress
 #[derive(Debug)]
 enum Compilation {
@@ -94,6 +108,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             dir,
             progress: Default::default(),
             in_memory,
+            stats: Default::default(),
             name: Default::default(),
             access: Default::default(),
         }
@@ -124,6 +139,21 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         self.in_memory.read().unwrap().weight()
     }
 
+    /// Get the number of modules that were found in the in-memory cache
+    pub fn get_hit_count(&self) -> u64 {
+        self.stats.hits.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of modules that were not found in the in-memory cache
+    pub fn get_miss_count(&self) -> u64 {
+        self.stats.misses.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of modules that were evicted from the in-memory cache
+    pub fn get_eviction_count(&self) -> u64 {
+        self.stats.evictions.load(Ordering::Relaxed)
+    }
+
     /// Get a WASM module from LRU cache, from a file or compile it and cache
     /// it. Updates the position in the LRU cache.
     fn get(
@@ -137,9 +167,11 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                 N::name(),
                 hash.to_string()
             );
+            self.stats.hits.fetch_add(1, Ordering::Relaxed);
             return Ok(Some((module.clone(), store())));
         }
         drop(in_memory);
+        self.stats.misses.fetch_add(1, Ordering::Relaxed);
 
         let mut iter = 0;
         loop {
@@ -164,8 +196,12 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                         hash.to_string()
                     );
                     // Put into cache, ignore result if it's full
-                    let _ =
-                        in_memory.put_with_weight(hash.clone(), module.clone());
+                    put_into_memory(
+                        &mut in_memory,
+                        &self.stats,
+                        hash.clone(),
+                        module.clone(),
+                    );
 
                     return Ok(Some((module, store)));
                 }
@@ -201,8 +237,12 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     // Put into cache, ignore the result (fails if the module
                     // cannot fit into the cache)
                     let mut in_memory = self.in_memory.write().unwrap();
-                    let _ =
-                        in_memory.put_with_weight(hash.clone(), module.clone());
+                    put_into_memory(
+                        &mut in_memory,
+                        &self.stats,
+                        hash.clone(),
+                        module.clone(),
+                    );
 
                     return Ok(Some((module, store)));
                 }
@@ -223,9 +263,11 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                 N::name(),
                 hash.to_string()
             );
+            self.stats.hits.fetch_add(1, Ordering::Relaxed);
             return Ok(Some((module.clone(), store())));
         }
         drop(in_memory);
+        self.stats.misses.fetch_add(1, Ordering::Relaxed);
 
         let mut iter = 0;
         loop {
@@ -321,7 +363,12 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
 
                     // Put into cache, ignore result if it's full
                     let mut in_memory = self.in_memory.write().unwrap();
-                    let _ = in_memory.put_with_weight(hash, module.clone());
+                    put_into_memory(
+                        &mut in_memory,
+                        &self.stats,
+                        hash,
+                        module.clone(),
+                    );
 
                     Ok(Some((module, store)))
                 }
@@ -424,12 +471,32 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             dir: self.dir.clone(),
             progress: self.progress.clone(),
             in_memory: self.in_memory.clone(),
+            stats: self.stats.clone(),
             name: Default::default(),
             access: Default::default(),
         }
     }
 }
 
+/// Put a module into the in-memory cache, ignoring the result if it cannot
+/// fit, and count the modules that were evicted to make space for it.
+fn put_into_memory(
+    in_memory: &mut MemoryCache,
+    stats: &CacheStats,
+    hash: Hash,
+    module: Module,
+) {
+    let len_pre = in_memory.len();
+    let expected_len = match in_memory.put_with_weight(hash, module) {
+        // A new module was added
+        Ok(None) => len_pre + 1,
+        // An existing module was replaced or the module doesn't fit
+        Ok(Some(_)) | Err(_) => len_pre,
+    };
+    let evicted = expected_len.saturating_sub(in_memory.len());
+    stats.evictions.fetch_add(evicted as u64, Ordering::Relaxed);
+}
+
 fn exponential_backoff(iteration: u64) {
     sleep(Duration::from_millis(u64::pow(2, iteration as u32) * 10))
 }
@@ -683,6 +750,12 @@ mod test {
                     None,
                     "The module should have been popped from memory"
                 );
+
+                // Both modules missed the in-memory cache and the previous
+                // module was evicted
+                assert_eq!(cache.get_hit_count(), 0);
+                assert_eq!(cache.get_miss_count(), 2);
+                assert_eq!(cache.get_eviction_count(), 1);
             }
 
             // Reset the in-memory cache and progress and fetch
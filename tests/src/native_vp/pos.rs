@@ -141,16 +141,40 @@ pub fn init_pos(
 #[cfg(test)]
 mod tests {
 
-    use namada::ledger::pos::{PosParams, PosVP};
+    use borsh::BorshSerialize;
+    use namada::ledger::governance::storage as gov_storage;
+    use namada::ledger::pos::{PosBurnVp, PosParams, PosVP, SlashPoolVp};
+    use namada::proof_of_stake::storage::{
+        multisig_bond_counter_key, total_burned_key,
+        validator_commission_rate_announcement_key,
+    };
+    use namada::proof_of_stake::types::{
+        CommissionRateAnnouncement, MultisigBond, PendingMultisigBond,
+        SlashPoolDestination,
+    };
+    use namada::proof_of_stake::{
+        announce_validator_commission_rate_change, approve_multisig_bond,
+        burn_address, burn_slash_pool, change_validator_commission_rate,
+        pending_multisig_bonds_handle, redistribute_slash_pool,
+        slash_pool_address, validator_commission_rate_handle,
+    };
+    use namada::proto::Tx;
+    use namada::types::hash::Hash;
     use namada::types::key::common::PublicKey;
-    use namada::types::storage::Epoch;
-    use namada::types::{address, token};
+    use namada::types::key::RefTo;
+    use namada::types::storage::{Epoch, Key};
+    use namada::types::transaction::pos::{
+        CommissionChange, PosEvent, TX_CHANGE_COMMISSION_WASM,
+    };
+    use namada::types::{address, key, token};
     use namada_tx_prelude::proof_of_stake::parameters::testing::arb_pos_params;
-    use namada_tx_prelude::Address;
+    use namada_tx_prelude::{Address, StorageWrite, TxEnv};
     use proptest::prelude::*;
     use proptest::prop_state_machine;
     use proptest::state_machine::{AbstractStateMachine, StateMachineTest};
     use proptest::test_runner::Config;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
     use test_log::test;
 
     use super::testing::{
@@ -159,7 +183,7 @@ mod tests {
     };
     use super::*;
     use crate::native_vp::TestNativeVpEnv;
-    use crate::tx::tx_host_env;
+    use crate::tx::{self, tx_host_env};
 
     prop_state_machine! {
         #![proptest_config(Config {
@@ -567,6 +591,602 @@ mod tests {
                 })
         }
     }
+
+    /// Initialize PoS with a single validator with the given commission rate
+    /// and max commission rate change and return its address.
+    fn init_commission_test(
+        commission_rate: Decimal,
+        max_commission_rate_change: Decimal,
+    ) -> (Address, PosParams) {
+        init_commission_test_with_params(
+            commission_rate,
+            max_commission_rate_change,
+            PosParams::default(),
+        )
+    }
+
+    /// Like [`init_commission_test`], but with the given PoS parameters.
+    fn init_commission_test_with_params(
+        commission_rate: Decimal,
+        max_commission_rate_change: Decimal,
+        params: PosParams,
+    ) -> (Address, PosParams) {
+        let validator = address::testing::established_address_1();
+        let genesis_validators = [GenesisValidator {
+            address: validator.clone(),
+            tokens: token::Amount::from(1_000_000),
+            consensus_key: key::testing::keypair_1().ref_to(),
+            commission_rate,
+            max_commission_rate_change,
+        }];
+        init_pos(&genesis_validators[..], &params, Epoch(0));
+        (validator, params)
+    }
+
+    /// Run the PoS VP on the changes applied in the tx env.
+    fn validate_pos_tx() -> bool {
+        let tx_env = tx_host_env::take();
+        let vp_env = TestNativeVpEnv::from_tx_env(tx_env, address::POS);
+        vp_env
+            .validate_tx(PosVP::new)
+            .expect("Validation of PoS changes must not fail!")
+    }
+
+    /// Test that a commission rate change within the validator's max change
+    /// is accepted.
+    #[test]
+    fn test_commission_rate_change_valid() {
+        let (validator, _params) = init_commission_test(dec!(0.05), dec!(0.01));
+
+        change_validator_commission_rate(
+            tx::ctx(),
+            &validator,
+            dec!(0.06),
+            Epoch(0),
+        )
+        .unwrap();
+
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept a valid commission rate change"
+        );
+    }
+
+    /// Test that a commission rate change larger than the validator's max
+    /// change is rejected.
+    #[test]
+    fn test_commission_rate_change_too_large() {
+        let (validator, params) = init_commission_test(dec!(0.05), dec!(0.01));
+
+        // Bypass the checks in `change_validator_commission_rate`
+        validator_commission_rate_handle(&validator)
+            .set(tx::ctx(), dec!(0.07), Epoch(0), params.pipeline_len)
+            .unwrap();
+
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a commission rate change larger than the max \
+             change"
+        );
+    }
+
+    /// Set the tx in the tx env to a commission change tx with the given
+    /// data, whose code hash is stored as the commission change wasm's hash.
+    fn set_commission_change_tx(change: CommissionChange) {
+        tx_host_env::with(|tx_env| {
+            let code = b"tx_change_validator_commission".to_vec();
+            let code_hash = Hash::sha256(&code);
+            tx_env
+                .wl_storage
+                .storage
+                .write(
+                    &Key::wasm_hash(TX_CHANGE_COMMISSION_WASM),
+                    code_hash.to_vec(),
+                )
+                .unwrap();
+            tx_env.tx = Tx::new(
+                code,
+                Some(change.try_to_vec().unwrap()),
+                tx_env.wl_storage.storage.chain_id.clone(),
+                None,
+            )
+            .sign(&key::testing::keypair_1());
+        });
+    }
+
+    /// Test that a commission change tx may only change the commission rate
+    /// of the validator in its data.
+    #[test]
+    fn test_commission_change_tx_data() {
+        let (validator, _params) = init_commission_test(dec!(0.05), dec!(0.01));
+        set_commission_change_tx(CommissionChange {
+            validator: validator.clone(),
+            new_rate: dec!(0.06),
+        });
+        change_validator_commission_rate(
+            tx::ctx(),
+            &validator,
+            dec!(0.06),
+            Epoch(0),
+        )
+        .unwrap();
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept a commission change of the tx's validator"
+        );
+
+        let (validator, _params) = init_commission_test(dec!(0.05), dec!(0.01));
+        set_commission_change_tx(CommissionChange {
+            validator: address::testing::established_address_2(),
+            new_rate: dec!(0.06),
+        });
+        change_validator_commission_rate(
+            tx::ctx(),
+            &validator,
+            dec!(0.06),
+            Epoch(0),
+        )
+        .unwrap();
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a commission change of another validator"
+        );
+    }
+
+    /// Test that a PoS event emitted by a tx must match the changes that the
+    /// tx applied.
+    #[test]
+    fn test_emitted_pos_events() {
+        let (validator, params) = init_commission_test(dec!(0.05), dec!(0.01));
+        let amount = token::Amount::from(1_000);
+        let bond = PosEvent::Bond {
+            source: validator.clone(),
+            validator: validator.clone(),
+            amount,
+            epoch: Epoch(params.pipeline_len),
+        };
+        tx_host_env::with(|tx_env| {
+            let native_token = tx_env.wl_storage.storage.native_token.clone();
+            tx_env.credit_tokens(&validator, &native_token, None, amount);
+        });
+        tx::ctx().bond_tokens(None, &validator, amount).unwrap();
+        tx::ctx().emit_pos_event(&bond).unwrap();
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept a bond event that matches the bond"
+        );
+
+        init_commission_test(dec!(0.05), dec!(0.01));
+        tx::ctx().emit_pos_event(&bond).unwrap();
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a bond event without a bond"
+        );
+
+        init_commission_test(dec!(0.05), dec!(0.01));
+        tx::ctx()
+            .emit_pos_event(&PosEvent::Slash {
+                validator: validator.clone(),
+                slash_type: "DuplicateVote".to_string(),
+                rate: dec!(0.1),
+                epoch: Epoch(0),
+            })
+            .unwrap();
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a slash event emitted by a tx"
+        );
+    }
+
+    /// Test that a negative commission rate is rejected.
+    #[test]
+    fn test_commission_rate_change_negative() {
+        let (validator, params) = init_commission_test(dec!(0.005), dec!(0.01));
+
+        // Bypass the checks in `change_validator_commission_rate`
+        validator_commission_rate_handle(&validator)
+            .set(tx::ctx(), dec!(-0.001), Epoch(0), params.pipeline_len)
+            .unwrap();
+
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a negative commission rate"
+        );
+    }
+
+    /// Test that changing the commission rate to the current rate is a no-op
+    /// that is accepted.
+    #[test]
+    fn test_commission_rate_change_same_rate() {
+        let (validator, _params) = init_commission_test(dec!(0.05), dec!(0.01));
+
+        change_validator_commission_rate(
+            tx::ctx(),
+            &validator,
+            dec!(0.05),
+            Epoch(0),
+        )
+        .unwrap();
+
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept a commission rate change to the same rate"
+        );
+    }
+
+    /// Test that an announced commission rate change is accepted once its
+    /// notice period has passed.
+    #[test]
+    fn test_commission_rate_change_announced() {
+        let params = PosParams {
+            commission_change_notice_epochs: 2,
+            ..Default::default()
+        };
+        let (validator, params) =
+            init_commission_test_with_params(dec!(0.05), dec!(0.01), params);
+
+        announce_validator_commission_rate_change(
+            tx::ctx(),
+            &validator,
+            dec!(0.06),
+            Epoch(0),
+        )
+        .unwrap();
+        tx_host_env::commit_tx_and_block();
+
+        let current_epoch = Epoch(params.commission_change_notice_epochs);
+        tx_host_env::with(|tx_env| {
+            tx_env.wl_storage.storage.block.epoch = current_epoch;
+        });
+        change_validator_commission_rate(
+            tx::ctx(),
+            &validator,
+            dec!(0.06),
+            current_epoch,
+        )
+        .unwrap();
+
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept an announced commission rate change"
+        );
+    }
+
+    /// Test that a commission rate change that wasn't announced is rejected
+    /// when a notice period is required.
+    #[test]
+    fn test_commission_rate_change_not_announced() {
+        let params = PosParams {
+            commission_change_notice_epochs: 2,
+            ..Default::default()
+        };
+        let (validator, params) =
+            init_commission_test_with_params(dec!(0.05), dec!(0.01), params);
+
+        // Bypass the checks in `change_validator_commission_rate`
+        validator_commission_rate_handle(&validator)
+            .set(tx::ctx(), dec!(0.06), Epoch(0), params.pipeline_len)
+            .unwrap();
+
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a commission rate change that wasn't announced"
+        );
+    }
+
+    /// Test that a commission rate change announced in a past epoch is
+    /// rejected, as it would shorten the notice period.
+    #[test]
+    fn test_commission_rate_announcement_backdated() {
+        let (validator, _params) = init_commission_test(dec!(0.05), dec!(0.01));
+        tx_host_env::with(|tx_env| {
+            tx_env.wl_storage.storage.block.epoch = Epoch(2);
+        });
+
+        // Bypass `announce_validator_commission_rate_change`
+        tx::ctx()
+            .write(
+                &validator_commission_rate_announcement_key(&validator),
+                CommissionRateAnnouncement {
+                    announced_epoch: Epoch(0),
+                    new_rate: dec!(0.06),
+                },
+            )
+            .unwrap();
+
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a backdated commission rate announcement"
+        );
+    }
+
+    /// Initialize PoS with a single validator and return a multisig bond to
+    /// it from two sources, whose owner has the bonded tokens.
+    fn init_multisig_bond_test() -> MultisigBond {
+        let (validator, _params) = init_commission_test(dec!(0.05), dec!(0.01));
+        let owner = address::testing::established_address_2();
+        let amount = token::Amount::from(1_000);
+        tx_host_env::with(|tx_env| {
+            let native_token = tx_env.wl_storage.storage.native_token.clone();
+            tx_env.spawn_accounts([&owner]);
+            tx_env.credit_tokens(&owner, &native_token, None, amount);
+        });
+        MultisigBond {
+            sources: vec![owner, address::testing::established_address_3()],
+            threshold: 2,
+            validator,
+            amount,
+        }
+    }
+
+    /// Test that a multisig bond submitted by its owner is accepted.
+    #[test]
+    fn test_multisig_bond_submission() {
+        let bond = init_multisig_bond_test();
+
+        tx::ctx().submit_multisig_bond(bond).unwrap();
+
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept a submitted multisig bond"
+        );
+    }
+
+    /// Test that a pending multisig bond with forged approvals is rejected.
+    #[test]
+    fn test_multisig_bond_forged_approvals() {
+        let bond = init_multisig_bond_test();
+
+        let pending = PendingMultisigBond {
+            approvals: bond.sources.iter().cloned().collect(),
+            bond,
+            submission_epoch: Epoch(0),
+        };
+        pending_multisig_bonds_handle()
+            .insert(tx::ctx(), 0, pending)
+            .unwrap();
+        tx::ctx()
+            .write(&multisig_bond_counter_key(), 1_u64)
+            .unwrap();
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a pending multisig bond with approvals that \
+             are not from the owner"
+        );
+
+        // The multisig bond counter may only be incremented by one
+        init_multisig_bond_test();
+        tx::ctx()
+            .write(&multisig_bond_counter_key(), 2_u64)
+            .unwrap();
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a change of the multisig bond counter by more \
+             than one"
+        );
+    }
+
+    /// Test that an approval of a multisig bond is accepted from a source that
+    /// is a verifier of the tx, whose VP checks the signature, and rejected
+    /// otherwise.
+    #[test]
+    fn test_multisig_bond_approval() {
+        let bond = init_multisig_bond_test();
+        let co_signer = bond.sources[1].clone();
+        let id = tx::ctx().submit_multisig_bond(bond.clone()).unwrap();
+        tx_host_env::commit_tx_and_block();
+
+        assert!(tx::ctx().approve_multisig_bond(id, &co_signer).unwrap());
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept an approval from a verifier source"
+        );
+
+        let bond = init_multisig_bond_test();
+        let id = tx::ctx().submit_multisig_bond(bond).unwrap();
+        tx_host_env::commit_tx_and_block();
+
+        // Bypass the verifier insertion of the tx
+        approve_multisig_bond(tx::ctx(), id, &co_signer, Epoch(0)).unwrap();
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject an approval from a source that isn't a \
+             verifier"
+        );
+    }
+
+    /// Initialize PoS with a single validator, credit the slash pool with the
+    /// given amount of the native token and set the tx data to the given
+    /// governance proposal ID. When `is_accepted`, the proposal is marked as
+    /// being executed.
+    fn init_slash_pool_test(
+        balance: token::Amount,
+        proposal_id: u64,
+        is_accepted: bool,
+    ) {
+        init_commission_test(dec!(0.05), dec!(0.01));
+        tx_host_env::with(|tx_env| {
+            let native_token = tx_env.wl_storage.storage.native_token.clone();
+            tx_env.credit_tokens(
+                &slash_pool_address(),
+                &native_token,
+                None,
+                balance,
+            );
+            if is_accepted {
+                tx_env
+                    .wl_storage
+                    .storage
+                    .write(
+                        &gov_storage::get_proposal_execution_key(proposal_id),
+                        ().try_to_vec().unwrap(),
+                    )
+                    .unwrap();
+            }
+            tx_env.tx.data = Some(proposal_id.try_to_vec().unwrap());
+        });
+    }
+
+    /// Run the slash pool VP on the changes applied in the tx env.
+    fn validate_slash_pool_tx() -> bool {
+        let tx_env = tx_host_env::take();
+        let vp_env = TestNativeVpEnv::from_tx_env(tx_env, slash_pool_address());
+        vp_env
+            .validate_tx(SlashPoolVp::new)
+            .expect("Validation of slash pool changes must not fail!")
+    }
+
+    /// Test that a redistribution of the slashed tokens from an accepted
+    /// governance proposal is accepted.
+    #[test]
+    fn test_slash_pool_redistribution_from_proposal() {
+        init_slash_pool_test(token::Amount::from(1_000), 0, true);
+
+        redistribute_slash_pool(
+            tx::ctx(),
+            Epoch(0),
+            SlashPoolDestination::Treasury(
+                address::testing::established_address_2(),
+            ),
+            None,
+        )
+        .unwrap();
+
+        assert!(
+            validate_slash_pool_tx(),
+            "Slash pool VP must accept a redistribution from an accepted \
+             proposal"
+        );
+    }
+
+    /// Test that a redistribution of the slashed tokens outside of an
+    /// accepted governance proposal is rejected.
+    #[test]
+    fn test_slash_pool_redistribution_without_proposal() {
+        init_slash_pool_test(token::Amount::from(1_000), 0, false);
+
+        redistribute_slash_pool(
+            tx::ctx(),
+            Epoch(0),
+            SlashPoolDestination::Treasury(
+                address::testing::established_address_2(),
+            ),
+            None,
+        )
+        .unwrap();
+
+        assert!(
+            !validate_slash_pool_tx(),
+            "Slash pool VP must reject a redistribution without an accepted \
+             proposal"
+        );
+    }
+
+    /// Test that burning tokens from the slash pool, which transfers them from
+    /// the pool to the burn address, is accepted by the PoS VP.
+    #[test]
+    fn test_total_burned_with_burn() {
+        init_slash_pool_test(token::Amount::from(1_000), 0, true);
+
+        burn_slash_pool(tx::ctx(), token::Amount::from(500)).unwrap();
+
+        assert!(
+            validate_pos_tx(),
+            "PoS VP must accept a burn of the slash pool tokens"
+        );
+    }
+
+    /// Test that an increase of the total burned amount that isn't backed by
+    /// a burn of the slash pool tokens is rejected.
+    #[test]
+    fn test_total_burned_without_burn() {
+        init_slash_pool_test(token::Amount::from(1_000), 0, true);
+
+        tx::ctx()
+            .write(&total_burned_key(), token::Amount::from(500))
+            .unwrap();
+
+        assert!(
+            !validate_pos_tx(),
+            "PoS VP must reject a total burned change without a burn"
+        );
+    }
+
+    /// Test that any change of the slash pool other than a debit of its
+    /// native token balance is rejected, even from an accepted proposal.
+    #[test]
+    fn test_slash_pool_other_changes() {
+        init_slash_pool_test(token::Amount::from(1_000), 0, true);
+
+        // A credit of the native token balance
+        let native_token = tx_host_env::with(|tx_env| {
+            tx_env.wl_storage.storage.native_token.clone()
+        });
+        let balance_key =
+            token::balance_key(&native_token, &slash_pool_address());
+        tx::ctx()
+            .write(&balance_key, token::Amount::from(2_000))
+            .unwrap();
+        assert!(
+            !validate_slash_pool_tx(),
+            "Slash pool VP must reject a credit of its native token balance"
+        );
+
+        // A balance of another token
+        init_slash_pool_test(token::Amount::from(1_000), 0, true);
+        let balance_key = token::balance_key(
+            &address::testing::established_address_2(),
+            &slash_pool_address(),
+        );
+        tx::ctx()
+            .write(&balance_key, token::Amount::from(1_000))
+            .unwrap();
+        assert!(
+            !validate_slash_pool_tx(),
+            "Slash pool VP must reject a change of another token's balance"
+        );
+    }
+
+    /// Run the burn address VP on the changes applied in the tx env.
+    fn validate_burn_tx() -> bool {
+        let tx_env = tx_host_env::take();
+        let vp_env = TestNativeVpEnv::from_tx_env(tx_env, burn_address());
+        vp_env
+            .validate_tx(PosBurnVp::new)
+            .expect("Validation of burn address changes must not fail!")
+    }
+
+    /// Test that the burn address accepts the burned tokens, but rejects any
+    /// debit of its balance.
+    #[test]
+    fn test_burn_address_balance() {
+        init_slash_pool_test(token::Amount::from(1_000), 0, true);
+        burn_slash_pool(tx::ctx(), token::Amount::from(500)).unwrap();
+        assert!(
+            validate_burn_tx(),
+            "Burn address VP must accept a credit of its balance"
+        );
+
+        init_slash_pool_test(token::Amount::from(1_000), 0, true);
+        let native_token = tx_host_env::with(|tx_env| {
+            let native_token = tx_env.wl_storage.storage.native_token.clone();
+            tx_env.credit_tokens(
+                &burn_address(),
+                &native_token,
+                None,
+                token::Amount::from(500),
+            );
+            native_token
+        });
+        let balance_key = token::balance_key(&native_token, &burn_address());
+        tx::ctx()
+            .write(&balance_key, token::Amount::from(100))
+            .unwrap();
+        assert!(
+            !validate_burn_tx(),
+            "Burn address VP must reject a debit of its balance"
+        );
+    }
 }
 
 /// Testing helpers
@@ -433,6 +433,12 @@ mod native_tx_host_env {
         result_ptr: u64
     ));
     native_host_fn!(tx_emit_ibc_event(event_ptr: u64, event_len: u64));
+    native_host_fn!(tx_emit_pos_event(
+        event_type_ptr: u64,
+        event_type_len: u64,
+        event_data_ptr: u64,
+        event_data_len: u64,
+    ));
     native_host_fn!(tx_get_chain_id(result_ptr: u64));
     native_host_fn!(tx_get_block_height() -> u64);
     native_host_fn!(tx_get_tx_index() -> u32);
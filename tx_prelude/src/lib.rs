@@ -322,4 +322,21 @@ impl TxEnv for Ctx {
         };
         Ok(())
     }
+
+    fn emit_pos_event(
+        &mut self,
+        event: &transaction::pos::PosEvent,
+    ) -> Result<(), Error> {
+        let event_type = event.kind();
+        let event_data = BorshSerialize::try_to_vec(event).unwrap();
+        unsafe {
+            namada_tx_emit_pos_event(
+                event_type.as_ptr() as _,
+                event_type.len() as _,
+                event_data.as_ptr() as _,
+                event_data.len() as _,
+            )
+        };
+        Ok(())
+    }
 }
@@ -1,11 +1,16 @@
 //! Proof of Stake system integration with functions for transactions
 
+use namada_core::types::transaction::pos::PosEvent;
 use namada_core::types::transaction::InitValidator;
 use namada_core::types::{key, token};
 pub use namada_proof_of_stake::parameters::PosParams;
+use namada_proof_of_stake::types::{MultisigBond, SlashPoolDestination};
 use namada_proof_of_stake::{
-    become_validator, bond_tokens, change_validator_commission_rate,
-    read_pos_params, unbond_tokens, withdraw_tokens,
+    announce_validator_commission_rate_change, approve_multisig_bond,
+    become_validator, bond_tokens, burn_slash_pool,
+    change_validator_commission_rate, read_pos_params, read_total_burned,
+    redistribute_slash_pool, submit_multisig_bond, unbond_tokens,
+    withdraw_tokens,
 };
 pub use namada_proof_of_stake::{parameters, types};
 use rust_decimal::Decimal;
@@ -39,6 +44,29 @@ impl Ctx {
         unbond_tokens(self, source, validator, amount, current_epoch)
     }
 
+    /// Submit a multisig bond for approval by its sources. The bond's owner
+    /// has to sign the tx. On success, returns the ID of the bond.
+    pub fn submit_multisig_bond(
+        &mut self,
+        bond: MultisigBond,
+    ) -> EnvResult<u64> {
+        let current_epoch = self.get_block_epoch()?;
+        self.insert_verifier(bond.owner())?;
+        submit_multisig_bond(self, bond, current_epoch)
+    }
+
+    /// Approve a pending multisig bond by one of its sources, who has to sign
+    /// the tx. On success, returns `true` if the bond has been committed.
+    pub fn approve_multisig_bond(
+        &mut self,
+        id: u64,
+        approver: &Address,
+    ) -> EnvResult<bool> {
+        let current_epoch = self.get_block_epoch()?;
+        self.insert_verifier(approver)?;
+        approve_multisig_bond(self, id, approver, current_epoch)
+    }
+
     /// NEW: Withdraw unbonded tokens from a self-bond to a validator when
     /// `source` is `None` or equal to the `validator` address, or withdraw
     /// unbonded tokens delegated to the `validator` to the `source`.
@@ -61,6 +89,51 @@ impl Ctx {
         change_validator_commission_rate(self, validator, *rate, current_epoch)
     }
 
+    /// Announce a change of validator commission rate, which can only be
+    /// applied once the notice period has passed.
+    pub fn announce_validator_commission_rate_change(
+        &mut self,
+        validator: &Address,
+        rate: &Decimal,
+    ) -> TxResult {
+        let current_epoch = self.get_block_epoch()?;
+        announce_validator_commission_rate_change(
+            self,
+            validator,
+            *rate,
+            current_epoch,
+        )
+    }
+
+    /// Redistribute tokens from the PoS slash pool. This is only allowed from
+    /// the code of an accepted governance proposal.
+    pub fn redistribute_slash_pool(
+        &mut self,
+        destination: SlashPoolDestination,
+        amount: Option<token::Amount>,
+    ) -> TxResult {
+        let current_epoch = self.get_block_epoch()?;
+        let burned_before = read_total_burned(self)?;
+        redistribute_slash_pool(self, current_epoch, destination, amount)?;
+        let burned_after = read_total_burned(self)?;
+        if let Some(amount) = burned_after.checked_sub(burned_before) {
+            if !amount.is_zero() {
+                self.emit_pos_event(&PosEvent::Burn { amount })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Burn tokens from the PoS slash pool. This is only allowed from the
+    /// code of an accepted governance proposal.
+    pub fn burn_slash_pool(&mut self, amount: token::Amount) -> TxResult {
+        burn_slash_pool(self, amount)?;
+        if !amount.is_zero() {
+            self.emit_pos_event(&PosEvent::Burn { amount })?;
+        }
+        Ok(())
+    }
+
     /// NEW: Attempt to initialize a validator account. On success, returns the
     /// initialized validator account's address.
     pub fn init_validator(
@@ -78,6 +78,14 @@ pub mod tx {
         // Emit an IBC event
         pub fn namada_tx_emit_ibc_event(event_ptr: u64, event_len: u64);
 
+        // Emit a PoS event of the given type
+        pub fn namada_tx_emit_pos_event(
+            event_type_ptr: u64,
+            event_type_len: u64,
+            event_data_ptr: u64,
+            event_data_len: u64,
+        );
+
         // Get the chain ID
         pub fn namada_tx_get_chain_id(result_ptr: u64);
 
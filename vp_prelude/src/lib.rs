@@ -92,6 +92,53 @@ pub fn is_valid_tx(ctx: &Ctx, tx_data: &[u8]) -> VpResult {
     }
 }
 
+/// Checks if the given address approves a pending PoS multisig bond with the
+/// change of the given key. Such an approval must be signed by the address.
+pub fn is_multisig_bond_approval(
+    ctx: &Ctx,
+    key: &storage::Key,
+    addr: &Address,
+) -> VpResult {
+    if proof_of_stake::is_pending_multisig_bond_key(key).is_none() {
+        return Ok(false);
+    }
+    let pre: Option<proof_of_stake::PendingMultisigBond> = ctx.read_pre(key)?;
+    let post: Option<proof_of_stake::PendingMultisigBond> =
+        ctx.read_post(key)?;
+    let new_approvers = proof_of_stake::PendingMultisigBond::new_approvers(
+        pre.as_ref(),
+        post.as_ref(),
+    );
+    Ok(new_approvers.contains(addr))
+}
+
+/// Checks if the tx commits a pending PoS multisig bond with the given bond
+/// ID. The bond is applied on behalf of its owner without the owner's
+/// signature, because its approvals are checked by the PoS VP.
+pub fn is_multisig_bond_committed(
+    ctx: &Ctx,
+    bond_id: &proof_of_stake::BondId,
+    keys_changed: &BTreeSet<storage::Key>,
+) -> VpResult {
+    for key in keys_changed {
+        if proof_of_stake::is_pending_multisig_bond_key(key).is_none()
+            || ctx.has_key_post(key)?
+        {
+            continue;
+        }
+        let pre: Option<proof_of_stake::PendingMultisigBond> =
+            ctx.read_pre(key)?;
+        if let Some(pre) = pre {
+            if pre.bond.owner() == &bond_id.source
+                && pre.bond.validator == bond_id.validator
+            {
+                return accept();
+            }
+        }
+    }
+    reject()
+}
+
 /// Format and log a string in a debug build.
 ///
 /// In WASM target debug build, the message will be printed at the
@@ -1,3 +1,5 @@
+#[cfg(feature = "tx_approve_multisig_bond")]
+pub mod tx_approve_multisig_bond;
 #[cfg(feature = "tx_bond")]
 pub mod tx_bond;
 #[cfg(feature = "tx_change_validator_commission")]
@@ -12,6 +14,8 @@ pub mod tx_init_proposal;
 pub mod tx_init_validator;
 #[cfg(feature = "tx_reveal_pk")]
 pub mod tx_reveal_pk;
+#[cfg(feature = "tx_submit_multisig_bond")]
+pub mod tx_submit_multisig_bond;
 #[cfg(feature = "tx_transfer")]
 pub mod tx_transfer;
 #[cfg(feature = "tx_unbond")]
@@ -0,0 +1,21 @@
+//! A tx for a source of a pending PoS multisig bond to approve it.
+
+use namada_tx_prelude::transaction::pos::MultisigBondApproval;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Vec<u8>) -> TxResult {
+    let signed = SignedTxData::try_from_slice(&tx_data[..])
+        .wrap_err("failed to decode SignedTxData")?;
+    let data = signed.data.ok_or_err_msg("Missing data")?;
+    let MultisigBondApproval { id, approver } =
+        MultisigBondApproval::try_from_slice(&data[..])
+            .wrap_err("failed to decode MultisigBondApproval")?;
+    let committed = ctx.approve_multisig_bond(id, &approver)?;
+    debug_log!(
+        "Approved multisig bond with ID {}, committed: {}",
+        id,
+        committed
+    );
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! A tx to submit a PoS bond that is only committed once it's approved by
+//! enough of its sources.
+
+use namada_tx_prelude::proof_of_stake::types::MultisigBond;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Vec<u8>) -> TxResult {
+    let signed = SignedTxData::try_from_slice(&tx_data[..])
+        .wrap_err("failed to decode SignedTxData")?;
+    let data = signed.data.ok_or_err_msg("Missing data")?;
+    let bond = MultisigBond::try_from_slice(&data[..])
+        .wrap_err("failed to decode MultisigBond")?;
+    let id = ctx.submit_multisig_bond(bond)?;
+    debug_log!("Submitted multisig bond with ID {}", id);
+    Ok(())
+}
@@ -156,12 +156,23 @@ fn validate_tx(
                 let valid = match bond_id {
                     Some(bond_id) => {
                         // Bonds and unbonds changes for this address
-                        // must be signed
-                        bond_id.source != addr || *valid_sig
+                        // must be signed, unless a multisig bond approved by
+                        // its sources is committed
+                        bond_id.source != addr
+                            || *valid_sig
+                            || (proof_of_stake::is_bond_key(key).is_some()
+                                && is_multisig_bond_committed(
+                                    ctx,
+                                    &bond_id,
+                                    &keys_changed,
+                                )?)
                     }
                     None => {
-                        // Any other PoS changes are allowed without signature
-                        true
+                        // Multisig bond approvals by this address must be
+                        // signed. Any other PoS changes are allowed without
+                        // signature
+                        !is_multisig_bond_approval(ctx, key, &addr)?
+                            || *valid_sig
                     }
                 };
                 debug_log!(
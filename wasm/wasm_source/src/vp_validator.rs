@@ -131,12 +131,23 @@ fn validate_tx(
                 let valid_bond_or_unbond_change = match bond_id {
                     Some(bond_id) => {
                         // Bonds and unbonds changes for this address
-                        // must be signed
-                        bond_id.source != addr || *valid_sig
+                        // must be signed, unless a multisig bond approved by
+                        // its sources is committed
+                        bond_id.source != addr
+                            || *valid_sig
+                            || (proof_of_stake::is_bond_key(key).is_some()
+                                && is_multisig_bond_committed(
+                                    ctx,
+                                    &bond_id,
+                                    &keys_changed,
+                                )?)
                     }
                     None => {
-                        // Any other PoS changes are allowed without signature
-                        true
+                        // Multisig bond approvals by this address must be
+                        // signed. Any other PoS changes are allowed without
+                        // signature
+                        !is_multisig_bond_approval(ctx, key, &addr)?
+                            || *valid_sig
                     }
                 };
                 let comm =
@@ -523,6 +534,117 @@ mod tests {
         );
     }
 
+    /// Test that a change of the VP owner's commission rate without the
+    /// validator's signature is rejected.
+    #[test]
+    fn test_unsigned_commission_rate_change_rejected() {
+        // Init PoS genesis
+        let pos_params = PosParams::default();
+        let validator = address::testing::established_address_3();
+        let initial_stake = token::Amount::from(10_098_123);
+        let consensus_key = key::testing::keypair_2().ref_to();
+        let commission_rate = Decimal::new(5, 2);
+        let max_commission_rate_change = Decimal::new(1, 2);
+
+        let genesis_validators = [GenesisValidator {
+            address: validator.clone(),
+            tokens: initial_stake,
+            consensus_key,
+            commission_rate,
+            max_commission_rate_change,
+        }];
+
+        init_pos(&genesis_validators[..], &pos_params, Epoch(0));
+
+        // Initialize a tx environment
+        let mut tx_env = tx_host_env::take();
+
+        let secret_key = key::testing::keypair_1();
+        let public_key = secret_key.ref_to();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&validator]);
+        tx_env.write_public_key(&validator, &public_key);
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(validator.clone(), tx_env, |_address| {
+            tx::ctx()
+                .change_validator_commission_rate(
+                    &validator,
+                    &Decimal::new(6, 2),
+                )
+                .unwrap();
+        });
+
+        let vp_env = vp_host_env::take();
+        let tx_data: Vec<u8> = vec![];
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        let is_valid =
+            validate_tx(&CTX, tx_data, validator, keys_changed, verifiers)
+                .unwrap();
+        assert!(!is_valid);
+    }
+
+    /// Test that a change of the VP owner's commission rate with the
+    /// validator's signature is accepted.
+    #[test]
+    fn test_signed_commission_rate_change_accepted() {
+        // Init PoS genesis
+        let pos_params = PosParams::default();
+        let validator = address::testing::established_address_3();
+        let initial_stake = token::Amount::from(10_098_123);
+        let consensus_key = key::testing::keypair_2().ref_to();
+        let commission_rate = Decimal::new(5, 2);
+        let max_commission_rate_change = Decimal::new(1, 2);
+
+        let genesis_validators = [GenesisValidator {
+            address: validator.clone(),
+            tokens: initial_stake,
+            consensus_key,
+            commission_rate,
+            max_commission_rate_change,
+        }];
+
+        init_pos(&genesis_validators[..], &pos_params, Epoch(0));
+
+        // Initialize a tx environment
+        let mut tx_env = tx_host_env::take();
+
+        let secret_key = key::testing::keypair_1();
+        let public_key = secret_key.ref_to();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&validator]);
+        tx_env.write_public_key(&validator, &public_key);
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(validator.clone(), tx_env, |_address| {
+            tx::ctx()
+                .change_validator_commission_rate(
+                    &validator,
+                    &Decimal::new(6, 2),
+                )
+                .unwrap();
+        });
+
+        let mut vp_env = vp_host_env::take();
+        let tx = vp_env.tx.clone();
+        let signed_tx = tx.sign(&secret_key);
+        let tx_data: Vec<u8> = signed_tx.data.as_ref().cloned().unwrap();
+        vp_env.tx = signed_tx;
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, tx_data, validator, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
     /// Test that a transfer on with accounts other than self is accepted.
     #[test]
     fn test_transfer_between_other_parties_accepted() {